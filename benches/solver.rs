@@ -0,0 +1,153 @@
+use std::alloc::{ GlobalAlloc, Layout, System };
+use std::hint::black_box;
+use std::sync::atomic::{ AtomicUsize, Ordering };
+use std::time::Instant;
+use solv_a_line::solver_config::{ Engine, SolverConfig };
+use solv_a_line::generator::solved_grid;
+use solv_a_line::random::{ RandomSource, SplitMix64 };
+use solv_a_line::sudoku_board::SudokuBoard;
+use solv_a_line::sudoku_solver::{ SearchScratch, SudokuSolver };
+use solv_a_line::uniqueness::{ is_unique, Uniqueness };
+
+// Run with `cargo bench`, add `--features simd` to time the vectorized `all_spaces_valid` and `--features parallel` for `is_unique_parallel`. Criterion isn't a dependency, so this reports plain averages over a fixed number of iterations.
+
+const EASY: [u8; 81] = [
+    0,7,3, 8,9,4, 5,1,2,
+    9,1,2, 7,3,5, 4,8,6,
+    8,4,5, 0,0,2, 9,7,3,
+    7,9,8, 2,6,1, 3,5,4,
+    5,2,6, 4,7,3, 8,9,1,
+    1,3,4, 5,8,9, 2,6,7,
+    4,6,9, 0,2,8, 7,3,5,
+    2,8,7, 3,5,6, 1,4,9,
+    3,5,1, 9,4,7, 6,2,0
+];
+
+const MEDIUM: [u8; 81] = [
+    7,8,0, 4,0,0, 1,2,0,
+    6,0,0, 0,7,5, 0,0,9,
+    0,0,0, 6,0,1, 0,7,8,
+    0,0,7, 0,4,0, 2,6,0,
+    0,0,1, 0,5,0, 9,3,0,
+    9,0,4, 0,6,0, 0,0,5,
+    0,7,0, 3,0,0, 0,1,2,
+    1,2,0, 0,0,7, 4,0,0,
+    0,4,9, 2,0,6, 0,0,7
+];
+
+const HARD: [u8; 81] = [
+    0,0,0, 0,0,0, 0,0,0,
+    0,0,2, 0,0,5, 0,4,0,
+    1,0,8, 0,4,0, 0,0,0,
+    0,0,0, 0,0,0, 4,0,3,
+    0,0,6, 0,5,0, 0,0,1,
+    0,0,0, 0,2,0, 0,0,6,
+    3,0,1, 0,0,0, 0,8,0,
+    2,0,7, 0,0,0, 6,0,0,
+    0,0,0, 0,0,6, 1,3,9
+];
+
+// `solved_grid(seed)` with `holes` random spaces emptied, the same boards as `strategies::masked_puzzle`
+fn masked_puzzle(seed: u64, holes: usize) -> SudokuBoard {
+    let mut board = solved_grid(seed);
+    let mut spaces: Vec<usize> = (0..81).collect();
+    SplitMix64::new(seed ^ 0x5EED).shuffle(&mut spaces);
+    for index in spaces.into_iter().take(holes) {
+        board.clear_value(index / 9, index % 9);
+    }
+    return board;
+}
+
+// A masked puzzle with one value repeated in a row, so `all_spaces_valid` is always false, as `strategies::invalid_board`
+fn invalid_board(seed: u64) -> SudokuBoard {
+    let mut board = masked_puzzle(seed, 40);
+    let mut random = SplitMix64::new(seed ^ 0xBAD);
+    let row_index = random.below(9) as usize;
+    let (from, to) = (random.below(9) as usize, random.below(8) as usize);
+    let to = if to >= from { to + 1 } else { to };
+    let value = match board[(row_index, from)] {
+        0 => 1 + random.below(9) as u8,
+        value => value
+    };
+    board.set_value(row_index, from, value);
+    board.set_value(row_index, to, value);
+    return board;
+}
+
+// Counts every allocation so the scratch entries can report how many they save
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocations<F: FnOnce()>(routine: F) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    routine();
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn bench<F: FnMut()>(name: &str, iterations: u32, mut routine: F) {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        routine();
+    }
+    let elapsed = start.elapsed();
+    println!("{:<40} {:>12.3} µs/iter ({} iterations)", name, elapsed.as_secs_f64() * 1e6 / iterations as f64, iterations);
+}
+
+fn main() {
+    let easy_board = SudokuBoard::new(&EASY);
+    let medium_board = SudokuBoard::new(&MEDIUM);
+    let hard_board = SudokuBoard::new(&HARD);
+
+    bench("constructor easy", 100_000, || { black_box(SudokuSolver::new(black_box(&easy_board))); });
+    bench("constructor + solve easy", 100_000, || { black_box(SudokuSolver::new(black_box(&easy_board)).solve()); });
+    bench("constructor + solve medium", 10_000, || { black_box(SudokuSolver::new(black_box(&medium_board)).solve()); });
+    bench("constructor + solve hard", 10, || { black_box(SudokuSolver::new(black_box(&hard_board)).solve()); });
+    bench("constructor + solve_owned easy", 100_000, || { black_box(SudokuSolver::new(black_box(&easy_board)).solve_owned().unwrap()); });
+
+    let recursive = SolverConfig::new().engine(Engine::Recursive);
+    bench("recursive: constructor + solve easy", 100_000, || { black_box(SudokuSolver::with_config(black_box(&easy_board), recursive).solve()); });
+    bench("recursive: constructor + solve medium", 10_000, || { black_box(SudokuSolver::with_config(black_box(&medium_board), recursive).solve()); });
+    bench("recursive: constructor + solve hard", 10, || { black_box(SudokuSolver::with_config(black_box(&hard_board), recursive).solve()); });
+
+    // `all_spaces_valid` is the early exit scalar scan without `simd` and `simd::houses_valid` with it, so running this
+    // entry in both builds compares the two paths on the same 10k boards. `validate` always takes the scalar scan and
+    // fills a report on top.
+    let batch: Vec<SudokuBoard> = (0..10_000).map(|seed| if seed % 10 == 0 { invalid_board(seed) } else { masked_puzzle(seed, 50) }).collect();
+    let valid_path = if cfg!(feature = "simd") { "all_spaces_valid 10k boards (simd)" } else { "all_spaces_valid 10k boards (scalar)" };
+    bench(valid_path, 100, || { black_box(batch.iter().filter(|board| black_box(board).all_spaces_valid()).count()); });
+    bench("validate().is_valid() 10k boards", 100, || { black_box(batch.iter().filter(|board| black_box(board).validate().is_valid()).count()); });
+
+    // 25 to 30 clues, the range puzzle generation spends most of its uniqueness checks in
+    let puzzles: Vec<SudokuBoard> = (0..200).map(|seed| masked_puzzle(seed, 51 + (seed % 6) as usize)).collect();
+    bench("is_unique 200 boards", 10, || { black_box(puzzles.iter().filter(|board| is_unique(black_box(board)) == Uniqueness::Unique).count()); });
+    #[cfg(feature = "parallel")]
+    bench("is_unique_parallel 200 boards", 10, || { black_box(puzzles.iter().filter(|board| solv_a_line::uniqueness::is_unique_parallel(black_box(board)) == Uniqueness::Unique).count()); });
+    bench("count_solutions(2) 200 boards", 10, || { black_box(puzzles.iter().filter(|board| SudokuSolver::new(black_box(board)).count_solutions(2) == 1).count()); });
+    bench("solve + count_solutions(2) 200 boards", 10, || { black_box(puzzles.iter().filter(|board| { let solver = SudokuSolver::new(black_box(board)); solver.try_solve().is_ok() && solver.count_solutions(2) == 1 }).count()); });
+    bench("solve_unique 200 boards", 10, || { black_box(puzzles.iter().filter(|board| SudokuSolver::new(black_box(board)).solve_unique().is_ok()).count()); });
+
+    // 1000 easy puzzles, once through the usual constructor and once reusing one scratch for every search
+    let easy_batch: Vec<SudokuBoard> = (0..1000).map(|seed| masked_puzzle(seed, 30)).collect();
+    let mut scratch = SearchScratch::new();
+    let fresh = |board: &SudokuBoard| { black_box(SudokuSolver::new(black_box(board)).try_solve().is_ok()); };
+    let mut reused = |board: &SudokuBoard| { black_box(SudokuSolver::new_unchecked(black_box(board)).solve_with_scratch(&mut scratch).is_ok()); };
+    bench("constructor + try_solve 1000 easy", 10, || easy_batch.iter().for_each(fresh));
+    bench("solve_with_scratch 1000 easy", 10, || easy_batch.iter().for_each(&mut reused));
+    println!("{:<40} {:>12} allocations", "constructor + try_solve 1000 easy", allocations(|| easy_batch.iter().for_each(fresh)));
+    println!("{:<40} {:>12} allocations", "solve_with_scratch 1000 easy", allocations(|| easy_batch.iter().for_each(&mut reused)));
+}
@@ -0,0 +1,226 @@
+use crate::candidate_board::CandidateBoard;
+use crate::sudoku_board::SudokuBoard;
+use crate::sudoku_solver::SudokuSolver;
+use crate::techniques::find_singles;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Analysis {
+    pub candidate_counts: [[u8; 9]; 9], // Candidates of every space in the starting position, 0 for givens
+    pub stall_points: Vec<(usize, usize)>, // Where singles ran out, the unsolved spaces with the fewest candidates left
+    pub unlock_scores: [[u32; 9]; 9] // For every space the logical solution filled, how many new singles placing it opened up
+}
+
+impl Analysis {
+    // The placement that opened up the most singles, ties broken row-major
+    pub fn best_unlock(&self) -> Option<(usize, usize)> {
+        return (0..81).map(|index| (index / 9, index % 9))
+            .filter(|&(row_index, column_index)| self.unlock_scores[row_index][column_index] > 0)
+            .max_by_key(|&(row_index, column_index)| (self.unlock_scores[row_index][column_index], 80 - 9 * row_index - column_index));
+    }
+}
+
+// Replays the logical solution from `SudokuSolver::rate`, tracking which spaces were singles before and after each step
+pub fn analyze(solver: &SudokuSolver) -> Analysis {
+    let starting_candidates = CandidateBoard::new(&solver.board);
+    let mut candidate_counts = [[0; 9]; 9];
+    for (row_index, row) in candidate_counts.iter_mut().enumerate() {
+        for (column_index, count) in row.iter_mut().enumerate() {
+            *count = starting_candidates.get(row_index, column_index).len() as u8;
+        }
+    }
+
+    let mut board = SudokuBoard::copy(&solver.board);
+    let mut unlock_scores = [[0; 9]; 9];
+    let mut singles = single_spaces(&board);
+    for step in solver.rate().steps {
+        board.set_value(step.row, step.column, step.value);
+        let next_singles = single_spaces(&board);
+        unlock_scores[step.row][step.column] = next_singles.iter().filter(|space| !singles.contains(space)).count() as u32;
+        singles = next_singles;
+    }
+
+    let mut stall_points = Vec::new();
+    if !board.all_spaces_solved() {
+        let candidate_board = CandidateBoard::new(&board);
+        let unsolved_spaces = board.get_unsolved_spaces();
+        let fewest = unsolved_spaces.iter().map(|&(row_index, column_index)| candidate_board.get(row_index, column_index).len()).min().unwrap();
+        stall_points = unsolved_spaces.into_iter().filter(|&(row_index, column_index)| candidate_board.get(row_index, column_index).len() == fewest).collect();
+    }
+
+    return Analysis { candidate_counts, stall_points, unlock_scores };
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Backdoor {
+    // Placing the solution's value in each witness space lets singles alone finish the puzzle
+    Found { size: usize, witness: Vec<((usize, usize), u8)> },
+    Unknown // Larger than the size searched, out of budget, or the puzzle has no solution
+}
+
+impl SudokuSolver {
+    // The fewest spaces to guess (correctly) before naked and hidden singles solve the rest, trying every combination
+    // of up to `max_size` spaces. `budget` caps the singles runs tried, each one costs about a full logical solve.
+    pub fn backdoor(&self, max_size: usize, budget: usize) -> Backdoor {
+        let solution = match self.try_solve() {
+            Ok(solution) => solution,
+            Err(_) => return Backdoor::Unknown
+        };
+
+        let mut runs = 0;
+        let unsolved_spaces = self.board.get_unsolved_spaces();
+        for size in 0..=max_size.min(unsolved_spaces.len()) {
+            let mut combination: Vec<usize> = (0..size).collect();
+            loop {
+                if runs == budget {
+                    return Backdoor::Unknown;
+                }
+                runs += 1;
+
+                let witness: Vec<((usize, usize), u8)> = combination.iter().map(|&index| (unsolved_spaces[index], solution[unsolved_spaces[index]])).collect();
+                let mut board = SudokuBoard::copy(&self.board);
+                for &((row_index, column_index), value) in witness.iter() {
+                    board.set_value(row_index, column_index, value);
+                }
+                if solves_with_singles(&mut board) {
+                    return Backdoor::Found { size, witness };
+                }
+
+                if !next_combination(&mut combination, unsolved_spaces.len()) {
+                    break;
+                }
+            }
+        }
+        return Backdoor::Unknown;
+    }
+}
+
+// Fills in every single at once, round after round, which is safe here because every position comes from a solution
+fn solves_with_singles(board: &mut SudokuBoard) -> bool {
+    loop {
+        let hints = find_singles(&CandidateBoard::new(board));
+        if hints.is_empty() {
+            return board.all_spaces_solved();
+        }
+        for hint in hints {
+            board.set_value(hint.row, hint.column, hint.value);
+        }
+    }
+}
+
+// Advances to the next `k` element combination of `0..n` in lexicographic order
+pub(crate) fn next_combination(combination: &mut [usize], n: usize) -> bool {
+    let k = combination.len();
+    for position in (0..k).rev() {
+        if combination[position] < n - k + position {
+            combination[position] += 1;
+            for later in position + 1..k {
+                combination[later] = combination[later - 1] + 1;
+            }
+            return true;
+        }
+    }
+    return false;
+}
+
+fn single_spaces(board: &SudokuBoard) -> Vec<(usize, usize)> {
+    return find_singles(&CandidateBoard::new(board)).into_iter().map(|hint| (hint.row, hint.column)).collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{ hard, medium };
+
+    #[test]
+    fn analyze_medium_works() {
+        let solver = SudokuSolver::new(&medium());
+
+        let analysis = analyze(&solver);
+
+        assert_eq!(analysis.candidate_counts[0][0], 0);
+        assert_eq!(analysis.candidate_counts[0][2], 2); // 3 or 5
+        assert_eq!(analysis.candidate_counts[0][4], 2); // 3 or 9
+        assert_eq!(analysis.candidate_counts[1][1], 2); // 1 or 3
+        assert!(analysis.stall_points.is_empty());
+        assert!(solver.board.get_unsolved_spaces().iter().any(|&(row_index, column_index)| analysis.unlock_scores[row_index][column_index] > 0));
+        assert!((0..81).all(|index| solver.board[(index / 9, index % 9)] == 0 || analysis.unlock_scores[index / 9][index % 9] == 0));
+
+        let best_unlock = analysis.best_unlock().unwrap();
+        assert!(analysis.unlock_scores.iter().flatten().all(|score| *score <= analysis.unlock_scores[best_unlock.0][best_unlock.1]));
+    }
+
+    #[test]
+    fn analyze_hard_stalls() {
+        let solver = SudokuSolver::new(&hard());
+
+        let analysis = analyze(&solver);
+
+        assert!(!analysis.stall_points.is_empty());
+        assert!(analysis.stall_points.iter().all(|&(row_index, column_index)| analysis.candidate_counts[row_index][column_index] >= 2));
+    }
+
+    #[test]
+    fn backdoor_singles_solvable() {
+        let solver = SudokuSolver::new(&medium());
+
+        assert_eq!(solver.backdoor(2, 1000), Backdoor::Found { size: 0, witness: Vec::new() });
+    }
+
+    #[test]
+    fn backdoor_one_guess() {
+        let solver = SudokuSolver::new(&SudokuBoard::new(&[
+            9,0,0, 0,1,0, 8,0,0,
+            0,0,7, 0,5,0, 0,0,6,
+            3,0,0, 0,0,0, 0,0,7,
+            6,4,0, 5,0,0, 0,7,0,
+            0,5,8, 0,0,0, 9,0,0,
+            0,0,0, 0,7,4, 0,0,5,
+            0,0,0, 0,0,0, 4,0,0,
+            0,3,0, 0,0,9, 5,0,0,
+            0,0,0, 2,8,6, 0,0,0
+        ]));
+
+        let witness = match solver.backdoor(2, 1000) {
+            Backdoor::Found { size: 1, witness } => witness,
+            other => panic!("Expected a backdoor of size 1, got {:?}", other)
+        };
+
+        let mut board = SudokuBoard::copy(&solver.board);
+        let ((row_index, column_index), value) = witness[0];
+        board.set_value(row_index, column_index, value);
+        assert!(!SudokuSolver::new(&board).rate().guessing_required);
+        assert_eq!(solver.backdoor(1, 1), Backdoor::Unknown);
+    }
+
+    #[test]
+    fn backdoor_larger_than_searched() {
+        let solver = SudokuSolver::new(&hard());
+
+        assert_eq!(solver.backdoor(1, 1000), Backdoor::Unknown);
+    }
+
+    #[test]
+    fn next_combination_works() {
+        let mut combination = vec![0, 1];
+        let mut combinations = vec![combination.clone()];
+        while next_combination(&mut combination, 4) {
+            combinations.push(combination.clone());
+        }
+
+        assert_eq!(combinations, vec![vec![0, 1], vec![0, 2], vec![0, 3], vec![1, 2], vec![1, 3], vec![2, 3]]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let mut board = SudokuSolver::new(&crate::test_fixtures::easy()).solve(); // Only R1C1 and R1C2 empty
+        board.clear_value(0, 0);
+        board.clear_value(0, 1);
+        let analysis = analyze(&SudokuSolver::new(&board));
+        let json = serde_json::to_string(&analysis).unwrap();
+
+        assert_eq!(json, "{\"candidate_counts\":[[1,1,0,0,0,0,0,0,0],[0,0,0,0,0,0,0,0,0],[0,0,0,0,0,0,0,0,0],[0,0,0,0,0,0,0,0,0],[0,0,0,0,0,0,0,0,0],[0,0,0,0,0,0,0,0,0],[0,0,0,0,0,0,0,0,0],[0,0,0,0,0,0,0,0,0],[0,0,0,0,0,0,0,0,0]],\"stall_points\":[],\"unlock_scores\":[[0,0,0,0,0,0,0,0,0],[0,0,0,0,0,0,0,0,0],[0,0,0,0,0,0,0,0,0],[0,0,0,0,0,0,0,0,0],[0,0,0,0,0,0,0,0,0],[0,0,0,0,0,0,0,0,0],[0,0,0,0,0,0,0,0,0],[0,0,0,0,0,0,0,0,0],[0,0,0,0,0,0,0,0,0]]}");
+        assert_eq!(serde_json::from_str::<Analysis>(&json).unwrap(), analysis);
+    }
+}
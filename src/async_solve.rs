@@ -0,0 +1,122 @@
+use std::future::Future;
+use std::panic;
+use std::pin::Pin;
+use std::task::{ Context, Poll };
+use tokio::task::{ self, JoinHandle };
+use crate::sudoku_board::SudokuBoard;
+use crate::sudoku_solver::{ SolveControl, SolveError, SudokuSolver };
+
+enum State {
+    Ready(Option<Result<SudokuBoard, SolveError>>), // Taken once the future has returned it
+    Searching(JoinHandle<Result<SudokuBoard, SolveError>>)
+}
+
+// Runs the search on Tokio's blocking pool so the async workers stay free. Dropping the future cancels the search,
+// which is what makes `tokio::time::timeout` and `select!` stop the work.
+pub struct SolveFuture<'a> {
+    solver: &'a SudokuSolver,
+    control: SolveControl,
+    state: State
+}
+
+impl SolveFuture<'_> {
+    pub fn control(&self) -> &SolveControl {
+        return &self.control;
+    }
+}
+
+impl Future for SolveFuture<'_> {
+    type Output = Result<SudokuBoard, SolveError>;
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        let result = match &mut self.state {
+            State::Ready(result) => result.take().expect("SolveFuture polled after it completed"),
+            State::Searching(handle) => match Pin::new(handle).poll(context) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Ok(result)) => result,
+                Poll::Ready(Err(error)) if error.is_panic() => panic::resume_unwind(error.into_panic()),
+                Poll::Ready(Err(_)) => Err(SolveError::Cancelled) // The runtime shut down under the search
+            }
+        };
+
+        if let Ok(solved_board) = &result {
+            self.solver.cache_solution(solved_board);
+        }
+        self.state = State::Ready(None);
+        return Poll::Ready(result);
+    }
+}
+
+impl Drop for SolveFuture<'_> {
+    fn drop(&mut self) {
+        self.control.cancel();
+    }
+}
+
+impl SudokuSolver {
+    // An already cached solution is returned straight away. Otherwise a copy of the solver searches on
+    // `tokio::task::spawn_blocking`, so this panics outside a Tokio runtime, and the solution is cached once the future
+    // hands it out.
+    pub fn solve_async(&self) -> SolveFuture<'_> {
+        let control = SolveControl::new();
+        if self.is_cached() {
+            return SolveFuture { solver: self, control, state: State::Ready(Some(self.try_solve())) };
+        }
+
+        let worker = SudokuSolver::with_config(&self.board, self.config());
+        let worker_control = control.clone();
+        let handle = task::spawn_blocking(move || worker.solve_with_control(&worker_control));
+
+        return SolveFuture { solver: self, control, state: State::Searching(handle) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{ hard, medium };
+    use std::mem;
+    use std::time::Duration;
+    use tokio::time;
+
+    fn assert_send<T: Send>(_: &T) {}
+
+    #[tokio::test]
+    async fn solve_async_works() {
+        let solver = SudokuSolver::new(&medium());
+
+        let future = solver.solve_async();
+        assert_send(&future);
+
+        assert_eq!(future.await, Ok(solver.solve()));
+        assert!(solver.is_cached());
+        assert_eq!(solver.solve_async().await, Ok(solver.solve()));
+    }
+
+    #[tokio::test]
+    async fn dropping_stops_the_worker() {
+        let solver = SudokuSolver::new(&hard());
+        let mut future = solver.solve_async();
+        let handle = match mem::replace(&mut future.state, State::Ready(None)) {
+            State::Searching(handle) => handle,
+            State::Ready(_) => panic!("Nothing was cached, the search should be running")
+        };
+
+        drop(future);
+
+        assert_eq!(handle.await.unwrap(), Err(SolveError::Cancelled));
+        assert!(!solver.is_cached());
+    }
+
+    #[tokio::test]
+    async fn timeout_race_works() {
+        let solver = SudokuSolver::new(&hard());
+        let future = solver.solve_async();
+        let control = future.control().clone();
+
+        assert!(time::timeout(Duration::ZERO, future).await.is_err());
+        assert!(control.is_cancelled());
+
+        assert_eq!(time::timeout(Duration::from_secs(60), solver.solve_async()).await, Ok(Ok(solver.solve())));
+    }
+}
@@ -0,0 +1,101 @@
+use std::panic;
+use std::thread::{ self, JoinHandle };
+use crate::sudoku_board::SudokuBoard;
+use crate::sudoku_solver::{ SolveControl, SolveError, SudokuSolver };
+
+// A solve running on its own thread. Dropping the handle cancels the search and waits for the thread to stop, so the
+// work is never left running detached.
+pub struct SolveHandle {
+    control: SolveControl,
+    thread: Option<JoinHandle<Result<SudokuBoard, SolveError>>>
+}
+
+impl SolveHandle {
+    pub fn is_finished(&self) -> bool {
+        return self.thread.as_ref().map_or(true, |thread| thread.is_finished());
+    }
+
+    // Iterations of the search so far, see `SolveControl::iterations`
+    pub fn progress(&self) -> u64 {
+        return self.control.iterations();
+    }
+
+    // The search stops at its next check, `join` then reports `SolveError::Cancelled`
+    pub fn cancel(&self) {
+        self.control.cancel();
+    }
+
+    pub fn join(mut self) -> Result<SudokuBoard, SolveError> {
+        let thread = self.thread.take().unwrap();
+        return match thread.join() {
+            Ok(result) => result,
+            Err(payload) => panic::resume_unwind(payload)
+        };
+    }
+}
+
+impl Drop for SolveHandle {
+    fn drop(&mut self) {
+        if let Some(thread) = self.thread.take() {
+            self.control.cancel();
+            let _ = thread.join();
+        }
+    }
+}
+
+impl SudokuSolver {
+    pub fn solve_in_background(self) -> SolveHandle {
+        let control = SolveControl::new();
+        let thread_control = control.clone();
+        let thread = thread::spawn(move || self.solve_with_control(&thread_control));
+
+        return SolveHandle { control, thread: Some(thread) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::hard;
+    use std::time::Duration;
+
+    #[test]
+    fn solve_in_background_works() {
+        let handle = SudokuSolver::new(&hard()).solve_in_background();
+
+        let mut last_progress = 0;
+        let mut progress_increases = 0;
+        while !handle.is_finished() {
+            let progress = handle.progress();
+            assert!(progress >= last_progress);
+            if progress > last_progress {
+                progress_increases += 1;
+            }
+            last_progress = progress;
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        assert!(progress_increases > 0);
+        assert!(handle.progress() > 0);
+        assert_eq!(handle.join(), Ok(SudokuSolver::new(&hard()).solve()));
+    }
+
+    #[test]
+    fn cancel_works() {
+        let handle = SudokuSolver::new(&hard()).solve_in_background();
+
+        handle.cancel();
+
+        assert_eq!(handle.join(), Err(SolveError::Cancelled));
+    }
+
+    #[test]
+    fn drop_cancels() {
+        let handle = SudokuSolver::new(&hard()).solve_in_background();
+        let control = handle.control.clone();
+
+        drop(handle);
+
+        assert!(control.is_cancelled());
+    }
+}
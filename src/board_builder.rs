@@ -0,0 +1,118 @@
+use std::error::Error;
+use std::fmt::{ self, Display, Formatter };
+use crate::sudoku_board::{ Conflict, SudokuBoard };
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    Conflicts(Vec<Conflict>) // Every duplicate in the board, in the order `validate` reports them
+}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        return match self {
+            BuildError::Conflicts(conflicts) => write!(f, "The board breaks the rules of Sudoku with {} duplicated value(s)", conflicts.len())
+        };
+    }
+}
+
+impl Error for BuildError {}
+
+// Collects values in any order without checking them against each other, mid-construction states are allowed to
+// break the rules. Everything is validated once in `build`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SudokuBoardBuilder {
+    values: [u8; 81]
+}
+
+impl Default for SudokuBoardBuilder {
+    fn default() -> SudokuBoardBuilder {
+        return SudokuBoardBuilder::new();
+    }
+}
+
+impl SudokuBoardBuilder {
+    pub fn new() -> SudokuBoardBuilder {
+        return SudokuBoardBuilder { values: [0; 81] };
+    }
+
+    // Later calls for the same space overwrite earlier ones, `0` empties it again
+    pub fn set(mut self, row_index: usize, column_index: usize, value: u8) -> SudokuBoardBuilder {
+        if row_index > 8 || column_index > 8 {
+            panic!("An invalid space was passed into 'set', it was ({}, {})", row_index, column_index);
+        }
+        if value > 9 {
+            panic!("All values must be [0..9] inclusive");
+        }
+
+        self.values[9 * row_index + column_index] = value;
+        return self;
+    }
+
+    // Every duplicate is reported at once through `BuildError::Conflicts`, not only the first one found
+    pub fn build(self) -> Result<SudokuBoard, BuildError> {
+        let board = self.build_unchecked();
+        let report = board.validate();
+        if !report.is_valid() {
+            return Err(BuildError::Conflicts(report.conflicts));
+        }
+        return Ok(board);
+    }
+
+    // Skips the rule check for data that's already known to be valid
+    pub fn build_unchecked(self) -> SudokuBoard {
+        return SudokuBoard::new(&self.values);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sudoku_board::House;
+
+    #[test]
+    fn build_out_of_order() {
+        let solution = "435269781682571493197834562826195347374682915951743628519326874248957136763418259";
+        let mut builder = SudokuBoardBuilder::new();
+        for index in (0..81).rev().step_by(2).chain((0..81).skip(1).step_by(2)) {
+            builder = builder.set(index / 9, index % 9, solution.as_bytes()[index] - b'0');
+        }
+
+        let board = builder.build().unwrap();
+
+        assert_eq!(board.to_line(), solution);
+        assert!(board.is_solved());
+    }
+
+    #[test]
+    fn build_reports_every_conflict() {
+        // Mid-construction the first row holds two 5s, later overwritten
+        let builder = SudokuBoardBuilder::new()
+            .set(0, 0, 5)
+            .set(0, 8, 5)
+            .set(0, 8, 0)
+            .set(4, 4, 9)
+            .set(4, 7, 9)
+            .set(8, 0, 2)
+            .set(6, 0, 2);
+
+        assert_eq!(builder.build(), Err(BuildError::Conflicts(vec![
+            Conflict { house: House::Row(4), value: 9, spaces: vec![(4, 4), (4, 7)] },
+            Conflict { house: House::Column(0), value: 2, spaces: vec![(6, 0), (8, 0)] },
+            Conflict { house: House::Nonet(6), value: 2, spaces: vec![(6, 0), (8, 0)] }
+        ])));
+    }
+
+    #[test]
+    fn build_unchecked_skips_validation() {
+        let board = SudokuBoardBuilder::new().set(0, 0, 5).set(0, 1, 5).build_unchecked();
+
+        assert_eq!(board[(0, 1)], 5);
+        assert!(!board.all_spaces_valid());
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_invalid_value() {
+        SudokuBoardBuilder::new().set(0, 0, 10);
+    }
+}
@@ -0,0 +1,147 @@
+use std::fmt::{ Display, Formatter, Result };
+use crate::peers::PEERS;
+use crate::sudoku_board::SudokuBoard;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CandidateSet(u16); // Bit `n` is set when `n` is a candidate, bits 0 and 10..16 are always clear
+
+impl CandidateSet {
+    pub const ALL: CandidateSet = CandidateSet(0b11_1111_1110);
+
+    pub fn new() -> CandidateSet {
+        return CandidateSet(0);
+    }
+
+    pub fn from_bits(bits: u16) -> CandidateSet {
+        return CandidateSet(bits & CandidateSet::ALL.0);
+    }
+
+    pub fn bits(&self) -> u16 {
+        return self.0;
+    }
+
+    pub fn contains(&self, value: u8) -> bool {
+        return (1..=9).contains(&value) && self.0 & (1 << value) != 0;
+    }
+
+    pub fn insert(&mut self, value: u8) {
+        if !(1..=9).contains(&value) {
+            panic!("Candidates must be [1..9] inclusive, it was {}", value);
+        }
+        self.0 |= 1 << value;
+    }
+
+    pub fn remove(&mut self, value: u8) {
+        if (1..=9).contains(&value) {
+            self.0 &= !(1 << value);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        return self.0.count_ones() as usize;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.0 == 0;
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = u8> {
+        let bits = self.0;
+        return (1..=9).filter(move |value| bits & (1 << value) != 0);
+    }
+}
+
+impl Display for CandidateSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        for value in self.iter() {
+            write!(f, "{}", value)?;
+        }
+        return Ok(());
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandidateBoard {
+    candidates: [CandidateSet; 81]
+}
+
+impl CandidateBoard {
+    pub fn new(sudoku_board: &SudokuBoard) -> CandidateBoard {
+        // A space's candidates are every value not already used by one of its peers. Solved spaces have none.
+        let mut candidates = [CandidateSet::new(); 81];
+        for row in 0..=8 {
+            for column in 0..=8 {
+                if sudoku_board.digit(row, column).is_some() {
+                    continue;
+                }
+
+                let mut space_candidates = CandidateSet::ALL;
+                for peer in PEERS[9 * row + column].iter() {
+                    space_candidates.remove(sudoku_board[(peer / 9, peer % 9)]);
+                }
+                candidates[9 * row + column] = space_candidates;
+            }
+        }
+
+        return CandidateBoard { candidates };
+    }
+
+    pub fn empty() -> CandidateBoard {
+        return CandidateBoard { candidates: [CandidateSet::new(); 81] };
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> CandidateSet {
+        return self.candidates[9 * row + column];
+    }
+
+    pub fn set(&mut self, row: usize, column: usize, candidates: CandidateSet) {
+        self.candidates[9 * row + column] = candidates;
+    }
+
+    pub fn remove(&mut self, row: usize, column: usize, value: u8) {
+        self.candidates[9 * row + column].remove(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::easy;
+
+    #[test]
+    fn candidate_set_works() {
+        let mut candidate_set = CandidateSet::new();
+        candidate_set.insert(9);
+        candidate_set.insert(1);
+        candidate_set.insert(5);
+        candidate_set.remove(5);
+        candidate_set.remove(0);
+
+        assert_eq!(candidate_set.iter().collect::<Vec<u8>>(), vec![1, 9]);
+        assert_eq!(candidate_set.len(), 2);
+        assert!(candidate_set.contains(9));
+        assert!(!candidate_set.contains(0));
+        assert_eq!(candidate_set.to_string(), "19");
+        assert_eq!(CandidateSet::ALL.len(), 9);
+        assert_eq!(CandidateSet::from_bits(0xFFFF), CandidateSet::ALL);
+    }
+
+    #[test]
+    #[should_panic]
+    fn candidate_set_insert_invalid_value() {
+        CandidateSet::new().insert(10);
+    }
+
+    #[test]
+    fn constructor_works() {
+        let board = easy();
+
+        let candidate_board = CandidateBoard::new(&board);
+
+        assert_eq!(candidate_board.get(0, 0).iter().collect::<Vec<u8>>(), vec![6]);
+        assert_eq!(candidate_board.get(2, 3).iter().collect::<Vec<u8>>(), vec![1, 6]);
+        assert_eq!(candidate_board.get(2, 4).iter().collect::<Vec<u8>>(), vec![1]);
+        assert_eq!(candidate_board.get(8, 8).iter().collect::<Vec<u8>>(), vec![8]);
+        assert!(candidate_board.get(0, 1).is_empty());
+    }
+}
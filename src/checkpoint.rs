@@ -0,0 +1,356 @@
+#[cfg(feature = "serde")]
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt::{ self, Display, Formatter };
+use crate::sudoku_board::SudokuBoard;
+use crate::sudoku_solver::{ used_values, SudokuSolver };
+
+// How many loop turns of the iterative search one call to `solve_resumable` may spend before pausing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Budget {
+    iterations: u64
+}
+
+impl Budget {
+    pub fn iterations(iterations: u64) -> Budget {
+        return Budget { iterations };
+    }
+
+    pub fn unlimited() -> Budget {
+        return Budget { iterations: u64::MAX };
+    }
+}
+
+// Everything the iterative search needs to pick up where it stopped: the values placed so far, the order the
+// unsolved spaces are decided in, the values already tried for each of them, and how deep the search currently is
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(into = "CheckpointRecord", try_from = "CheckpointRecord"))]
+pub struct Checkpoint {
+    board: [u8; 81],
+    unsolved_spaces: Vec<(usize, usize)>,
+    attempted_values: Vec<u16>, // Bitmask of the values tried so far, indexed the same as `unsolved_spaces`
+    depth: usize,
+    iterations: u64 // Loop turns spent across every slice so far
+}
+
+impl Checkpoint {
+    pub fn iterations(&self) -> u64 {
+        return self.iterations;
+    }
+}
+
+// The serde form of a `Checkpoint`, the board as an 81 character line of digits
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct CheckpointRecord {
+    board: String,
+    spaces: Vec<(usize, usize)>,
+    attempted: Vec<u16>,
+    depth: usize,
+    iterations: u64
+}
+
+#[cfg(feature = "serde")]
+impl From<Checkpoint> for CheckpointRecord {
+    fn from(checkpoint: Checkpoint) -> CheckpointRecord {
+        return CheckpointRecord {
+            board: checkpoint.board.iter().map(|value| char::from(b'0' + value)).collect(),
+            spaces: checkpoint.unsolved_spaces,
+            attempted: checkpoint.attempted_values,
+            depth: checkpoint.depth,
+            iterations: checkpoint.iterations
+        };
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<CheckpointRecord> for Checkpoint {
+    type Error = &'static str;
+
+    fn try_from(record: CheckpointRecord) -> Result<Checkpoint, &'static str> {
+        if record.board.len() != 81 {
+            return Err("the board must be 81 digits");
+        }
+        let mut board = [0; 81];
+        for (space, character) in board.iter_mut().zip(record.board.chars()) {
+            *space = character.to_digit(10).ok_or("the board must be 81 digits")? as u8;
+        }
+        if record.spaces.iter().any(|&(row, column)| row >= 9 || column >= 9) {
+            return Err("rows and columns must be [0..8] inclusive");
+        }
+        if record.attempted.iter().any(|attempted| *attempted >= 1 << 10) {
+            return Err("attempted values must be [1..9] inclusive");
+        }
+        if record.attempted.len() != record.spaces.len() || record.depth > record.spaces.len() {
+            return Err("the fields aren't consistent with each other");
+        }
+
+        return Ok(Checkpoint { board, unsolved_spaces: record.spaces, attempted_values: record.attempted, depth: record.depth, iterations: record.iterations });
+    }
+}
+
+// Why `solve_resumable` refused a checkpoint, which usually comes back from disk and may have been damaged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckpointError {
+    OtherPuzzle, // The givens or the unsolved spaces aren't the solver's
+    Inconsistent(&'static str) // The fields don't describe a point the search could have reached
+}
+
+impl Display for CheckpointError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        return match self {
+            CheckpointError::OtherPuzzle => write!(f, "The checkpoint is from a different puzzle"),
+            CheckpointError::Inconsistent(reason) => write!(f, "The checkpoint is inconsistent: {}", reason)
+        };
+    }
+}
+
+impl Error for CheckpointError {}
+
+#[derive(Debug)]
+pub enum ResumableOutcome {
+    Solved(SudokuBoard),
+    Exhausted, // Every branch was tried, the board has no solution
+    // The budget ran out. `best_partial` is the fullest board the search held during this slice, the givens plus every
+    // placement live at that point. The search only ever places a value none of its peers has, so it breaks no rule,
+    // but it needn't extend to the solution either: resume from `checkpoint` to carry on.
+    Paused { checkpoint: Checkpoint, best_partial: SudokuBoard }
+}
+
+impl SudokuSolver {
+    // The iterative engine in slices of at most `budget` loop turns. Pass `None` to start, then the checkpoint of each
+    // `Paused` outcome to carry on. The search order is the same as `solve` with the iterative engine, so the slices
+    // end on the same board a straight-through solve does. The cached solution is neither used nor filled in.
+    // A checkpoint from another puzzle, or one that was changed since it was paused, is refused with an error.
+    pub fn solve_resumable(&self, checkpoint: Option<Checkpoint>, budget: Budget) -> Result<ResumableOutcome, CheckpointError> {
+        let Checkpoint { board, unsolved_spaces, mut attempted_values, mut depth, mut iterations } = match checkpoint {
+            Some(checkpoint) => checkpoint,
+            None => Checkpoint {
+                board: self.board.to_array(),
+                unsolved_spaces: self.unsolved_spaces.to_vec(),
+                attempted_values: vec![0; self.unsolved_spaces.len()],
+                depth: 0,
+                iterations: 0
+            }
+        };
+        if unsolved_spaces != *self.unsolved_spaces {
+            return Err(CheckpointError::OtherPuzzle);
+        }
+        let mut solved_board = SudokuBoard::new(&board); // `TryFrom<CheckpointRecord>` only lets digits through, `check_search_state` below vets the rest
+        if solved_board.is_consistent_extension_of(&self.board).is_err() {
+            return Err(CheckpointError::OtherPuzzle);
+        }
+        check_search_state(&solved_board, &unsolved_spaces, &attempted_values, depth)?;
+
+        let mut best_partial = SudokuBoard::copy(&solved_board);
+        let mut spent = 0;
+        while !solved_board.all_spaces_solved() {
+            if spent == budget.iterations {
+                let checkpoint = Checkpoint { board: solved_board.to_array(), unsolved_spaces, attempted_values, depth, iterations };
+                return Ok(ResumableOutcome::Paused { checkpoint, best_partial });
+            }
+            spent += 1;
+            iterations += 1;
+
+            let (row_index, column_index) = unsolved_spaces[depth];
+            solved_board.clear_value(row_index, column_index); // Set back to 0 in the case this was a back-tracked space
+
+            let invalid_value_candidates = attempted_values[depth] | used_values(&solved_board, row_index, column_index);
+            if let Some(first_value) = (1..=9).find(|value| invalid_value_candidates & (1 << value) == 0) {
+                solved_board.set_value(row_index, column_index, first_value);
+                attempted_values[depth] |= 1 << first_value;
+                depth += 1;
+                if solved_board.unsolved_count() < best_partial.unsolved_count() {
+                    best_partial.copy_from(&solved_board);
+                }
+            }
+            else {
+                if depth == 0 {
+                    return Ok(ResumableOutcome::Exhausted);
+                }
+
+                attempted_values[depth] = 0;
+                depth -= 1;
+            }
+        }
+
+        return Ok(ResumableOutcome::Solved(solved_board));
+    }
+}
+
+// What the search loop relies on: every space before `depth` holds a value it tried, the one at `depth` is empty or
+// holds one, the rest are empty, and the board breaks no rule
+fn check_search_state(board: &SudokuBoard, unsolved_spaces: &[(usize, usize)], attempted_values: &[u16], depth: usize) -> Result<(), CheckpointError> {
+    if attempted_values.len() != unsolved_spaces.len() || depth > unsolved_spaces.len() {
+        return Err(CheckpointError::Inconsistent("the search position is out of range"));
+    }
+    if !board.all_spaces_valid() {
+        return Err(CheckpointError::Inconsistent("the board breaks the rules"));
+    }
+    for (index, (&(row_index, column_index), attempted)) in unsolved_spaces.iter().zip(attempted_values.iter()).enumerate() {
+        let value = board[(row_index, column_index)];
+        let placed_and_tried = value != 0 && attempted & 1 << value != 0;
+        let in_order = if index < depth {
+            placed_and_tried
+        }
+        else if index == depth {
+            value == 0 || placed_and_tried
+        }
+        else {
+            value == 0 && *attempted == 0
+        };
+        if attempted & !0b11_1111_1110 != 0 || !in_order {
+            return Err(CheckpointError::Inconsistent("the board doesn't match the search position"));
+        }
+    }
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::hard;
+
+    #[test]
+    fn solve_resumable_matches_straight_through() {
+        let solver = SudokuSolver::new(&hard());
+        let mut checkpoint = None;
+        let mut slices = 0;
+
+        let solved_board = loop {
+            match solver.solve_resumable(checkpoint.take(), Budget::iterations(50_000)).unwrap() {
+                ResumableOutcome::Solved(solved_board) => break solved_board,
+                ResumableOutcome::Exhausted => panic!("The hard fixture has a solution"),
+                ResumableOutcome::Paused { checkpoint: paused, .. } => {
+                    // Under serde every slice goes through JSON, as if the process had restarted in between
+                    #[cfg(feature = "serde")]
+                    let paused = {
+                        let reloaded = serde_json::from_str::<Checkpoint>(&serde_json::to_string(&paused).unwrap()).unwrap();
+                        assert_eq!(reloaded, paused);
+                        reloaded
+                    };
+                    checkpoint = Some(paused);
+                    slices += 1;
+                }
+            }
+        };
+
+        assert!(slices > 1);
+        assert_eq!(solved_board, solver.solve());
+    }
+
+    #[test]
+    fn solve_resumable_unlimited_budget() {
+        let solver = SudokuSolver::new(&hard());
+
+        match solver.solve_resumable(None, Budget::unlimited()).unwrap() {
+            ResumableOutcome::Solved(solved_board) => assert_eq!(solved_board, solver.solve()),
+            outcome => panic!("Expected a solved board, got {:?}", outcome)
+        }
+    }
+
+    #[test]
+    fn solve_resumable_exhausted() {
+        let mut unsolvable = [0; 81];
+        unsolvable[..8].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        unsolvable[17] = 9;
+        let solver = SudokuSolver::new(&SudokuBoard::new(&unsolvable));
+
+        assert!(matches!(solver.solve_resumable(None, Budget::unlimited()), Ok(ResumableOutcome::Exhausted)));
+    }
+
+    #[test]
+    fn solve_resumable_best_partial() {
+        let solver = SudokuSolver::new(&hard());
+        let (checkpoint, best_partial) = match solver.solve_resumable(None, Budget::iterations(200)).unwrap() {
+            ResumableOutcome::Paused { checkpoint, best_partial } => (checkpoint, best_partial),
+            outcome => panic!("Expected a paused search, got {:?}", outcome)
+        };
+
+        assert!(best_partial.all_spaces_valid());
+        assert!(best_partial.unsolved_count() < hard().unsolved_count());
+        assert!(best_partial.is_consistent_extension_of(&hard()).is_ok());
+        assert!(best_partial.unsolved_count() <= SudokuBoard::new(&checkpoint.board).unsolved_count());
+
+        match solver.solve_resumable(Some(checkpoint), Budget::unlimited()).unwrap() {
+            ResumableOutcome::Solved(solved_board) => assert_eq!(solved_board, solver.solve()),
+            outcome => panic!("Expected a solved board, got {:?}", outcome)
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn checkpoint_serde_rejects_inconsistent() {
+        let solver = SudokuSolver::new(&hard());
+        let checkpoint = match solver.solve_resumable(None, Budget::iterations(10)).unwrap() {
+            ResumableOutcome::Paused { checkpoint, .. } => checkpoint,
+            outcome => panic!("Expected a paused search, got {:?}", outcome)
+        };
+        assert_eq!(checkpoint.iterations(), 10);
+
+        let mut json = serde_json::to_value(&checkpoint).unwrap();
+        json["iterations"] = serde_json::json!((1u64 << 53) + 1);
+        assert_eq!(serde_json::from_value::<Checkpoint>(json.clone()).unwrap().iterations(), (1 << 53) + 1);
+        json["attempted"] = serde_json::json!([]);
+        assert!(serde_json::from_value::<Checkpoint>(json).is_err());
+    }
+
+    fn paused(solver: &SudokuSolver, iterations: u64) -> Checkpoint {
+        return match solver.solve_resumable(None, Budget::iterations(iterations)).unwrap() {
+            ResumableOutcome::Paused { checkpoint, .. } => checkpoint,
+            outcome => panic!("Expected a paused search, got {:?}", outcome)
+        };
+    }
+
+    #[test]
+    fn solve_resumable_other_puzzle() {
+        let checkpoint = paused(&SudokuSolver::new(&hard()), 10);
+        let mut other = [0; 81];
+        other[0] = 9;
+
+        let outcome = SudokuSolver::new(&SudokuBoard::new(&other)).solve_resumable(Some(checkpoint), Budget::unlimited());
+        assert_eq!(outcome.unwrap_err(), CheckpointError::OtherPuzzle);
+    }
+
+    #[test]
+    fn solve_resumable_rejects_tampered() {
+        let solver = SudokuSolver::new(&hard());
+        let checkpoint = paused(&solver, 30);
+        let (first_row, first_column) = checkpoint.unsolved_spaces[0];
+        let (last_row, last_column) = *checkpoint.unsolved_spaces.last().unwrap();
+        let resume = |checkpoint: Checkpoint| solver.solve_resumable(Some(checkpoint), Budget::unlimited()).unwrap_err();
+
+        let mut changed_given = checkpoint.clone();
+        changed_given.board[80] = 0;
+        assert_eq!(resume(changed_given), CheckpointError::OtherPuzzle);
+
+        let mut reordered_spaces = checkpoint.clone();
+        reordered_spaces.unsolved_spaces.swap(0, 1);
+        assert_eq!(resume(reordered_spaces), CheckpointError::OtherPuzzle);
+
+        // Every space searched, but the last one left empty, used to index past the end of the spaces
+        let mut depth_past_board = checkpoint.clone();
+        depth_past_board.depth = depth_past_board.unsolved_spaces.len();
+        assert!(matches!(resume(depth_past_board), CheckpointError::Inconsistent(_)));
+
+        let mut placed_past_depth = checkpoint.clone();
+        placed_past_depth.board[9 * last_row + last_column] = (1..=9).find(|value| {
+            let mut board = SudokuBoard::new(&checkpoint.board);
+            board.set_value(last_row, last_column, *value);
+            return board.all_spaces_valid();
+        }).unwrap();
+        assert!(matches!(resume(placed_past_depth), CheckpointError::Inconsistent(_)));
+
+        let mut untried_value = checkpoint.clone();
+        untried_value.attempted_values[0] &= !(1 << checkpoint.board[9 * first_row + first_column]);
+        assert!(matches!(resume(untried_value), CheckpointError::Inconsistent(_)));
+
+        let mut broken_rule = checkpoint.clone(); // The 1 given at (2, 0) shares the first space's column
+        assert_eq!((first_row, first_column), (0, 0));
+        broken_rule.board[0] = 1;
+        broken_rule.attempted_values[0] |= 1 << 1;
+        assert!(matches!(resume(broken_rule), CheckpointError::Inconsistent(_)));
+
+        assert!(matches!(solver.solve_resumable(Some(checkpoint), Budget::unlimited()), Ok(ResumableOutcome::Solved(_))));
+    }
+}
@@ -0,0 +1,225 @@
+use std::cmp::Ordering;
+use std::hash::{ Hash, Hasher };
+use crate::digit::Digit;
+use crate::sudoku_board::{ BoardError, SudokuBoard };
+
+const GIVEN: u8 = 0x80; // Set on the byte of every given, values only need the low 4 bits
+
+// The 81 values row-major with 0 for empty spaces, one byte each with `GIVEN` marking the givens, for keeping millions
+// of boards in a flat `Vec`. Equality, hashing and ordering only look at the values, as `SudokuBoard`'s equality does,
+// and compare row-major so sorting matches sorting by `SudokuBoard::to_line`. Converting back keeps the givens, but
+// not whether they were locked, nor the observer.
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct CompactBoard([u8; 81]);
+
+impl CompactBoard {
+    // Every placed value is a given, as with `SudokuBoard::new`
+    pub fn new(sudoku_puzzle: &[u8; 81]) -> CompactBoard {
+        return match CompactBoard::try_new(sudoku_puzzle) {
+            Ok(compact_board) => compact_board,
+            Err(_) => panic!("All values must be [0..9] inclusive")
+        };
+    }
+
+    pub fn try_new(sudoku_puzzle: &[u8; 81]) -> Result<CompactBoard, BoardError> {
+        if let Some(index) = sudoku_puzzle.iter().position(|value| *value > 9) {
+            return Err(BoardError::ValueOutOfRange { row: index / 9, column: index % 9, value: sudoku_puzzle[index] });
+        }
+        let mut bytes = *sudoku_puzzle;
+        for byte in bytes.iter_mut().filter(|value| **value != 0) {
+            *byte |= GIVEN;
+        }
+        return Ok(CompactBoard(bytes));
+    }
+
+    pub fn get(&self, row_index: usize, column_index: usize) -> u8 {
+        if row_index > 8 || column_index > 8 {
+            panic!("An invalid space was passed into 'get', it was ({}, {})", row_index, column_index);
+        }
+        return self.0[9 * row_index + column_index] & !GIVEN;
+    }
+
+    pub fn digit(&self, row_index: usize, column_index: usize) -> Option<Digit> {
+        return Digit::new(self.get(row_index, column_index));
+    }
+
+    pub fn to_array(&self) -> [u8; 81] {
+        let mut values = self.0;
+        for value in values.iter_mut() {
+            *value &= !GIVEN;
+        }
+        return values;
+    }
+
+    pub fn is_given(&self, row_index: usize, column_index: usize) -> bool {
+        if row_index > 8 || column_index > 8 {
+            panic!("An invalid space was passed into 'is_given', it was ({}, {})", row_index, column_index);
+        }
+        return self.0[9 * row_index + column_index] & GIVEN != 0;
+    }
+
+    // Same as `SudokuBoard::digit_counts`, `digit_counts()[0]` is the number of empty spaces
+    pub fn digit_counts(&self) -> [u8; 10] {
+        let mut counts = [0; 10];
+        for value in self.values() {
+            counts[value as usize] += 1;
+        }
+        return counts;
+    }
+
+    // Full, with every value once in each row, column and nonet
+    pub fn is_solved(&self) -> bool {
+        let mut seen = [0u16; 27]; // Rows, then columns, then nonets
+        for (index, value) in self.values().enumerate() {
+            if value == 0 {
+                return false;
+            }
+
+            let (row_index, column_index) = (index / 9, index % 9);
+            for house in [row_index, 9 + column_index, 18 + SudokuBoard::nonet_index_of(row_index, column_index)].iter() {
+                if seen[*house] & 1 << value != 0 {
+                    return false;
+                }
+                seen[*house] |= 1 << value;
+            }
+        }
+        return true;
+    }
+
+    fn values(&self) -> impl Iterator<Item = u8> + '_ {
+        return self.0.iter().map(|byte| byte & !GIVEN);
+    }
+
+    fn givens_bitboard(&self) -> u128 {
+        return self.0.iter().enumerate().filter(|(_, byte)| **byte & GIVEN != 0).fold(0, |givens, (index, _)| givens | 1 << index);
+    }
+}
+
+impl PartialEq for CompactBoard {
+    fn eq(&self, other: &CompactBoard) -> bool {
+        return self.values().eq(other.values());
+    }
+}
+
+impl Eq for CompactBoard {}
+
+impl Hash for CompactBoard {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_array().hash(state);
+    }
+}
+
+impl PartialOrd for CompactBoard {
+    fn partial_cmp(&self, other: &CompactBoard) -> Option<Ordering> {
+        return Some(self.cmp(other));
+    }
+}
+
+impl Ord for CompactBoard {
+    fn cmp(&self, other: &CompactBoard) -> Ordering {
+        return self.values().cmp(other.values());
+    }
+}
+
+impl From<&SudokuBoard> for CompactBoard {
+    fn from(sudoku_board: &SudokuBoard) -> CompactBoard {
+        let givens = sudoku_board.givens_bitboard();
+        let mut bytes = sudoku_board.to_array();
+        for (index, byte) in bytes.iter_mut().enumerate() {
+            if givens & 1 << index != 0 {
+                *byte |= GIVEN;
+            }
+        }
+        return CompactBoard(bytes);
+    }
+}
+
+impl From<CompactBoard> for SudokuBoard {
+    fn from(compact_board: CompactBoard) -> SudokuBoard {
+        return SudokuBoard::new_unchecked_with_givens(&compact_board.to_array(), compact_board.givens_bitboard()); // Values are [0..9], from `try_new` or a board's own
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sudoku_solver::SudokuSolver;
+    use crate::test_fixtures::medium;
+
+    fn fixtures() -> Vec<SudokuBoard> {
+        let medium = medium();
+        let solved = SudokuSolver::new(&medium).solve();
+        let mut full_but_wrong = solved.to_array();
+        full_but_wrong.swap(0, 1);
+        let mut in_progress = SudokuBoard::copy(&medium);
+        in_progress.set_value(0, 2, solved[(0, 2)]);
+        in_progress.set_value(8, 8, 0); // Stays a given
+        return vec![medium, solved, SudokuBoard::new(&full_but_wrong), SudokuBoard::new(&[0; 81]), in_progress];
+    }
+
+    #[test]
+    fn conversions_round_trip() {
+        for board in fixtures() {
+            let compact_board = CompactBoard::from(&board);
+            assert_eq!(SudokuBoard::from(compact_board), board);
+            assert_eq!(SudokuBoard::from(compact_board).givens_bitboard(), board.givens_bitboard());
+            assert_eq!(CompactBoard::new(&board.to_array()).to_array(), compact_board.to_array());
+            assert_eq!(compact_board.to_array(), board.to_array());
+        }
+
+        let mut out_of_range = [0; 81];
+        out_of_range[80] = 10;
+        assert!(matches!(CompactBoard::try_new(&out_of_range), Err(BoardError::ValueOutOfRange { row: 8, column: 8, value: 10 })));
+    }
+
+    #[test]
+    fn queries_agree_with_sudoku_board() {
+        for board in fixtures() {
+            let compact_board = CompactBoard::from(&board);
+            assert_eq!(compact_board.digit_counts(), board.digit_counts());
+            assert_eq!(compact_board.is_solved(), board.is_solved());
+            for row_index in 0..=8 {
+                for column_index in 0..=8 {
+                    assert_eq!(compact_board.get(row_index, column_index), board[(row_index, column_index)]);
+                    assert_eq!(compact_board.digit(row_index, column_index), board.digit(row_index, column_index));
+                    assert_eq!(compact_board.is_given(row_index, column_index), board.is_given(row_index, column_index));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn ordering_sorts_and_dedups() {
+        let mut boards: Vec<CompactBoard> = fixtures().iter().chain(fixtures().iter()).map(CompactBoard::from).collect();
+        boards.sort();
+        boards.dedup();
+
+        assert_eq!(boards.len(), 5);
+        let lines: Vec<String> = boards.iter().map(|compact_board| SudokuBoard::from(*compact_board).to_line()).collect();
+        let mut sorted_lines = lines.clone();
+        sorted_lines.sort();
+        assert_eq!(lines, sorted_lines);
+        assert_eq!(boards[0], CompactBoard::new(&[0; 81]));
+
+        // Only the grid counts, the same values with other givens are the same board
+        let mut entered = SudokuBoard::new(&[0; 81]);
+        entered.set_value(4, 4, 5);
+        let given = SudokuBoard::new(&entered.to_array());
+        assert_eq!(CompactBoard::from(&entered), CompactBoard::from(&given));
+        assert_eq!(CompactBoard::from(&entered).cmp(&CompactBoard::from(&given)), Ordering::Equal);
+        assert!(!CompactBoard::from(&entered).is_given(4, 4) && CompactBoard::from(&given).is_given(4, 4));
+    }
+
+    #[test]
+    fn one_byte_per_space() {
+        assert_eq!(std::mem::size_of::<CompactBoard>(), 81);
+        assert_eq!(std::mem::size_of::<[CompactBoard; 4]>(), 4 * 81);
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_invalid_space() {
+        CompactBoard::new(&[0; 81]).get(9, 0);
+    }
+}
@@ -0,0 +1,261 @@
+use std::fmt::{ self, Display, Formatter };
+use std::io::{ self, Write };
+use std::time::{ Duration, Instant };
+use crate::solver_config::SolverConfig;
+use crate::sudoku_board::SudokuBoard;
+use crate::sudoku_solver::{ SolveControl, SolveError, SudokuSolver };
+
+// Mean, median and 95th percentile of one measurement over every board. The percentiles are nearest-rank, so they're
+// always one of the measured values.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Distribution {
+    pub mean: f64,
+    pub median: u64,
+    pub p95: u64
+}
+
+impl Distribution {
+    // All zero for no samples
+    pub fn of(samples: &[u64]) -> Distribution {
+        if samples.is_empty() {
+            return Distribution::default();
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let rank = |percentile: usize| sorted[(percentile * sorted.len()).div_ceil(100) - 1];
+        return Distribution {
+            mean: sorted.iter().sum::<u64>() as f64 / sorted.len() as f64,
+            median: rank(50),
+            p95: rank(95)
+        };
+    }
+}
+
+// How one config did over every board. Boards it failed on still count towards the distributions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigSummary {
+    pub config: SolverConfig,
+    pub solved: usize,
+    pub failed: usize, // Invalid boards, boards without a solution and cancelled searches
+    pub iterations: Distribution, // Values placed by the search
+    pub backtracks: Distribution, // Placed values that were taken back again, every one of them for a failed search
+    pub micros: Distribution // Wall-clock time of each search, not including building the solver
+}
+
+// A config whose answer for a board differs from the first config's, or isn't a solution of the board at all
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Disagreement {
+    pub board_index: usize,
+    pub config_index: usize
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparisonReport {
+    pub boards: usize,
+    pub configs: Vec<ConfigSummary>, // In the order the configs were passed
+    pub disagreements: Vec<Disagreement> // By board, then config
+}
+
+impl ComparisonReport {
+    pub fn is_consistent(&self) -> bool {
+        return self.disagreements.is_empty();
+    }
+
+    // One row per config under a `config,solved,failed,iterations_mean,iterations_median,iterations_p95,
+    // backtracks_mean,backtracks_median,backtracks_p95,micros_mean,micros_median,micros_p95` header, means to 2 decimals
+    pub fn to_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "config,solved,failed,iterations_mean,iterations_median,iterations_p95,backtracks_mean,backtracks_median,backtracks_p95,micros_mean,micros_median,micros_p95")?;
+        for summary in self.configs.iter() {
+            write!(writer, "{},{},{}", config_label(&summary.config), summary.solved, summary.failed)?;
+            for distribution in [summary.iterations, summary.backtracks, summary.micros].iter() {
+                write!(writer, ",{:.2},{},{}", distribution.mean, distribution.median, distribution.p95)?;
+            }
+            writeln!(writer)?;
+        }
+        return writer.flush();
+    }
+}
+
+// A fixed-width table with mean/median/p95 in each column, then any disagreements
+impl Display for ComparisonReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} boards", self.boards)?;
+        write!(f, "{:<12} {:>7} {:>7} {:>26} {:>26} {:>26}", "config", "solved", "failed", "iterations", "backtracks", "time (us)")?;
+        for summary in self.configs.iter() {
+            write!(f, "\n{:<12} {:>7} {:>7}", config_label(&summary.config), summary.solved, summary.failed)?;
+            for distribution in [summary.iterations, summary.backtracks, summary.micros].iter() {
+                write!(f, " {:>26}", format!("{:.1}/{}/{}", distribution.mean, distribution.median, distribution.p95))?;
+            }
+        }
+        for disagreement in self.disagreements.iter() {
+            write!(f, "\nBoard {} disagrees under {}", disagreement.board_index, config_label(&self.configs[disagreement.config_index].config))?;
+        }
+        return Ok(());
+    }
+}
+
+fn config_label(config: &SolverConfig) -> String {
+    return format!("{:?}", config.engine);
+}
+
+// One search of one board under one config
+struct Run {
+    result: Result<SudokuBoard, SolveError>,
+    iterations: u64,
+    backtracks: u64,
+    elapsed: Duration
+}
+
+// Solves every board under every config, one at a time so the timings don't compete for cores. The first config is
+// the reference the others are checked against.
+pub fn compare_configs(boards: &[SudokuBoard], configs: &[SolverConfig]) -> ComparisonReport {
+    return compare_runs(boards, configs, run);
+}
+
+fn run(config: &SolverConfig, board: &SudokuBoard) -> Run {
+    if !board.all_spaces_valid() {
+        return Run { result: Err(SolveError::InvalidBoard), iterations: 0, backtracks: 0, elapsed: Duration::from_secs(0) };
+    }
+
+    let solver = SudokuSolver::with_config(board, *config);
+    let control = SolveControl::new();
+    let started = Instant::now();
+    let result = solver.solve_with_control(&control);
+    let elapsed = started.elapsed();
+
+    let iterations = control.iterations();
+    let kept = if result.is_ok() { solver.unsolved_spaces.len() as u64 } else { 0 };
+    return Run { result, iterations, backtracks: iterations.saturating_sub(kept), elapsed };
+}
+
+fn compare_runs<F: Fn(&SolverConfig, &SudokuBoard) -> Run>(boards: &[SudokuBoard], configs: &[SolverConfig], run: F) -> ComparisonReport {
+    let runs: Vec<Vec<Run>> = boards.iter().map(|board| configs.iter().map(|config| run(config, board)).collect()).collect();
+
+    let mut disagreements = Vec::new();
+    for (board_index, (board, board_runs)) in boards.iter().zip(runs.iter()).enumerate() {
+        for (config_index, board_run) in board_runs.iter().enumerate() {
+            let solves_board = match &board_run.result {
+                Ok(solution) => solution.is_solved() && solution.is_consistent_extension_of(board).is_ok(),
+                Err(_) => true
+            };
+            if !solves_board || board_run.result != board_runs[0].result {
+                disagreements.push(Disagreement { board_index, config_index });
+            }
+        }
+    }
+
+    let configs = configs.iter().enumerate().map(|(config_index, config)| {
+        let config_runs: Vec<&Run> = runs.iter().map(|board_runs| &board_runs[config_index]).collect();
+        let samples = |measure: &dyn Fn(&Run) -> u64| config_runs.iter().map(|config_run| measure(config_run)).collect::<Vec<u64>>();
+        let solved = config_runs.iter().filter(|config_run| config_run.result.is_ok()).count();
+        return ConfigSummary {
+            config: *config,
+            solved,
+            failed: config_runs.len() - solved,
+            iterations: Distribution::of(&samples(&|config_run| config_run.iterations)),
+            backtracks: Distribution::of(&samples(&|config_run| config_run.backtracks)),
+            micros: Distribution::of(&samples(&|config_run| config_run.elapsed.as_micros() as u64))
+        };
+    }).collect();
+
+    return ComparisonReport { boards: boards.len(), configs, disagreements };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver_config::Engine;
+    use crate::test_fixtures::medium;
+
+    fn configs() -> [SolverConfig; 2] {
+        return [SolverConfig::new(), SolverConfig::new().engine(Engine::Recursive)];
+    }
+
+    fn samples() -> Vec<SudokuBoard> {
+        let mut unsolvable = [0; 81];
+        unsolvable[..8].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        unsolvable[17] = 9;
+
+        return vec![
+            medium(),
+            SudokuBoard::new(&unsolvable),
+            SudokuBoard::new(&[0; 81])
+        ];
+    }
+
+    #[test]
+    fn distribution_works() {
+        assert_eq!(Distribution::of(&[]), Distribution::default());
+        assert_eq!(Distribution::of(&[7]), Distribution { mean: 7.0, median: 7, p95: 7 });
+        let hundred: Vec<u64> = (1..=100).rev().collect();
+        assert_eq!(Distribution::of(&hundred), Distribution { mean: 50.5, median: 50, p95: 95 });
+    }
+
+    #[test]
+    fn compare_configs_works() {
+        let report = compare_configs(&samples(), &configs());
+
+        assert_eq!(report.boards, 3);
+        assert_eq!(report.configs.len(), 2);
+        assert!(report.is_consistent());
+        for (summary, config) in report.configs.iter().zip(configs().iter()) {
+            assert_eq!(summary.config, *config);
+            assert_eq!((summary.solved, summary.failed), (2, 1));
+            assert!(summary.iterations.p95 >= summary.iterations.median && summary.backtracks.median <= summary.iterations.median);
+        }
+
+        let table = report.to_string();
+        assert_eq!(table.lines().count(), 4);
+        assert!(table.lines().nth(2).unwrap().starts_with("Iterative"));
+        assert!(table.lines().nth(3).unwrap().starts_with("Recursive"));
+    }
+
+    #[cfg(feature = "datasets")]
+    #[test]
+    fn compare_configs_on_bundled_puzzles() {
+        let boards = crate::datasets::load(&crate::datasets::medium()[..20]).unwrap();
+        let report = compare_configs(&boards, &configs());
+
+        assert!(report.is_consistent());
+        assert!(report.configs.iter().all(|summary| summary.solved == 20));
+    }
+
+    #[test]
+    fn broken_config_is_caught() {
+        // Stands in for a config whose search is broken, it swaps two values of every solution
+        let broken = |config: &SolverConfig, board: &SudokuBoard| {
+            let mut correct = run(config, board);
+            if config.engine == Engine::Recursive {
+                if let Ok(solution) = correct.result.as_mut() {
+                    let (first, second) = (solution[(8, 7)], solution[(8, 8)]);
+                    solution.set_value(8, 7, second);
+                    solution.set_value(8, 8, first);
+                }
+            }
+            return correct;
+        };
+        let report = compare_runs(&samples(), &configs(), broken);
+
+        assert_eq!(report.disagreements, vec![Disagreement { board_index: 0, config_index: 1 }, Disagreement { board_index: 2, config_index: 1 }]);
+        assert!(report.to_string().ends_with("Board 2 disagrees under Recursive"));
+    }
+
+    #[test]
+    fn csv_parses_back() {
+        let report = compare_configs(&samples(), &configs());
+        let mut csv = Vec::new();
+        report.to_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+
+        let rows: Vec<Vec<&str>> = csv.lines().map(|line| line.split(',').collect()).collect();
+        assert_eq!(rows.len(), 3);
+        assert!(rows.iter().all(|row| row.len() == 12));
+        for (row, summary) in rows[1..].iter().zip(report.configs.iter()) {
+            assert_eq!(row[0], config_label(&summary.config));
+            assert_eq!(row[1].parse::<usize>(), Ok(summary.solved));
+            assert_eq!(row[4].parse::<u64>(), Ok(summary.iterations.median));
+            assert!((row[3].parse::<f64>().unwrap() - summary.iterations.mean).abs() < 0.01);
+            assert_eq!(row[11].parse::<u64>(), Ok(summary.micros.p95));
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::HashSet;
+use crate::moves::Move;
+use crate::peers::PEERS;
+use crate::sudoku_board::SudokuBoard;
+
+// The spaces whose conflicted state one move flipped, so a UI only redraws those
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConflictDelta {
+    pub added: Vec<(usize, usize)>, // Newly conflicted, in row-major order
+    pub removed: Vec<(usize, usize)> // No longer conflicted, in row-major order
+}
+
+impl ConflictDelta {
+    pub fn is_empty(&self) -> bool {
+        return self.added.is_empty() && self.removed.is_empty();
+    }
+}
+
+// The spaces that share their value with a peer, kept up to date move by move instead of revalidating the whole board.
+// Every space counts its clashing peers, so a move only has to look at the 20 peers of the space it changes.
+pub struct ConflictTracker {
+    values: [u8; 81],
+    clashes: [u8; 81], // How many peers hold the same value, 0 for empty spaces
+    conflicted: HashSet<(usize, usize)>
+}
+
+impl ConflictTracker {
+    pub fn new(board: &SudokuBoard) -> ConflictTracker {
+        let values = board.to_array();
+        let mut clashes = [0; 81];
+        for (index, clash_count) in clashes.iter_mut().enumerate() {
+            if values[index] != 0 {
+                *clash_count = PEERS[index].iter().filter(|peer| values[**peer] == values[index]).count() as u8;
+            }
+        }
+        let conflicted = (0..81).filter(|index| clashes[*index] != 0).map(|index| (index / 9, index % 9)).collect();
+        return ConflictTracker { values, clashes, conflicted };
+    }
+
+    pub fn conflicted_cells(&self) -> &HashSet<(usize, usize)> {
+        return &self.conflicted;
+    }
+
+    pub fn is_conflicted(&self, row_index: usize, column_index: usize) -> bool {
+        return self.conflicted.contains(&(row_index, column_index));
+    }
+
+    // The move is taken as given, checking it against locked givens or the like is the board's job
+    pub fn apply(&mut self, player_move: &Move) -> ConflictDelta {
+        return self.set_value(player_move.row, player_move.column, player_move.value.unwrap_or(0));
+    }
+
+    // 0 erases the space
+    pub fn set_value(&mut self, row_index: usize, column_index: usize, value: u8) -> ConflictDelta {
+        if row_index > 8 || column_index > 8 {
+            panic!("An invalid space was passed into 'set_value', it was ({}, {})", row_index, column_index);
+        }
+        if value > 9 {
+            panic!("All values must be [0..9] inclusive");
+        }
+
+        let index = 9 * row_index + column_index;
+        let previous_value = self.values[index];
+        let mut delta = ConflictDelta::default();
+        if previous_value == value {
+            return delta;
+        }
+
+        let was_conflicted = self.clashes[index] != 0;
+        self.values[index] = value;
+        self.clashes[index] = 0;
+        for &peer in PEERS[index].iter() {
+            let peer_value = self.values[peer];
+            if peer_value == 0 || (peer_value != previous_value && peer_value != value) {
+                continue;
+            }
+
+            let peer_was_conflicted = self.clashes[peer] != 0;
+            if peer_value == previous_value {
+                self.clashes[peer] -= 1;
+            }
+            else {
+                self.clashes[peer] += 1;
+                self.clashes[index] += 1;
+            }
+            if peer_was_conflicted != (self.clashes[peer] != 0) {
+                self.record(peer, &mut delta);
+            }
+        }
+        if was_conflicted != (self.clashes[index] != 0) {
+            self.record(index, &mut delta);
+        }
+
+        delta.added.sort_unstable();
+        delta.removed.sort_unstable();
+        return delta;
+    }
+
+    // Moves the space to whichever side of the set its clash count now puts it on
+    fn record(&mut self, index: usize, delta: &mut ConflictDelta) {
+        let space = (index / 9, index % 9);
+        if self.clashes[index] != 0 {
+            self.conflicted.insert(space);
+            delta.added.push(space);
+        }
+        else {
+            self.conflicted.remove(&space);
+            delta.removed.push(space);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::{ RandomSource, SplitMix64 };
+    use crate::strategies::{ invalid_board, masked_puzzle };
+
+    // The conflicted spaces according to a full revalidation
+    fn revalidated(board: &SudokuBoard) -> HashSet<(usize, usize)> {
+        return board.validate().conflicts.into_iter().flat_map(|conflict| conflict.spaces).collect();
+    }
+
+    #[test]
+    fn erasing_one_of_two_clears_both() {
+        let mut board = SudokuBoard::new(&[0; 81]);
+        let mut tracker = ConflictTracker::new(&board);
+
+        assert_eq!(tracker.apply(&Move::place(0, 0, 5)), ConflictDelta::default());
+        assert_eq!(tracker.apply(&Move::place(0, 7, 5)), ConflictDelta { added: vec![(0, 0), (0, 7)], removed: Vec::new() });
+        assert_eq!(tracker.apply(&Move::place(1, 1, 5)), ConflictDelta { added: vec![(1, 1)], removed: Vec::new() });
+        assert!(tracker.is_conflicted(0, 0));
+
+        // (0, 0) still clashes with (1, 1) in its nonet after (0, 7) goes
+        assert_eq!(tracker.apply(&Move::erase(0, 7)), ConflictDelta { added: Vec::new(), removed: vec![(0, 7)] });
+        assert_eq!(tracker.apply(&Move::erase(1, 1)), ConflictDelta { added: Vec::new(), removed: vec![(0, 0), (1, 1)] });
+        assert!(tracker.conflicted_cells().is_empty());
+
+        board.set_value(0, 0, 5);
+        assert_eq!(revalidated(&board), *tracker.conflicted_cells());
+    }
+
+    #[test]
+    fn changing_value_moves_conflict() {
+        let mut tracker = ConflictTracker::new(&SudokuBoard::new(&[0; 81]));
+        tracker.set_value(4, 4, 3);
+        tracker.set_value(4, 0, 3);
+        tracker.set_value(0, 4, 7);
+
+        assert_eq!(tracker.set_value(4, 4, 7), ConflictDelta { added: vec![(0, 4)], removed: vec![(4, 0)] });
+        assert_eq!(tracker.conflicted_cells(), &[(0, 4), (4, 4)].iter().copied().collect());
+        assert!(tracker.set_value(4, 4, 7).is_empty());
+    }
+
+    #[test]
+    fn tracker_matches_revalidation() {
+        for seed in 0..100 {
+            let mut board = if seed % 2 == 0 { masked_puzzle(seed, 50) } else { invalid_board(seed) };
+            let mut tracker = ConflictTracker::new(&board);
+            assert_eq!(*tracker.conflicted_cells(), revalidated(&board), "seed {}", seed);
+
+            let mut random = SplitMix64::new(seed);
+            for _ in 0..200 {
+                let (row_index, column_index) = (random.below(9) as usize, random.below(9) as usize);
+                let value = if random.below(4) == 0 { 0 } else { 1 + random.below(9) as u8 };
+                let before = tracker.conflicted_cells().clone();
+
+                let delta = tracker.set_value(row_index, column_index, value);
+                board.set_value(row_index, column_index, value);
+
+                let after = revalidated(&board);
+                assert_eq!(*tracker.conflicted_cells(), after, "seed {}", seed);
+                let mut added: Vec<(usize, usize)> = after.difference(&before).copied().collect();
+                let mut removed: Vec<(usize, usize)> = before.difference(&after).copied().collect();
+                added.sort_unstable();
+                removed.sort_unstable();
+                assert_eq!(delta, ConflictDelta { added, removed }, "seed {}", seed);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_value_out_of_bounds() {
+        ConflictTracker::new(&SudokuBoard::new(&[0; 81])).set_value(9, 0, 1);
+    }
+}
@@ -0,0 +1,408 @@
+use crate::parse::ParseGridError;
+use crate::sudoku_board::SudokuBoard;
+
+// Bundled puzzles as 81 character lines with `.` for empty spaces. Easy, medium and hard are graded by
+// `SudokuSolver::rate`, every one has a unique solution and no two share a solution grid. Version 3 of the data:
+// version 2 regraded once `rate` applied locked candidates, which moved 8 hard puzzles to the end of medium, then each
+// tier filled to 100 with generated puzzles. From here on puzzles are only ever appended so indices stay stable.
+
+pub fn easy() -> &'static [&'static str] {
+    return &EASY;
+}
+
+pub fn medium() -> &'static [&'static str] {
+    return &MEDIUM;
+}
+
+pub fn hard() -> &'static [&'static str] {
+    return &HARD;
+}
+
+// Puzzles known to be slow or otherwise awkward for back-tracking solvers
+pub fn pathological() -> &'static [&'static str] {
+    return &PATHOLOGICAL;
+}
+
+// Stops at the first puzzle that doesn't parse, so it also takes puzzles from outside the bundled tiers
+pub fn load(puzzles: &[&str]) -> Result<Vec<SudokuBoard>, ParseGridError> {
+    return puzzles.iter().map(|puzzle| SudokuBoard::parse_grid(puzzle)).collect();
+}
+
+const EASY: [&str; 100] = [
+    "7..6...3...3541.7.2..3.9845..6.3.9.1...2.7....3...568.8..9...64..572..983....6..2",
+    "9...1.8.4417.5...63......9764.59..7..58...9......74..5.7...548..3.7495.2...286...",
+    ".9.245...6....1.4...5673....86...327...48.16.5.2..78..3..92.4.19...1.6..861....7.",
+    "..8.....2..1.2.469.....5...5.74...86213.68..4.86.192....9381.2..6...4.7.13..7...8",
+    "347..56915617...2.28.........2583..4...174...41.....57...63.5....42.813....45....",
+    "5.47.8...283..1....97..258...91..76.7..6..3...2....95.9...8...6..8243.9..35916...",
+    "63...1.75..23....445..9.31..26..94.8......7...9...75.1.4...862...1.7.85.2..956..7",
+    ".7.9.218..5....392......4...82.......43.85.7..671....8.19..386.4356..9...26..1.35",
+    "89.75..3.6..48..292..3.6.545...3........78....279.5..89..8435.2.5.......7...19.43",
+    ".82594.3.........83.5..24.62.93.8.5....2.....8..9..2.394.865.....3...86.56..2.941",
+    "576.....9....7..52.3.16....147...8..86..2.9..95.7....369.23.5.73...57..8.1.4.9..6",
+    "..8.4..7.2543....167.182..483..61..9.6.....8..4.......4936.8.17.....46.8.8.9..2.3",
+    "..7..9458..6.....2..3825.768.....7.13.9.46.8...1.87.6..38...6...12.....3.5.361.2.",
+    "1...6....5.6..2..4..3..7...95.6..38..6....54...4.289.12.51..8.9..785.12..18.7.4.6",
+    "45...961........847.8.6..3..426...7.9..258..68..4..2.9.8497...327....89.....25.6.",
+    "27.5....4.39..4...14...8.264.7951...5.246.8...6..82....23..5.....4827.5.85...9...",
+    "8...23..57.....6...1...93....13.7...27...456.3.52864.795.41..864.....23.......954",
+    "..657..4...2...51395......68..349..7.79.1...423.7..198.9...2.6.52..9..3......1.82",
+    ".......7..4....28...53429.63..71584.45.9..32...8..4..15.217.4.891...3...6.7.....5",
+    "..36.84.22...4..9...6.27.3..2..34..63....19.4.61.79.25.5..8.279........1182.....3",
+    "963...7..4.7..8..1.284.9..3..4.32.19..6....54...94.837.3....1....9213.75..1.9....",
+    ".....3.2...6.987..45..216....3.49..2.1756.4.88...3.9.5...2.4.166....5.893..9...4.",
+    "......4...8..142.6...7.95.8..94.816.......3.9..293..8479.5.361...5.97...463...95.",
+    "4...321.82........3..9......1.85.3..8..297....9.1...421.56497.39.37...61.8.315...",
+    "6.28..5914....1.785..9.....2..6.97.53.......989.3..1.49.5..84..7.419...6.8.4.2...",
+    ".4.3.9.1..3..76...92.5.8...6.8....7....69..23....54..94.7.6235....4.328.21.7..69.",
+    "9.73.2...126..47.3..3..7...4.5.....1.685...9..914..68.....359.2....8.514.12...83.",
+    "98..574..6.....9.7...9.6.2.27..9.384...6.27.5.9.....6...9.715..32..68.49.1..3...8",
+    "67.192.454.8..3916..9.........235467...9471...4..1....86........9.874.3.3....9.7.",
+    "......8...8...951...13...94.467..98......4.6..3..164..27...864986..3..75..496.2.8",
+    ".46.3.92559.6.7.81.3..5..76............9.5..44578..1.9.1..9..4..29..6.176..5....3",
+    "...391.2.49...2.682.7648.1..5.7..43.7.9.3...6..38....1.1.9..8..8..4.5.9.....8.15.",
+    "7.4.8.6..2...6.39.6......78.....9.81.6...1.54....4526.45.9....61..35..47386..4..9",
+    "43..5.7.28.6..........7.849.8..3..1.1..4.2.7..4.19.23.39..17...71.9...83....831.7",
+    "896..4...2..91...6713....4946..7125....62.49.35..49....24...9.79...683.........1.",
+    "458.92...73...1....6.7438.9.7.4.89..6.1.7..2...431......51.927......7...92753....",
+    "62.73.4817.98245...8....9...4.....5.9......23.63.75...53..4.79....3..2.519.5..3..",
+    "87.63...2.4157.......8....45289....1.6...158731.4.52..4.629...5.3.....6..5.1.6...",
+    "...1.654..1...3......5.4183..2.18......4327.8......2.48.3.57.9.175..983..49..16..",
+    "476.25..11........5.317....85..6.2...1.3824.5.2..5..7.....435.7..521..8.23.5.7...",
+    "....7.15..2564.9...731.9..2.5.91.8..7...3...594...5.7..37.9.61....2837....4...52.",
+    "41..36985...82.31.5.........46....2.8..3.2..72.3.57.48..5..1..93...7.8..184...7.2",
+    "713...6...6.21.45.....9...12849..5....1..49......6.14..4..5.2198..1..734.7..4.8.5",
+    "...2.7.......5.1.45.....9.29...72.818..5..7.327..184..1...2..3965.1..8..38.746.1.",
+    "3.67.9...9....2...5.41..3.........911..895432...4.1.67.8..6..14...958.73.39...6.5",
+    ".1...6....642.7.5.5...132..6..4.839.79...154..537298.....1.46.....87.4...4..9.7..",
+    "8.39.54....7.248.342..7.96..3...1...7..8...94.81.....5..85.9.42.......1....482739",
+    "...912.8..7.....2..2...36.441.78623...319.76.96.2.5...89..2.3...3.8....2.5.....18",
+    ".812.....36....8.95..89.6..83..2..46..29..53.1.9..6.784963.2.8...3.......18..7..4",
+    "...4.8.69...3.5...8951.2..7..2..7.93.3.6.4.5.5..2.97..3.8......476.53...95..2..34",
+    "..53784.64...1...7.7.......3.....9.49125.7..3...9.27..7.1.956...8..23.4554..8.2..",
+    "3...24..7..13..5....4.519.2.9.6.24.....4..7.614.79.8234....9...7.9..3...53..7.24.",
+    ".4......32...9745.....5.26....6..82.7.3129.4.46278....1.457.9....7..25..53....17.",
+    ".......57.7.1256.4.59.46..34...63.757..219436..3....1.5.6......847......29.43....",
+    "...1.9..8....54.6.1....793..81....5495..23..14.3..57.9.34..82.7......84671...2.9.",
+    "965...1.7.18.7.2.32.7.1.45........2.6.31...4....4679..3..64..19.9......21.6.395..",
+    "8...97....67..3.8..3...57.64...21..8673.5.1..28.....5.74.812..95.943..1...8..9...",
+    ".519...8.23745..1..8.1..25.3..7....2..286.4.1..624.....2......77...12835..3.7.1..",
+    "3.1..6...9..3..48.78...53.14.9.7.5....6..3718..3.6.9.2....3.8.9695....23...1..6.4",
+    ".4.289.3..584..7..6.2..54.85.4.6.8.2....72.461..8.4....6.1..284.....7.6.4.5...9..",
+    ".6529.7..8......122..6......7251.43.5.4..6.7..3...2..57.8.65.43..38.9...1...47.2.",
+    ".......2.73.9.8....6.2..7...9..3.....2.1.43.53.1.6.9421..5.96..653...21.2..613.87",
+    "3..1..7...........1.4.72..9.8..476516...153.87..8632..27...98....8.2143.4.1....6.",
+    "5.2986.7...4...8..891....5...8.7.1..6.7.2........142873.6..172..29..5.1.17..6.9..",
+    "2....8..5..465..1.3..291.7...253...11.58.43..89.....4..27...49...832....631..72..",
+    ".3..8.742.4..6.198918.27.36...2.6......51.98....79........3.85.8639...7..9.8.2...",
+    "5..4.2...46.7.13....9...4...8....7.22148.7.....792.8..89.2...3...2.195.86.5.7.29.",
+    "...72...4....9451.5....172.3.85.96...768.2951.9..7.2.....1..8.28.4.5..7.71......5",
+    "46.3758....5.......3..6..5..9...3....1.7.8.955..6.....65..2473..4.13.52882.9.7..4",
+    ".8.9..256...3.2.717..1...9.2.96.758...4.2....6.74389.234....7....2...1.9..87...2.",
+    "..7864..9.389....1..653.87.8...751.3.6.2.379..7.1.62.57.93...........53.48.......",
+    ".7.82.....34...1..85..1..9.4......6.......87..9...7.2....9...3292..63.8.....72..4",
+    "...3..9.....784...3.....4.77...49.28.8....1..52..7..9.94..6.......2..6..8.3......",
+    "......25....8..7....2..3.9.45...968..........891..2.7..7....1....34...65.1.23.8..",
+    "..34...7.5.1.......8.32...4.......128.21..5.......5......5.9.2..5.6..14.42...76..",
+    ".5.1.....91.57.3..2...96.....27.5...47...1..58...2......1...29..2.93.7.......4..8",
+    ".5.......9....64...2...4.58.1.92..4....6..3.57.8.5............7.43..251...9......",
+    "....1..9..1.....8...52........3.4.....752.93.25...78...7.....1.429....63..6.9....",
+    ".....7.6.3...5...9..98...1..31...........1...49....32.61..9253....7.694......8...",
+    "........6....5624..9...2.1...73.....6...9.43..8.6....5.71...8.........6.3....7.91",
+    "..4...8...3..9....8.....6.13......47.....1.8.2..54.3.....87.913....29.....7.362..",
+    ".1.3..9....4582..3.....9.5.6....34........7....9.2436.83.........5..1..4...7.5.1.",
+    "..2..9..8...7.3..11..58..6...92.4....4...72....1..59....5......47.....8.2....8.3.",
+    "4....9..27.....638....5....27..9......8.64.7..9.2...54...3724...6.......8........",
+    ".3.85...24...17...5..4.......69.....7.9..3...3.2....7....136.......4.52.8.....6..",
+    "179....6.........9...53...4..2.67..........97.....5....38....1.7..3.6.......4.2.5",
+    "....2.953.5.......2...14.....8136...39....2.6.......8.4.......8..5...61......8542",
+    "54....37...2......38....4....3.72..87....1...6..5.92..9..3..6.....1...5..658.....",
+    "........91.........9.6.5....47..9.....1....8.8....25....3.1..7.41..3..58..94.83..",
+    "9...37...8.5.2..7..148.5.....2.76..54....386.5..4....7..........7....94.3.1......",
+    ".2..6....81....7..5.6....811..4......8.1.5.4.4......23.....8..2..2..6.7479.3.....",
+    "..46...5..9..3...77...21.4..8.....3..4......9..7..4.2..7....2..523...8.....5...96",
+    "..34......6....5.1.9...3...8..5.73923.9..2.6.....4....6.4.8...........2..876..14.",
+    "...1.8......94.5.........4.4.5.91.28.91.2............7.4.283.7.52........6..5.8..",
+    "...65.8..7....9....1...85.9.9.1.4..........3..43......9867.........83.5.5....2..7",
+    "9......655..4.......752......9....8..4..7..3...8.341.978..9....2.6..1....1...6..2",
+    "34..28...7..1........9......7...43.....6721....2...9....1..37.6.....52.3.8.......",
+    "..5..296.7.......4....64....7..1.3.....2..8..2..83617.3.....61...9.7..5..1.....89",
+    "1..4....72.9.....4..43..62.....6.27....1...38...8.5....216..79..8.....4.4..2...6.",
+    ".1.59.....4...6....8..32........7.499..64.2....3.8....2...187.4.........15.....92"
+];
+
+const MEDIUM: [&str; 100] = [
+    "9.625.....3...69....291.53..19..46.5268.754.1...6..........276..25...8....7.3.254",
+    "8.691.4.5..4.8.9.....3.56.8..7.6.8.26..198....8..7.1...4.6.1.5.19.8...64.6....28.",
+    "7.3...91..9.64...3.2..1..655417.......25.1487....6.2.12....65.......56484.6....72",
+    "....5284.284....9......8..6...74.....1...568.5.3..6...425817.6997...4.12..1..9.78",
+    ".6.3...82...586.4...7...16.9..8..4.6....4..9.8.5.9.3.1....186..1...6...4624.53819",
+    "3.2465....618293.....13...253.612...8295..1...16...7..6.......9.7....68..9..562..",
+    "...2.195...95.38...5..4.723.6...5..2583..2..9....94..53.8.5.2..64..27..8....381..",
+    "...3.........89...4..625.7..82..3..91.59..3686.9.51..4.7..96...85.7.46.2916.....7",
+    ".71.3....4.96....1.58.7964.5.7..3..6.439.6.2519......8.8...5...712....5...579...4",
+    "......8...6.983..7...6...59.1.75.3484.....79.8.7...62..2.3.7.86..8.2.9..3.684.57.",
+    "..1..8....5....69.978..5......2.47......1.9..76......14...26.....35........3...4.",
+    "..5.....6....3679....85...3..9.1..2...13.2...62..4.13....2953..9...71.8...7.8....",
+    "..56.94........7......4.1..7.8..6..3.2..3.6....14972.85......2....5..87.4.67.2..1",
+    "..2.1897.5.7.3...81.6.......7.5..4..........6..8..6...825..934....2.5.97....4.8.2",
+    "...865.72.25..48..76.312945..6...72.29..3.......9.6.....21....66..2.91.7.1....25.",
+    "68......5..36........2.9...29...37.8....8.......4........8..52.13.....9..6.1....3",
+    ".5..6....7.8.....3.1.8..5..1.249.....4.2..97..8..53.14..61.4.92.3...........3..81",
+    ".53.96.8...64..........1...1..32.9484..5.8.1....9.463..2.149......7....9.....3..4",
+    "9......43.64.8.72....7.9...5.2.6..38..3..29.5...8.5.7..7.....5...56..49...1...6..",
+    "28....31...5.1...73.79...24...64........21..36..8....9..2.7.63....3.6...1.6...7.8",
+    ".2......7..53..6...9...7...28..61...7....2..915.7...2..6...9..2......35....2...8.",
+    ".7...382...3......68...5...5...3.7.8...41...........4....24..5..2...7..1.3.9....7",
+    ".319..8.4......1....9.6..5.7....5.......4..8....1..9.....8.2..9....5........19238",
+    "4....6..28.....3...9.15.6..5..9.8.7.7......3...3.........41.2....6.........6.9.54",
+    "47.....1.....9.524....6.......6.8.5...5..7.9.....2....8671........8.3....1..5.9..",
+    "3..2...8.1...5...4..8...1.7.8....6..25.6...4....813.....4.....6...5...9.....8..7.",
+    "....8..7..24..783....3..1......1...52...56....7....9.3..8.4.....9.....1....53....",
+    "9..4...83.65.3...4...7..5..1......2..7...3..883.9........8...59.....5.....42.6...",
+    "524....8...9.75.6............6.5....1....3.58.....4...7...6..3..3..297...91......",
+    "..4.......218..6.....75.........294......17.5...397.8..1..6..2.2931......4.......",
+    "..8.1.39.....9..7....8.52...82..3..9.3......4....49......4.1.26.57....4..........",
+    "65..71.4..84....1...9.......9....8...4.8.6...1....9..3..72....4.....75........38.",
+    "....53..8.5......3..4..19....9..736.5......1...3.2.......1..2.......5...4.26..83.",
+    "....6......297.46.......8...7....3.56.5..8....9.2.......7....1......4...4...16.89",
+    "3......5......7.2......2.694...2.....6...5..18..9....4..92...1..2.8......5.....3.",
+    "4....6....152....9.....3..81.8...9..9..7.........84.1.....3..96.27....833..8..2..",
+    "....75.1.1....6.43.....17.27........46....1..38..92.......3....25....3...9......6",
+    "7....3..9.1.........5...76...837..9............42....1.8..5..7......2...14.....36",
+    "......9..1....637....9...4.....5.....4.7...3682...3.....3.1...7..46....8.9.....5.",
+    ".......5.92...7.....79..23.1...3..2...9781..3.....6....9..6.4....4..816....4.....",
+    "..1...3..25...39.6.......5.6...7.....172..8........5.....5..789..24....3.6.8..2..",
+    "6....28..5321...........1...9...4.38.67.....93...7.......3...7...192.....5.8..2.1",
+    "9...7.6217...3.84.5.........2...1...41......28......7....82....1..4.9....9....3..",
+    "....7.5.15...81..24..2...76..5....4.2.......5.6..3.9.7.2.71..8.........9.7.......",
+    "81...6.7..97......2......38.32..8....6.3.29..........1...5....2..59.3.....9....1.",
+    "1.4.3..5...7..2.3.....7.4.9.......6.........1..5.139..6........9..1...4.2..59.3..",
+    "..4..3.9..8267.....53..8...........3......8.64.8...12......2...61..3..4...71...6.",
+    "....8..2..1..62...5.74.96......9.....5.124.....6.5............2..3....17.7...1.84",
+    "..1...7.54......2..9.....1...39...74....8.....62...95.25.84.....8.163......2...4.",
+    ".......14.....3.6.3..59....9..7......51....72.6.......4.9.7.....2.86...1..32.....",
+    "54..2...7..9...........5..9..62.....13..7...........5882........6.94....4..1.86.5",
+    ".7.5.....8..94......2..7.......98.2121.....3.5......8.7..6..352...........1.72..9",
+    ".....53.....4895.68.7...9..1.......5..3.124...9.8..6..37.24.....1.........25...6.",
+    ".3.4...6.87...6..9.....8.3.24..3.1.75.....3..7..2................6.1..7318..2....",
+    "3......7....6.21.9..61....8..1..9...5..4.8....2..1...6.........6.4......8.3...96.",
+    ".3.8..2.4.......87...79.....42...........9.3.6....5....8.....95.71...3..9.4.8..6.",
+    "....972.6..9......6.......5..8.....917...3.5..2.....3...26..8.......8....4..2.1..",
+    "...326....8...54........1.3.651....2.2.6.9.8.8.4............6....7..8.......5.741",
+    ".8...4.....9.6..1......9..547.........5..2..1...5..3..1...9..36..81......96..5...",
+    "2.1.9...3.4..3.98.....2......2....5.4........37.28.4.66....13.....86.........95..",
+    "....61...2.7..8..5...5....3...39..1.7...8....5..2.6...97......2..8.5.6.4..3...8..",
+    "..81..6..296..........2..5..85..4..2.2..67....63...9.....7...1.5.4..8.97........8",
+    "...4.5..83.9...72...2......5......7396..1.........31...7..5..6........154..6.....",
+    "5..9..4...4.....1386.7......36.9428........35............47.9...9...3..14..2.....",
+    "...7.8.9.43....2...9.....3..64...9...8...27..3..4.........2.3..94..51.2....9.68..",
+    ".74...6..6..8...5...39..21...2.7.8...5.....37......1..4..6.....89.51.....2...8...",
+    ".973.5..8..6..............9.8..19..2.......9.26....3...2.8.7......5..4.6...126..3",
+    "..29........8.7....6.....43391..6..........5..4..2.1..6...1.4...2....9...5.3.96.1",
+    ".2...7.9.5......2.3.4.98...........4..164..8.6...79...7....38...3....5....59....1",
+    "..53786..8.......1.9.6......69.............2.27.9...34......58...8.53...7....1...",
+    ".......1.12....49.4.......3.......3..438.27.......7.61...36.1..29...4...7...8....",
+    "...429.......5.2.......756...........2.3.....593.7...8..9...3....27.6.5..68...12.",
+    "..5....9.493.........36...7..2........7.9..63.....5.2..1..4.85.9..81..........6..",
+    ".....2.43.5...9.......8....2..516.3....7..51.4..........68.4...3.........9...7..5",
+    "84..7.9..12...5..33..6.....7..1...3...5......28......1....3.6......24.........2.4",
+    "1..67.........8....283.5..4.....26.5....6.9.2.3....4..2.4..9......156..3.........",
+    ".7......3...8.9.....971......56....4..4...7.61...4.......52.318..2.7..5....1...2.",
+    ".....813.4...5.8...9.........2....7..1....5....76.3....4.875.9......1....3.2.4.5.",
+    "...4.....9.1..5.43......51836..4..9..4..76.........8...8.6.9.2....5...8...9..26..",
+    "7..8...9........61.8..13...9..6..........1.74..2....166....2..9..31....5.....523.",
+    "........5...7..36...96...1.2...8....3.....8...1...6.9..3.......8...7.4.24...25...",
+    "21.....9..6...45.....7.58...8.6.....4.9..82.7.....2...........9...2.7.8...4.5..2.",
+    "5.2.6.4.8............324.....32....5.74.5...2..1...........52.1...1.7....48.....6",
+    "...68...98...3......6.9..7..6.....8..4....2..1....654.2...5..13.1..4.......8...9.",
+    "4.6............1...5167.......9.6..5.8.71....1..4.57.......327..62.....93.4......",
+    "...3....748.1.7..3.....6........5.2.6..2..5...4....9....6.1..9.9...8..1.1..6..742",
+    "9.3...52..5.7..83...8.........9.....82.6............462...9...41.6.5..7.....3.9..",
+    ".5.....8.4..1.3......9....6.34.......8....67....74....513.8.....2..9...7...3....2",
+    "..7.....6.....9...13.....4...85..4...9.3....2.......13....6.1..8.1.2....26.13..74",
+    "8....59...59...1.6.2...1..36....3...2.1.9.4.....1.7...3..7..6.2...8....7...5.....",
+    "......3.....9...17.7...5.89....1..68..2867....9......44.3......95...4....68....5.",
+    ".41...9...8.....21....7.6....2...4...7...5.16....4..3....9.8...13.....5.5..1.7..4",
+    "..73...42....8.....3.....7.......9.....75.61.879.61...6.8..3..1....7.4.....5.....",
+    "..4.....93.5..1.74......8.....7.4.5..7.5.2.......9..2....6.84....1..5..37.3......",
+    "...91......3.....664...3.579.726.....3..51...........23.....1....9.4.2.3.8.......",
+    "2..15.49...5...1...476....29...7.6.....8.3...7.4....1.6.....5...32..........2...1",
+    "28....57....72.1..7...58...17...4..3.4....9.....3.6.1..5..1.6.......3.5.823......",
+    ".......247..69....5....7.......5297...8.73..54.........9........6.....32..1...8..",
+    "694....2..1.2...6.........4...79.6.3.78.........5...12...651...4..3.....8.5.....6",
+    "4.65.7.....58....6..21..7.5857..3.6..9...............3..8.....1...24...8....1.39."
+];
+
+const HARD: [&str; 100] = [
+    "..641.589...97..6.89..2....25.794.3...7.36..4.6...1......18...3..954.81.18.3..9..",
+    "8.5..3....1..69..496....183...6..31.38...5..6.52......4..9..2..1.6..8.3....2..4..",
+    ".8....1.....78....924.....6..1.......4.2..68...9..7...3.....42.....14.......5..9.",
+    ".2...3...3.8....5..6..97.....4...91......163.....4.....83.....6...1.28..21..5....",
+    ".....4...6......8...3..9.62....9..46..4....1.71...68...8....1....713....3..8....9",
+    "1...28.5.9.....3..6..1......2..1...4..85....1.7....98..5.......8......4.....3.7..",
+    "..7..81.....9.....9....2.46.9.85.....14....2.....29.6.5......8....3....42..7..3..",
+    ".1.4...2..3.8....59.....7..........4.9..6....17...5..86...73...3.7..9..2.2..4....",
+    "..7...29.54.9......9...7..1.25...4..........2..6752.13.79.3.8..35..8...7..1...34.",
+    "3..9....857.368.92...52..............9..82.......39.64.2...5349...246..181.......",
+    ".4.5.....57.....16....16....9.63....2....1.....59.486.3......2...238....96....3..",
+    "6..1.3.7..1.9....4.84..6.9...6..8..775.....8.4..3.............1...2..6.9....51...",
+    "..7.9..8....4....1.5.....969.8.5...2......5....4..2.3.......42.37.6.......1..3...",
+    "...4.7....8...9..1.7.....6....56.....6.8.4..71..7.........5.39.......27.9.2...5..",
+    ".....3.....31...8.....867..3...69.7...45....8.89.7..3.8.7.....695..2.....1.......",
+    "5..6......21873..58...451..29...87....37...1....4.25..3..5..69....319.74.19.6..58",
+    ".9...4..14...93....7.12.4.....6............139...57...8.......57...4......6....32",
+    ".1......548.6.32..9.2....6.3.14....28495.....52.....86.....6......98.32....351...",
+    "7......85..9..2.4.56......92.3..69.4.8..2..76..5....23....3...8...1.53..3...84..7",
+    ".31.57..44...6135...6..3.7.9.3.....1..8.........63.8...84...21...981...3.7......6",
+    ".9.6.3....3...46.7.2...8...6.....89......5..2........4.78.9.1......76.....4....23",
+    ".2....83..3....6.29.......1..1.9.5.65.72..38.8......9......4......3.7......65.2..",
+    "..71...9.8.4...2.3....8..56...81......2......9.87..3....5.....1..6.5.72..2....63.",
+    "52....3.7..3..8........1.9....7..2187.....4...82.......4.5.......7....4..18..36..",
+    ".342....7..5....2....4....339..1..76.7...91.......82...2...3.1.5.........8..7....",
+    "2......3458...4...1.36...8.7...........38...1...51......92....63....1.478......23",
+    "14...6....3.......7.9..84...9.15...3....6.1.8...7.......3.9.....1....5.4...5..69.",
+    ".....2.....85..1.6....3498.......4359..4............7..25...6..17........8..2.5..",
+    "...8.....8.7.....6.5....4.3.....1..54....32..2.....71.7.35.6.....2...1.9....3....",
+    "41....7....6.1..4...9.....8.247.....6.3.....9.....25...9..2.316.4..3.95..........",
+    "2.....8.5.8.2.54....79.......8.2..61..61....8...3.8...1...9....5....31....46....2",
+    "4.8..65.....5.........8.4.18....316..7.6.5.3......7...65.9......27....1.1.......9",
+    "...2...6.72..6..95........1.9...37.2.7.....5.3.5..4.......96.8.96.4............79",
+    "4.9.8...73..2...6....93...48..5....9.9...32......1..8375.........6.5......4..6..2",
+    "...3...6.48...153..6..5.....5.1.9..2..4..3....78.4......742....1....645.8........",
+    "7..82..49.....67....24...1.....4.....16.....5.83......6...........9..5.8.7....326",
+    "3...67.5...6..8.24.1...5.........9.....3...7...982.....25....3..38....954..9.....",
+    "...9.21......5...3..2.3.5..7....4...325..98...8...63...54....91.9.4....6..7......",
+    "..7..9...2.3....1........4.....1....4....5.3.85......4........7..912.6...3.6.8...",
+    "...16..8.6.9........74...2..6....2.....37...884..96.........8..........53.1.5..76",
+    "..6.8........7.59....6.2.4.3.18..4..4..7......5...1.7............9...1.57....5.82",
+    ".......8..1...394.6.2.1..7.35......9....6..5......57......342...872....1...17....",
+    "..61...8.....2.5.6.....6.9.48.7......15...2..3...4....7..9.14........6...4..3...5",
+    ".847...1.2............29..4...8...568.....7...621......3...89....52..63.....3...7",
+    "..17.2..9.......42..5.1..3...2.....8..9.4.....6.5...2.957...4.......3....1..6....",
+    ".......4..9..12.5.3..4..9.1.....54..6.......751.6.7..2.3....8....2..37..8.......6",
+    "2...4...37..25...8......12...3...8.7...79......4.2.....1........8.4.9.1.....1.27.",
+    "....1..38.9.6...1...3...2...153.9.8..2..4....6...7.......5....4.8....1.......47..",
+    "..8.53....7...4..6..48.......9..8........56..7..96...3........1.4.3.7.9..8.6....5",
+    "..9...8.3.......5.51.43....3....4.7..786..1.......2.....2.6....1....5..4...7...68",
+    "..64.5............3..61..2....8.9..7.85..1......2..4..1.....3.5.47......9.....68.",
+    ".....4....2..3..51.1..68..97.6...4..1....57...5...9.6..3......8..9.8..3......2...",
+    ".6..35.91.....7...1.7...........3......15..6.38...69....9.6..8........4.654.7..2.",
+    "...9..1.6..571.4..6...4.........13.5..1....2.456..8.....8..9...324..........7...1",
+    ".49..........86.5....5.7...98....2...2.7...1.6.......871.6.9.....84..6......2.7.1",
+    ".7..8....5.......1..25.3.4.2..3.8.........7.6..716..2.....2.4..9.........539...1.",
+    "....9....27....4....45...1..9.2..5......13.6..3.8..1..958..........79..4..6......",
+    "....5....738..4...9...17..23...9....697...5........6..52...81.9...4...2........45",
+    ".7.5......3.7..5.8.1...8..6........7...1..49.7.92..3......2..833.6..4..2...87....",
+    "8...3......3..6.9......95....86...5...7...2.62..4.5....19.....26....7......89....",
+    "..756.2....2...6.7..3..7..8.2....4..69.....1.....9....4.51.....7..25.....3..7..8.",
+    ".3.....46.961.............7....4.1....48.....5...79..8....5..2.9.....56..6...2..4",
+    ".976..5........64.1...83.....51......8..6.7.....8...3.6....1..7.......6941..5....",
+    "7....1.5...19..3285...3.6...7......3..34..9..41...3.....72...9.............758...",
+    ".2.5..4...6.7..2......18.7.......71.6...3...9..2......1....73.2.7.....944..8.....",
+    "....76..5.6......1...8...9....23....27..41.6........7.6.1...8....75.84...54...2..",
+    "..6.54.3....6..24..4..7.........3....1...6.2...394.18.3....1.....5........7.3...6",
+    "..41..7...5..4.......36...5....9.8.33...2...7......65...193....9.3..8........4.2.",
+    ".1....7....4.3.6..........3.98..7......1....8....4..2.....25..76.2..8.4.4..67....",
+    "2..7..1......32...7.164...3..6....59.9.2..6..1....43..4.........7...892.......7..",
+    ".....2.5.....517.9.....9...98......7.26...8....7.456..7.5..4......2.71....2...3..",
+    ".9..1365...8..273.5.........1....87......5.9....8.....4...715....946.2...........",
+    "3..2.19......5....6...43...1.7.624...867...5.........9.5.1....87....8.6....3.....",
+    ".9...........37..6.8....4.....1.......3..2.798.2.7..5.6...2.3179.1...........5.2.",
+    "3....427......73...7..8......3...4..5.8..9....4.....2..398..7.2..2.1.9.8...5.....",
+    "1..4...6..39.....8.28...........1........74...7..56.8...2.74.......8..13.816...4.",
+    "..14...9...2.1.64.4..3.......5.8.....6....81.1.4.........1.82..7...9.........5..3",
+    ".5.239...27......39........7..9.84........36..6...4.9......6.5.....4......785...2",
+    ".......924.....1..5..1.8..6..8.7...9.54..6......5...3...673...4.....4.5....8....7",
+    "..1...7.43..7.....8......39..6.78...7.952........4.56......21.......5...1..3...48",
+    "...4...5...4..6..3.62..7..4.1......88.....23.....6.....4.58....3...4..8.....9..72",
+    "...........1..9....8...72.4.37...1..5...8..9.1...5.7.66732..........48..2.....9..",
+    "..35.....51..4..8..4............6........2..8.81.5......2.74.1....8..5.9..9....46",
+    "6.7......4..8....1.95..4...72.........6...2.31..35.....74..31.....6..9.8...4..7..",
+    "6.3...1..9.....4.3.85.......2.....1....2.964.1...8.7......7..3.4..1.....5..4.8...",
+    "........4.93.........86.........5..85..42..7....17..4.9....7.....1..6..934....2.1",
+    "46...3....8...149..5.9....2.......6..3.......9.4...7.....8...3.62.7....4.4..1...8",
+    "8......4..517.4...2..13..8.5.2...........9..8.9....5.....9..3...7.26....4..3.7952",
+    "87...4.36..2.9..1....5.6....2.9.....76...8..2...32..61.........18.7...2.4.7.....8",
+    "......6...3.21.9.......34.1.......7..1..4.359..7.3.1...8.6......21..7...69.48....",
+    "9.7...3.......25...3......91..........25..7....4....1.6...1.27.2...3...6..5.7..4.",
+    ".5............245.2...6......1...6.7...4....3.7..53.2...32...7...67..9.......1.8.",
+    "2....5...46.....3...71.2..9......386..67....5..32...........4......975.2..2..19..",
+    ".1.25....2....9..6..4.........67.....4...2.87...1...3..9...856..35...8..8..5..1.3",
+    "....4...6.....6..88.2.5.....3.91......6....3..5....84.72....1.....427.6.....3.5..",
+    "...2.36....2.7..94..8.4.....64.....8....3..7..85..92..21......3........7.....5...",
+    "1....6......87.....5.......9...2.8...2615..7.....3..4.7.....3...8.2....6..45..2..",
+    "......71....3..96...5.....2....8725.2.........7694....4.3..8...9.....4.3....5...1",
+    "5....6.....8...9.7.2..9.1.6...321...7..........64.5....64....15.3.......8.......4",
+    "..4.......7..891..2.1...7.....4..952.6.8..........34.....7...98.4..9....3.2......"
+];
+
+const PATHOLOGICAL: [&str; 3] = [
+    "..............3.85..1.2.......5.7.....4...1...9.......5......73..2.1........4...9", // Built against top-left-first back-tracking
+    ".......1.4.........2...........5.4.7..8...3....1.9....3..4..2...5.1........8.6...", // 17 clues
+    "...........2..5.4.1.8.4..........4.3..6.5...1....2...63.1....8.2.7...6.......6139" // Hard fixture of this crate
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use crate::rating::Difficulty;
+    use crate::sudoku_solver::SudokuSolver;
+
+    // Every seventh puzzle, enough to catch regressions without solving the whole set on each run
+    fn sample(puzzles: &[&str]) -> Vec<SudokuBoard> {
+        return load(puzzles).unwrap().into_iter().step_by(7).collect();
+    }
+
+    #[test]
+    fn every_puzzle_loads() {
+        for puzzles in [easy(), medium(), hard(), pathological()].iter() {
+            let boards = load(puzzles).unwrap();
+
+            assert_eq!(boards.len(), puzzles.len());
+            assert!(boards.iter().all(|board| board.all_spaces_valid()));
+        }
+        assert_eq!((easy().len(), medium().len(), hard().len()), (100, 100, 100));
+    }
+
+    #[test]
+    fn load_reports_bad_puzzles() {
+        let error = load(&[easy()[0], "123"]).unwrap_err();
+
+        assert_eq!(error.cells_found, 3);
+    }
+
+    #[test]
+    fn solution_grids_are_distinct() {
+        let mut solutions = HashSet::new();
+        for puzzles in [easy(), medium(), hard()].iter() {
+            for board in load(puzzles).unwrap() {
+                assert!(solutions.insert(SudokuSolver::new(&board).solve().to_line()), "{}", board);
+            }
+        }
+    }
+
+    #[test]
+    fn tiers_rate_as_graded() {
+        for (puzzles, difficulty) in [(easy(), Difficulty::Easy), (medium(), Difficulty::Medium), (hard(), Difficulty::Hard)].iter() {
+            for board in sample(puzzles) {
+                assert_eq!(SudokuSolver::new(&board).rate().difficulty, *difficulty, "{}", board);
+            }
+        }
+    }
+
+    #[test]
+    fn tiers_solve_uniquely() {
+        for puzzles in [easy(), medium(), hard(), pathological()].iter() {
+            for board in sample(puzzles) {
+                let solver = SudokuSolver::new(&board);
+
+                assert_eq!(solver.count_solutions(2), 1, "{}", board);
+            }
+        }
+        for board in sample(hard()) {
+            assert!(SudokuSolver::new(&board).solve().is_solved());
+        }
+    }
+}
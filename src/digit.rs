@@ -0,0 +1,82 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt::{ self, Display, Formatter };
+use std::num::NonZeroU8;
+
+// A value that can fill a space, always [1..9] inclusive. Empty spaces are `None` rather than a 0 sentinel, so a
+// `Digit` can't be mistaken for one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Digit(NonZeroU8);
+
+impl Digit {
+    // `None` unless `value` is [1..9] inclusive
+    pub fn new(value: u8) -> Option<Digit> {
+        if value > 9 {
+            return None;
+        }
+        return NonZeroU8::new(value).map(Digit);
+    }
+
+    pub fn get(self) -> u8 {
+        return self.0.get();
+    }
+
+    // Every digit in ascending order
+    pub fn all() -> impl Iterator<Item = Digit> {
+        return (1..=9).map(|value| Digit(NonZeroU8::new(value).unwrap()));
+    }
+}
+
+impl Display for Digit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", self.0);
+    }
+}
+
+impl From<Digit> for u8 {
+    fn from(digit: Digit) -> u8 {
+        return digit.get();
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DigitOutOfRange(pub u8);
+
+impl Display for DigitOutOfRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        return write!(f, "Digits must be [1..9] inclusive, got {}", self.0);
+    }
+}
+
+impl Error for DigitOutOfRange {}
+
+impl TryFrom<u8> for Digit {
+    type Error = DigitOutOfRange;
+
+    fn try_from(value: u8) -> Result<Digit, DigitOutOfRange> {
+        return Digit::new(value).ok_or(DigitOutOfRange(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digit_bounds() {
+        assert_eq!(Digit::try_from(0), Err(DigitOutOfRange(0)));
+        assert_eq!(Digit::try_from(10), Err(DigitOutOfRange(10)));
+        assert_eq!(Digit::try_from(255), Err(DigitOutOfRange(255)));
+        assert_eq!(Digit::try_from(1).map(Digit::get), Ok(1));
+        assert_eq!(Digit::try_from(9).map(u8::from), Ok(9));
+        assert_eq!(Digit::new(0), None);
+        assert_eq!(Digit::all().map(Digit::get).collect::<Vec<u8>>(), vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(Digit::new(7).unwrap().to_string(), "7");
+        assert_eq!(DigitOutOfRange(0).to_string(), "Digits must be [1..9] inclusive, got 0");
+    }
+
+    #[test]
+    fn option_digit_is_one_byte() {
+        assert_eq!(std::mem::size_of::<Option<Digit>>(), 1);
+    }
+}
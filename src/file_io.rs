@@ -0,0 +1,507 @@
+use std::error::Error;
+use std::fmt::{ self, Display, Formatter };
+use std::fs;
+use std::io::{ self, BufRead, Write };
+use std::path::{ Path, PathBuf };
+use std::process;
+use std::sync::atomic::{ AtomicUsize, Ordering };
+use crate::sudoku_board::SudokuBoard;
+
+static TEMPORARY_FILES: AtomicUsize = AtomicUsize::new(0); // Keeps temporary names apart within one process
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Line, // 81 characters on one line
+    Grid, // 9 lines of 9 characters
+    Pretty // The `Display` grid, or anything else `SudokuBoard::parse_grid` accepts
+}
+
+impl Format {
+    // Line and Grid have to match exactly, everything else is left to the pretty parser
+    pub fn sniff(text: &str) -> Format {
+        let lines: Vec<&str> = text.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+        if lines.len() == 1 && lines[0].chars().count() == 81 {
+            return Format::Line;
+        }
+        if lines.len() == 9 && lines.iter().all(|line| line.chars().count() == 9) {
+            return Format::Grid;
+        }
+        return Format::Pretty;
+    }
+}
+
+#[derive(Debug)]
+pub enum IoParseError {
+    Io { path: PathBuf, source: io::Error },
+    Parse { path: PathBuf, line: usize, column: usize, message: String } // `line` and `column` count from 1
+}
+
+impl Display for IoParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        return match self {
+            IoParseError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            IoParseError::Parse { path, line, column, message } => write!(f, "{}:{}:{}: {}", path.display(), line, column, message)
+        };
+    }
+}
+
+impl Error for IoParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        return match self {
+            IoParseError::Io { source, .. } => Some(source),
+            IoParseError::Parse { .. } => None
+        };
+    }
+}
+
+#[derive(Debug)]
+pub enum ParseErrorAt {
+    Io { line: usize, source: io::Error },
+    Parse { line: usize, column: usize, message: String } // `line` and `column` count from 1
+}
+
+impl Display for ParseErrorAt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        return match self {
+            ParseErrorAt::Io { line, source } => write!(f, "line {}: {}", line, source),
+            ParseErrorAt::Parse { line, column, message } => write!(f, "line {}:{}: {}", line, column, message)
+        };
+    }
+}
+
+impl Error for ParseErrorAt {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        return match self {
+            ParseErrorAt::Io { source, .. } => Some(source),
+            ParseErrorAt::Parse { .. } => None
+        };
+    }
+}
+
+// Lazily reads puzzles, skipping `#` comments. With `Format::Line` every non-blank line is a puzzle, the other formats
+// read blocks of lines separated by blank lines and hand each to `SudokuBoard::parse_grid`. The line and block buffers
+// are reused, so a successful puzzle costs nothing beyond its board. Reading stops after the first I/O error.
+pub struct PuzzleReader<R: BufRead> {
+    reader: R,
+    format: Format,
+    buffer: String,
+    block: String,
+    block_line: usize, // Where the current block started
+    line: usize,
+    puzzle_line: usize, // Where the last puzzle or parse error returned started
+    finished: bool
+}
+
+impl<R: BufRead> PuzzleReader<R> {
+    pub fn new(reader: R) -> PuzzleReader<R> {
+        return PuzzleReader::with_format(reader, Format::Line);
+    }
+
+    pub fn with_format(reader: R, format: Format) -> PuzzleReader<R> {
+        return PuzzleReader { reader, format, buffer: String::new(), block: String::new(), block_line: 0, line: 0, puzzle_line: 0, finished: false };
+    }
+
+    // The line, counting from 1, where the puzzle last returned by `next` started. 0 before the first one.
+    pub fn puzzle_line(&self) -> usize {
+        return self.puzzle_line;
+    }
+
+    fn take_block(&mut self) -> Option<Result<SudokuBoard, ParseErrorAt>> {
+        if self.block.is_empty() {
+            return None;
+        }
+        self.puzzle_line = self.block_line;
+        let result = SudokuBoard::parse_grid(&self.block).map_err(|error| ParseErrorAt::Parse { line: self.block_line, column: 1, message: error.to_string() });
+        self.block.clear();
+        return Some(result);
+    }
+}
+
+impl<R: BufRead> Iterator for PuzzleReader<R> {
+    type Item = Result<SudokuBoard, ParseErrorAt>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.finished {
+            self.buffer.clear();
+            self.line += 1;
+            match self.reader.read_line(&mut self.buffer) {
+                Ok(0) => {
+                    self.finished = true;
+                    return self.take_block();
+                },
+                Ok(_) => {
+                    let text = self.buffer.trim();
+                    if text.starts_with('#') {
+                        continue;
+                    }
+                    if self.format == Format::Line {
+                        if !text.is_empty() {
+                            self.puzzle_line = self.line;
+                            return Some(parse_puzzle_line(text).map_err(|(column, message)| ParseErrorAt::Parse { line: self.line, column, message }));
+                        }
+                    }
+                    else if text.is_empty() {
+                        if let Some(result) = self.take_block() {
+                            return Some(result);
+                        }
+                    }
+                    else {
+                        if self.block.is_empty() {
+                            self.block_line = self.line;
+                        }
+                        self.block.push_str(&self.buffer);
+                    }
+                },
+                Err(source) => {
+                    self.finished = true;
+                    return Some(Err(ParseErrorAt::Io { line: self.line, source }));
+                }
+            }
+        }
+        return None;
+    }
+}
+
+// Writes puzzles one after another, blocks in the grid and pretty formats are separated by a blank line so
+// `PuzzleReader::with_format` reads them back. Nothing is buffered here, wrap unbuffered writers such as files in an
+// `io::BufWriter` and call `finish` to flush it at the end.
+pub struct PuzzleWriter<W: Write> {
+    writer: W,
+    format: Format,
+    blank: char,
+    count: usize
+}
+
+impl<W: Write> PuzzleWriter<W> {
+    pub fn new(writer: W, format: Format) -> PuzzleWriter<W> {
+        return PuzzleWriter { writer, format, blank: '0', count: 0 };
+    }
+
+    // The character written for blanks in the line and grid formats, `0` by default. The pretty format always uses `0`.
+    pub fn blank(mut self, blank: char) -> PuzzleWriter<W> {
+        self.blank = blank;
+        return self;
+    }
+
+    pub fn write(&mut self, board: &SudokuBoard) -> io::Result<()> {
+        let mut text = board.to_format(self.format);
+        if self.format != Format::Pretty && self.blank != '0' {
+            text = text.replace('0', &self.blank.to_string());
+        }
+        if self.format != Format::Line && self.count != 0 {
+            self.writer.write_all(b"\n")?;
+        }
+        self.writer.write_all(text.as_bytes())?;
+        self.count += 1;
+        return Ok(());
+    }
+
+    // A `#` comment line, skipped by `PuzzleReader`. Line breaks in `comment` start new comment lines.
+    pub fn write_comment(&mut self, comment: &str) -> io::Result<()> {
+        for line in comment.lines() {
+            writeln!(self.writer, "# {}", line)?;
+        }
+        return Ok(());
+    }
+
+    // Puzzles written so far, comments don't count
+    pub fn count(&self) -> usize {
+        return self.count;
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        return Ok(self.writer);
+    }
+}
+
+// Columns are counted in characters of the trimmed line
+fn parse_puzzle_line(text: &str) -> Result<SudokuBoard, (usize, String)> {
+    let mut puzzle = [0; 81];
+    let mut length = 0;
+    for (column_index, character) in text.chars().enumerate() {
+        if !is_cell(character) {
+            return Err((column_index + 1, format!("unexpected character '{}'", character)));
+        }
+        if length == 81 {
+            return Err((column_index + 1, String::from("more than 81 spaces")));
+        }
+        puzzle[length] = character.to_digit(10).unwrap_or(0) as u8;
+        length += 1;
+    }
+    if length != 81 {
+        return Err((1, format!("expected 81 spaces, found {}", length)));
+    }
+    return Ok(SudokuBoard::new(&puzzle));
+}
+
+struct ParseFailure {
+    line: usize,
+    column: usize,
+    message: String
+}
+
+impl SudokuBoard {
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<SudokuBoard, IoParseError> {
+        return load_text(path.as_ref(), Format::sniff);
+    }
+
+    // Always goes through `parse_grid`, so a line or grid with other blanks or stray characters loads too
+    pub fn load_from_file_lenient<P: AsRef<Path>>(path: P) -> Result<SudokuBoard, IoParseError> {
+        return load_text(path.as_ref(), |_| Format::Pretty);
+    }
+
+    // Written and synced to a temporary file next to `path` first, so readers never see a half written board. The
+    // temporary name is unique to this process and call, concurrent saves to the same path don't trample each other.
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P, format: Format) -> Result<(), IoParseError> {
+        let path = path.as_ref();
+        let io_error = |source| IoParseError::Io { path: path.to_path_buf(), source };
+        let file_name = path.file_name().ok_or_else(|| io_error(io::Error::new(io::ErrorKind::InvalidInput, "not a file path")))?;
+        let mut temporary_name = std::ffi::OsString::from(".");
+        temporary_name.push(file_name);
+        temporary_name.push(format!(".{}-{}.tmp", process::id(), TEMPORARY_FILES.fetch_add(1, Ordering::Relaxed)));
+        let temporary_path = path.with_file_name(temporary_name);
+
+        let mut file = fs::OpenOptions::new().write(true).create_new(true).open(&temporary_path).map_err(io_error)?;
+        let result = file.write_all(self.to_format(format).as_bytes())
+            .and_then(|_| file.sync_all())
+            .and_then(|_| fs::rename(&temporary_path, path));
+        if result.is_err() {
+            let _ = fs::remove_file(&temporary_path);
+        }
+        return result.map_err(io_error);
+    }
+
+    pub fn to_format(&self, format: Format) -> String {
+        let digits = self.to_line();
+        return match format {
+            Format::Line => format!("{}\n", digits),
+            Format::Grid => (0..9).map(|row| format!("{}\n", &digits[9 * row..9 * row + 9])).collect(),
+            Format::Pretty => self.to_string()
+        };
+    }
+}
+
+fn is_cell(character: char) -> bool {
+    return character.is_ascii_digit() || character == '.';
+}
+
+fn load_text(path: &Path, format_of: impl Fn(&str) -> Format) -> Result<SudokuBoard, IoParseError> {
+    let text = fs::read_to_string(path).map_err(|source| IoParseError::Io { path: path.to_path_buf(), source })?;
+
+    return parse_text(&text, format_of(&text)).map_err(|failure| IoParseError::Parse {
+        path: path.to_path_buf(),
+        line: failure.line,
+        column: failure.column,
+        message: failure.message
+    });
+}
+
+fn parse_text(text: &str, format: Format) -> Result<SudokuBoard, ParseFailure> {
+    if format == Format::Pretty {
+        return SudokuBoard::parse_grid(text).map_err(|error| ParseFailure { line: error.line, column: error.column, message: error.to_string() });
+    }
+
+    let mut values = Vec::with_capacity(81);
+    let mut last_line = 0;
+
+    for (line_index, line) in text.lines().enumerate() {
+        last_line = line_index + 1;
+        let row_start = values.len();
+        for (column_index, character) in line.chars().enumerate() {
+            if character.is_whitespace() {
+                continue;
+            }
+            if !is_cell(character) {
+                return Err(ParseFailure { line: line_index + 1, column: column_index + 1, message: format!("unexpected character '{}'", character) });
+            }
+            if values.len() == 81 {
+                return Err(ParseFailure { line: line_index + 1, column: column_index + 1, message: String::from("more than 81 spaces") });
+            }
+            values.push(character.to_digit(10).unwrap_or(0) as u8);
+        }
+
+        let row_length = values.len() - row_start;
+        if format == Format::Grid && row_length != 0 && row_length != 9 {
+            return Err(ParseFailure { line: line_index + 1, column: 1, message: format!("expected 9 spaces in the row, found {}", row_length) });
+        }
+    }
+
+    if values.len() != 81 {
+        return Err(ParseFailure { line: last_line.max(1), column: 1, message: format!("expected 81 spaces, found {}", values.len()) });
+    }
+
+    let mut puzzle = [0; 81];
+    puzzle.copy_from_slice(&values);
+    return Ok(SudokuBoard::new(&puzzle));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::hard;
+    use crate::strategies::masked_puzzle;
+    use tempfile::TempDir;
+
+    #[test]
+    fn save_and_load_round_trip() {
+        for (name, format) in [("line.txt", Format::Line), ("grid.txt", Format::Grid), ("pretty.txt", Format::Pretty)].iter() {
+            let directory = TempDir::new().unwrap();
+            let path = directory.path().join(name);
+
+            hard().save_to_file(&path, *format).unwrap();
+            hard().save_to_file(&path, *format).unwrap(); // Replaces the first save
+            let text = fs::read_to_string(&path).unwrap();
+
+            assert_eq!(Format::sniff(&text), *format);
+            assert_eq!(SudokuBoard::load_from_file(&path).unwrap(), hard());
+            assert_eq!(fs::read_dir(directory.path()).unwrap().count(), 1); // No temporary file left behind
+        }
+    }
+
+    #[test]
+    fn save_to_file_cleans_up_after_failure() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("taken");
+        fs::create_dir(&path).unwrap();
+        fs::write(path.join("occupant"), "").unwrap(); // A non-empty directory can't be renamed over
+
+        assert!(matches!(hard().save_to_file(&path, Format::Line), Err(IoParseError::Io { .. })));
+        assert_eq!(fs::read_dir(directory.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn sniff_works() {
+        let line = hard().to_format(Format::Line).replace('0', ".");
+        let grid = "000000000\n002005040\n108040000\n000000403\n006050001\n000020006\n301000080\n207000600\n000006139\n";
+
+        assert_eq!(Format::sniff(&line), Format::Line);
+        assert_eq!(Format::sniff(grid), Format::Grid);
+        assert_eq!(Format::sniff(&hard().to_string()), Format::Pretty);
+        assert_eq!(Format::sniff("1234"), Format::Pretty);
+        assert_eq!(parse_text(&line, Format::Line).ok().unwrap(), hard());
+        assert_eq!(parse_text(grid, Format::Grid).ok().unwrap(), hard());
+    }
+
+    #[test]
+    fn load_from_file_reports_position() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("bad-grid.txt");
+        fs::write(&path, "000000000\n002005040\n108040000\n000000403\n006050001\n000020006\n3010x0080\n207000600\n000006139\n").unwrap();
+
+        match SudokuBoard::load_from_file(&path) {
+            Err(IoParseError::Parse { path: error_path, line, column, .. }) => {
+                assert_eq!(error_path, path);
+                assert_eq!((line, column), (7, 5));
+            },
+            other => panic!("Expected a parse error, got {:?}", other)
+        }
+
+        let short_path = directory.path().join("short.txt");
+        fs::write(&short_path, "| 1 2 3 |\n| 4 5 6 |\n").unwrap();
+        match SudokuBoard::load_from_file(&short_path) {
+            Err(IoParseError::Parse { line, column, message, .. }) => assert_eq!((line, column, message.as_str()), (2, 8, "Expected 81 cells, found 6")),
+            other => panic!("Expected a parse error, got {:?}", other)
+        }
+
+        let missing_path = directory.path().join("missing.txt");
+        assert!(matches!(SudokuBoard::load_from_file(&missing_path), Err(IoParseError::Io { path, .. }) if path == missing_path));
+    }
+
+    #[test]
+    fn load_from_file_lenient_works() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("underscores.txt");
+        fs::write(&path, format!("{}\n", hard().to_line().replace('0', "_"))).unwrap();
+
+        assert!(matches!(SudokuBoard::load_from_file(&path), Err(IoParseError::Parse { line: 1, column: 1, .. })));
+        assert_eq!(SudokuBoard::load_from_file_lenient(&path).unwrap(), hard());
+    }
+
+    #[test]
+    fn puzzle_reader_streams_lines() {
+        let mut text = String::from("# generated puzzles\n\n");
+        for seed in 0..1000u64 {
+            match seed {
+                100 => text.push_str("12345"),
+                500 => text.push_str(&masked_puzzle(seed, 40).to_line().replacen('0', "x", 1)),
+                _ => text.push_str(&masked_puzzle(seed, 40).to_line().replace('0', "."))
+            }
+            text.push('\n');
+        }
+
+        let mut successes = 0;
+        let mut failures = Vec::new();
+        for result in PuzzleReader::new(text.as_bytes()) {
+            match result {
+                Ok(board) => {
+                    assert_eq!(board.unsolved_count(), 40);
+                    successes += 1;
+                },
+                Err(ParseErrorAt::Parse { line, column, .. }) => failures.push((line, column)),
+                Err(error) => panic!("Unexpected error {}", error)
+            }
+        }
+
+        assert_eq!(successes, 998);
+        let first_blank = masked_puzzle(500, 40).to_line().find('0').unwrap();
+        assert_eq!(failures, vec![(103, 1), (503, first_blank + 1)]);
+    }
+
+    #[test]
+    fn puzzle_reader_reports_io_errors() {
+        struct FailingReader;
+
+        impl io::Read for FailingReader {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                return Err(io::Error::other("disk on fire"));
+            }
+        }
+
+        let mut reader = PuzzleReader::new(io::BufReader::new(FailingReader));
+
+        assert!(matches!(reader.next(), Some(Err(ParseErrorAt::Io { line: 1, .. }))));
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn puzzle_writer_round_trip() {
+        let boards: Vec<SudokuBoard> = (0..20).map(|seed| masked_puzzle(seed, 45)).collect();
+
+        for format in [Format::Line, Format::Grid, Format::Pretty].iter() {
+            let mut writer = PuzzleWriter::new(Vec::new(), *format).blank('.');
+            writer.write_comment("generated\nseeds 0 to 19").unwrap();
+            for board in boards.iter() {
+                writer.write(board).unwrap();
+            }
+            assert_eq!(writer.count(), 20);
+
+            let bytes = writer.finish().unwrap();
+            let read: Vec<SudokuBoard> = PuzzleReader::with_format(&bytes[..], *format).map(Result::unwrap).collect();
+            assert_eq!(read, boards);
+        }
+    }
+
+    #[test]
+    fn puzzle_writer_line_format() {
+        let mut writer = PuzzleWriter::new(Vec::new(), Format::Line);
+        writer.write_comment("one puzzle").unwrap();
+        writer.write(&hard()).unwrap();
+
+        let text = String::from_utf8(writer.finish().unwrap()).unwrap();
+
+        assert_eq!(text, "# one puzzle\n000000000002005040108040000000000403006050001000020006301000080207000600000006139\n");
+    }
+
+    #[test]
+    fn puzzle_reader_blocks_report_first_line() {
+        let text = "# grids\n000000000\n002005040\n\n\n000000000\n";
+
+        let errors: Vec<usize> = PuzzleReader::with_format(text.as_bytes(), Format::Grid).map(|result| match result {
+            Err(ParseErrorAt::Parse { line, .. }) => line,
+            other => panic!("Expected a parse error, got {:?}", other)
+        }).collect();
+
+        assert_eq!(errors, vec![2, 6]);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::sudoku_board::SudokuBoard;
+
+// Fingerprints are part of the public format, changing anything here changes every published identifier.
+//
+// Input: the 81 character `SudokuBoard::to_line` form, row-major with `0` for blanks, as ASCII bytes. Whitespace and
+// blank markers in whatever text the board was parsed from never reach the hash.
+// Hash: 64 bit FNV-1a (offset basis 0xcbf29ce484222325, prime 0x100000001b3).
+// Encoding: RFC 4648 base32 without padding, the 64 bits most significant first behind a single 0 bit, 13 characters.
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+const PERMUTATIONS_OF_3: [[usize; 3]; 6] = [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]];
+
+impl SudokuBoard {
+    pub fn fingerprint(&self) -> String {
+        return encode(fnv1a(self.to_line().as_bytes()));
+    }
+
+    // The fingerprint of `minlex`, shared by every puzzle that is the same up to relabeling digits, transposing, and
+    // permuting bands, stacks, and the rows and columns inside them
+    pub fn family_fingerprint(&self) -> String {
+        return self.minlex().fingerprint();
+    }
+
+    // The lexicographically smallest `to_line` among all equivalent boards, trying every row and column arrangement
+    // (both orientations) and numbering the digits in order of first appearance
+    pub fn minlex(&self) -> SudokuBoard {
+        let line_orders = line_orders();
+        let mut best = [u8::MAX; 81];
+
+        for transposed in [false, true].iter() {
+            let value = |row_index: usize, column_index: usize| if *transposed { self[(column_index, row_index)] } else { self[(row_index, column_index)] };
+            for row_order in line_orders.iter() {
+                for column_order in line_orders.iter() {
+                    let mut labels = [0u8; 10];
+                    let mut next_label = 1;
+                    let mut smaller = false;
+                    for index in 0..81 {
+                        let original = value(row_order[index / 9], column_order[index % 9]);
+                        if original != 0 && labels[original as usize] == 0 {
+                            labels[original as usize] = next_label;
+                            next_label += 1;
+                        }
+                        let relabeled = labels[original as usize];
+
+                        // Abandon the arrangement as soon as it can't beat the best one found so far
+                        if !smaller {
+                            if relabeled > best[index] {
+                                break;
+                            }
+                            smaller = relabeled < best[index];
+                        }
+                        if smaller {
+                            best[index] = relabeled;
+                        }
+                    }
+                }
+            }
+        }
+
+        return SudokuBoard::new(&best);
+    }
+}
+
+// All 1296 orders of the 9 rows (or columns) that keep bands together
+fn line_orders() -> Vec<[usize; 9]> {
+    let mut orders = Vec::with_capacity(1296);
+    for band_order in PERMUTATIONS_OF_3.iter() {
+        for first in PERMUTATIONS_OF_3.iter() {
+            for second in PERMUTATIONS_OF_3.iter() {
+                for third in PERMUTATIONS_OF_3.iter() {
+                    let inner_orders = [first, second, third];
+                    let mut order = [0; 9];
+                    for (index, line) in order.iter_mut().enumerate() {
+                        *line = 3 * band_order[index / 3] + inner_orders[index / 3][index % 3];
+                    }
+                    orders.push(order);
+                }
+            }
+        }
+    }
+    return orders;
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    return bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ *byte as u64).wrapping_mul(0x0000_0100_0000_01b3));
+}
+
+fn encode(hash: u64) -> String {
+    return (0..13).rev().map(|group| BASE32_ALPHABET[((hash as u128 >> (5 * group)) & 0x1f) as usize] as char).collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{ classic, medium };
+
+    // The same puzzle with its digits relabeled, transposed, the first two bands swapped and two columns of a stack swapped
+    fn isomorphic_puzzle() -> SudokuBoard {
+        let relabel = [0, 9, 8, 7, 6, 5, 4, 3, 2, 1];
+        let row_order = [3, 4, 5, 0, 1, 2, 6, 7, 8];
+        let column_order = [1, 0, 2, 3, 4, 5, 6, 7, 8];
+        let original = classic();
+        let mut values = [0; 81];
+        for (index, value) in values.iter_mut().enumerate() {
+            *value = relabel[original[(column_order[index % 9], row_order[index / 9])] as usize];
+        }
+        return SudokuBoard::new(&values);
+    }
+
+    #[test]
+    fn fingerprint_is_pinned() {
+        let medium = medium();
+
+        assert_eq!(classic().fingerprint(), "F6VYIPQ5XYT2L");
+        assert_eq!(medium.fingerprint(), "NVK55L2TUSR6C");
+        assert_eq!(SudokuBoard::new(&[0; 81]).fingerprint(), "KAHJVNZVDJO3P");
+        assert_eq!(classic().family_fingerprint(), "KZGTEOHOB7XJY");
+        assert_eq!(classic().minlex().to_line(), "000000001000002034156000270000008000000051002329740000001600900003400700280910450");
+    }
+
+    #[test]
+    fn fingerprint_ignores_formatting() {
+        let spaced = "5 3 . | . 7 . | . . .\n6 . . | 1 9 5 | . . .\n. 9 8 | . . . | . 6 .\n8 . . | . 6 . | . . 3\n4 . . | 8 . 3 | . . 1\n7 . . | . 2 . | . . 6\n. 6 . | . . . | 2 8 .\n. . . | 4 1 9 | . . 5\n. . . | . 8 . | . 7 9\n";
+
+        let fingerprint = SudokuBoard::parse_grid(spaced).unwrap().fingerprint();
+
+        assert_eq!(fingerprint, classic().fingerprint());
+        assert_eq!(fingerprint.len(), 13);
+        assert!(fingerprint.chars().all(|character| BASE32_ALPHABET.contains(&(character as u8))));
+    }
+
+    #[test]
+    fn family_fingerprint_is_shared_by_isomorphic_puzzles() {
+        let isomorphic = isomorphic_puzzle();
+
+        assert_ne!(isomorphic.fingerprint(), classic().fingerprint());
+        assert_eq!(isomorphic.minlex(), classic().minlex());
+        assert_eq!(isomorphic.family_fingerprint(), classic().family_fingerprint());
+        assert_eq!(classic().minlex().minlex(), classic().minlex());
+    }
+
+    #[test]
+    fn fnv1a_works() {
+        // Published FNV-1a 64 test vectors
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(encode(0), "AAAAAAAAAAAAA");
+        assert_eq!(encode(u64::MAX), "P777777777777");
+    }
+}
@@ -0,0 +1,1106 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt::{ self, Display, Formatter };
+use crate::candidate_board::{ CandidateBoard, CandidateSet };
+use crate::json::{ JsonValue, ToJson };
+use crate::moves::{ MergeOptions, Move, MoveError };
+use crate::notes::{ Notes, NotesPolicy };
+use crate::peers::PEERS;
+use crate::sudoku_board::{ CellChanged, SudokuBoard };
+use crate::sudoku_solver::{ SolveError, SudokuSolver };
+use crate::techniques::{ step_hint, Hint, HintKind, HintLevel, LeveledHint, StepHint };
+use crate::uniqueness::{ is_unique, Uniqueness };
+
+pub use crate::json::JsonError; // Inside `SaveError::Json`
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionSummary {
+    pub mistakes: u32,
+    pub hints_used: u32,
+    pub moves_made: u32,
+    pub elapsed_millis: u64,
+    pub complete: bool
+}
+
+// Where the player's time went, from the timestamps passed to the `_at` moves. Paused time isn't counted anywhere.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionStats {
+    pub active_millis: u64, // Since the session started, less the pauses
+    pub paused_millis: u64,
+    pub timed_moves: u32,
+    pub cells_timed: usize, // Spaces with any time spent on them
+    pub millis_per_cell: u64, // The time spent on those spaces, averaged over them
+    pub cell_millis: [u64; 81] // Row-major, see `GameSession::per_cell_durations`
+}
+
+// The clock between timed moves. Time goes to the space of the next timed move, counted from the move before it,
+// the session start or a resume, with the time before a pause carried over in `pending_millis`.
+struct Timing {
+    last_event_at: u64,
+    pending_millis: u64,
+    paused_at: Option<u64>,
+    paused_millis: u64, // Pauses already over
+    timed_moves: u32,
+    cell_millis: [u64; 81]
+}
+
+impl Timing {
+    fn new(started_at: u64) -> Timing {
+        return Timing { last_event_at: started_at, pending_millis: 0, paused_at: None, paused_millis: 0, timed_moves: 0, cell_millis: [0; 81] };
+    }
+
+    // Paused time includes the current pause up to `now`
+    fn paused_millis(&self, now: u64) -> u64 {
+        return self.paused_millis + self.paused_at.map_or(0, |paused_at| now.saturating_sub(paused_at));
+    }
+
+    // Unattributed active time up to `now`
+    fn pending_millis(&self, now: u64) -> u64 {
+        return self.pending_millis + if self.paused_at.is_some() { 0 } else { now.saturating_sub(self.last_event_at) };
+    }
+
+    fn record(&mut self, index: usize, at: u64) {
+        self.resume(at);
+        self.cell_millis[index] += self.pending_millis(at);
+        self.pending_millis = 0;
+        self.last_event_at = at;
+        self.timed_moves += 1;
+    }
+
+    fn pause(&mut self, at: u64) -> bool {
+        if self.paused_at.is_some() {
+            return false;
+        }
+        self.pending_millis = self.pending_millis(at);
+        self.paused_at = Some(at);
+        return true;
+    }
+
+    fn resume(&mut self, at: u64) -> bool {
+        if self.paused_at.is_none() {
+            return false;
+        }
+        self.paused_millis = self.paused_millis(at);
+        self.paused_at = None;
+        self.last_event_at = at;
+        return true;
+    }
+
+    // Durations only, fixed at `now`, so `from_json` can restart the clock at any later time
+    fn to_json(&self, now: u64) -> JsonValue {
+        return JsonValue::Object(vec![
+            (String::from("paused"), self.paused_at.is_some().into()),
+            (String::from("paused_millis"), self.paused_millis(now).into()),
+            (String::from("pending_millis"), self.pending_millis(now).into()),
+            (String::from("timed_moves"), self.timed_moves.into()),
+            (String::from("cell_millis"), self.cell_millis.to_vec().into())
+        ]);
+    }
+
+    fn from_json(value: &JsonValue, now: u64) -> Option<Timing> {
+        let mut cell_millis = [0; 81];
+        let saved_cells = value.get("cell_millis")?.as_array().filter(|saved_cells| saved_cells.len() == 81)?;
+        for (millis, saved) in cell_millis.iter_mut().zip(saved_cells.iter()) {
+            *millis = saved.as_u64()?;
+        }
+        return Some(Timing {
+            last_event_at: now,
+            pending_millis: value.get("pending_millis")?.as_u64()?,
+            paused_at: if value.get("paused")?.as_bool()? { Some(now) } else { None },
+            paused_millis: value.get("paused_millis")?.as_u64()?,
+            timed_moves: u32::try_from(value.get("timed_moves")?.as_u64()?).ok()?,
+            cell_millis
+        });
+    }
+}
+
+// Everything one `enter` or `erase` changed, so `undo` can put it back
+struct HistoryEntry {
+    row: usize,
+    column: usize,
+    value: u8, // 0 for an erase
+    previous_value: u8,
+    previous_notes: Vec<(usize, CandidateSet)> // Row-major index and marks of every space whose notes changed
+}
+
+impl HistoryEntry {
+    fn to_json(&self) -> JsonValue {
+        let previous_notes = self.previous_notes.iter().map(|(index, marks)| JsonValue::Array(vec![(*index).into(), marks.to_string().into()])).collect();
+        return JsonValue::Object(vec![
+            (String::from("row"), self.row.into()),
+            (String::from("col"), self.column.into()),
+            (String::from("value"), self.value.into()),
+            (String::from("previous_value"), self.previous_value.into()),
+            (String::from("previous_notes"), JsonValue::Array(previous_notes))
+        ]);
+    }
+
+    fn from_json(value: &JsonValue) -> Option<HistoryEntry> {
+        let mut previous_notes = Vec::new();
+        for space in value.get("previous_notes")?.as_array()? {
+            match space.as_array()? {
+                [index, marks] => previous_notes.push((index.as_u64().filter(|index| *index < 81)? as usize, candidates_from_str(marks.as_str()?)?)),
+                _ => return None
+            }
+        }
+        return Some(HistoryEntry {
+            row: value.get("row")?.as_u64().filter(|row| *row < 9)? as usize,
+            column: value.get("col")?.as_u64().filter(|column| *column < 9)? as usize,
+            value: value.get("value")?.as_u64().filter(|value| *value <= 9)? as u8,
+            previous_value: value.get("previous_value")?.as_u64().filter(|value| *value <= 9)? as u8,
+            previous_notes
+        });
+    }
+}
+
+// `None` unless every character is a digit 1 to 9
+fn candidates_from_str(marks: &str) -> Option<CandidateSet> {
+    let mut candidates = CandidateSet::new();
+    for character in marks.chars() {
+        match character.to_digit(10) {
+            Some(value) if value != 0 => candidates.insert(value as u8),
+            _ => return None
+        }
+    }
+    return Some(candidates);
+}
+
+// The values of a `to_line` string, `None` unless it's exactly 81 digits
+fn values_from_line(line: &str) -> Option<[u8; 81]> {
+    let mut values = [0; 81];
+    if line.chars().count() != 81 {
+        return None;
+    }
+    for (value, character) in values.iter_mut().zip(line.chars()) {
+        *value = character.to_digit(10)? as u8;
+    }
+    return Some(values);
+}
+
+// Bumped whenever the save layout changes. Older layouts are migrated where `from_save` knows how, anything else is
+// rejected instead of guessed at. Version 1 had no timing.
+const SAVE_VERSION: u64 = 2;
+
+// Why `from_save` refused a save
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SaveError {
+    Json(JsonError),
+    UnsupportedVersion(u64),
+    Malformed(&'static str), // The named field is missing or doesn't have the expected shape
+    Inconsistent(&'static str), // Every field parses, but together they don't describe a game that could have been played
+    SolutionMismatch // The puzzle doesn't have the saved solution as its only one
+}
+
+impl Display for SaveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        return match self {
+            SaveError::Json(error) => write!(f, "{}", error),
+            SaveError::UnsupportedVersion(version) => write!(f, "Save format version {} isn't supported, expected {}", version, SAVE_VERSION),
+            SaveError::Malformed(field) => write!(f, "The save's '{}' field is missing or malformed", field),
+            SaveError::Inconsistent(reason) => write!(f, "The save is inconsistent: {}", reason),
+            SaveError::SolutionMismatch => write!(f, "The saved puzzle doesn't have the saved solution")
+        };
+    }
+}
+
+impl Error for SaveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        return match self {
+            SaveError::Json(error) => Some(error),
+            _ => None
+        };
+    }
+}
+
+impl From<JsonError> for SaveError {
+    fn from(error: JsonError) -> SaveError {
+        return SaveError::Json(error);
+    }
+}
+
+// One move still in the undo history, `index` counts from the oldest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoveRef {
+    pub index: usize,
+    pub row: usize,
+    pub column: usize,
+    pub value: u8 // 0 for an erase
+}
+
+// A player's attempt at one puzzle. There's no clock inside, callers pass timestamps in milliseconds from any epoch.
+// Notes are only kept on empty spaces: toggling one on a filled space is rejected, and entering a value clears the
+// space's notes.
+pub struct GameSession {
+    pub solver: SudokuSolver,
+    board: SudokuBoard,
+    notes: Notes,
+    notes_policy: NotesPolicy,
+    history: Vec<HistoryEntry>,
+    solution: SudokuBoard, // The only one, puzzles with more than one solution are refused since entries are graded against it
+    check_mistakes: bool,
+    mistakes: u32,
+    hints_used: u32,
+    hints_by_level: [u32; 4], // Indexed by `HintLevel`
+    moves_made: u32,
+    autofilled: u32, // Spaces `autofill` put into the history, kept apart from the moves
+    started_at: u64,
+    timing: Timing
+}
+
+impl GameSession {
+    pub fn new(puzzle: &SudokuBoard, started_at: u64) -> Result<GameSession, SolveError> {
+        if !puzzle.all_spaces_valid() {
+            return Err(SolveError::InvalidBoard);
+        }
+
+        let solver = SudokuSolver::new(puzzle);
+        let solution = solver.solve_unique()?;
+        let mut board = SudokuBoard::copy(puzzle);
+        board.lock_givens();
+
+        return Ok(GameSession {
+            solver,
+            board,
+            notes: Notes::new(),
+            notes_policy: NotesPolicy::default(),
+            history: Vec::new(),
+            solution,
+            check_mistakes: true,
+            mistakes: 0,
+            hints_used: 0,
+            hints_by_level: [0; 4],
+            moves_made: 0,
+            autofilled: 0,
+            started_at,
+            timing: Timing::new(started_at)
+        });
+    }
+
+    pub fn check_mistakes(mut self, check_mistakes: bool) -> GameSession {
+        self.check_mistakes = check_mistakes;
+        return self;
+    }
+
+    pub fn notes_policy(mut self, notes_policy: NotesPolicy) -> GameSession {
+        self.notes_policy = notes_policy;
+        return self;
+    }
+
+    pub fn board(&self) -> &SudokuBoard {
+        return &self.board;
+    }
+
+    // Hears every change to the board's values like `SudokuBoard::set_observer`, including the ones `undo` and
+    // `autofill` make. Notes aren't reported.
+    pub fn set_observer(&mut self, observer: impl FnMut(CellChanged) + Send + 'static) {
+        self.board.set_observer(observer);
+    }
+
+    pub fn notes(&self) -> &Notes {
+        return &self.notes;
+    }
+
+    // Whether the note is set afterwards, notes don't count as moves
+    pub fn toggle_note(&mut self, row: usize, column: usize, value: u8) -> Result<bool, MoveError> {
+        if row > 8 || column > 8 {
+            return Err(MoveError::OutOfBounds { row, column });
+        }
+        if !(1..=9).contains(&value) {
+            return Err(MoveError::ValueOutOfRange(value));
+        }
+        if self.board[(row, column)] != 0 {
+            return Err(MoveError::SpaceFilled { row, column });
+        }
+        return Ok(self.notes.toggle_note(row, column, value));
+    }
+
+    pub fn notes_at(&self, row: usize, column: usize) -> CandidateSet {
+        return self.notes.notes_at(row, column);
+    }
+
+    pub fn clear_notes_at(&mut self, row: usize, column: usize) {
+        self.notes.clear_notes_at(row, column);
+    }
+
+    // Whether `value` matches the solution. Wrong entries stay on the board and count as a mistake while checking is on.
+    pub fn enter(&mut self, row: usize, column: usize, value: u8) -> Result<bool, MoveError> {
+        let previous_value = self.value_at(row, column);
+        self.board.apply_move(&Move::place(row, column, value))?;
+        let mut previous_notes = vec![(9 * row + column, self.notes.notes_at(row, column))];
+        self.notes.clear_notes_at(row, column);
+        if self.notes_policy == NotesPolicy::AutoPrune {
+            for peer in PEERS[9 * row + column].iter() {
+                let marks = self.notes.notes_at(peer / 9, peer % 9);
+                if marks.contains(value) {
+                    previous_notes.push((*peer, marks));
+                    self.notes.toggle_note(peer / 9, peer % 9, value);
+                }
+            }
+        }
+        self.history.push(HistoryEntry { row, column, value, previous_value, previous_notes });
+        self.moves_made += 1;
+
+        let is_correct = self.solution[(row, column)] == value;
+        if self.check_mistakes && !is_correct {
+            self.mistakes += 1;
+        }
+        return Ok(is_correct);
+    }
+
+    pub fn erase(&mut self, row: usize, column: usize) -> Result<(), MoveError> {
+        let previous_value = self.value_at(row, column);
+        self.board.apply_move(&Move::erase(row, column))?;
+        self.history.push(HistoryEntry { row, column, value: 0, previous_value, previous_notes: Vec::new() });
+        self.moves_made += 1;
+        return Ok(());
+    }
+
+    // `enter` made at `at`, the time since the last timed move goes to this space. A paused session resumes first.
+    // Rejected moves aren't timed, their time goes to the next move that goes through.
+    pub fn enter_at(&mut self, row: usize, column: usize, value: u8, at: u64) -> Result<bool, MoveError> {
+        let is_correct = self.enter(row, column, value)?;
+        self.timing.record(9 * row + column, at);
+        return Ok(is_correct);
+    }
+
+    pub fn erase_at(&mut self, row: usize, column: usize, at: u64) -> Result<(), MoveError> {
+        self.erase(row, column)?;
+        self.timing.record(9 * row + column, at);
+        return Ok(());
+    }
+
+    // `false` when already paused
+    pub fn pause(&mut self, at: u64) -> bool {
+        return self.timing.pause(at);
+    }
+
+    // `false` when not paused
+    pub fn resume(&mut self, at: u64) -> bool {
+        return self.timing.resume(at);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        return self.timing.paused_at.is_some();
+    }
+
+    // Milliseconds spent on each space row-major, from the timed moves only
+    pub fn per_cell_durations(&self) -> [u64; 81] {
+        return self.timing.cell_millis;
+    }
+
+    // Milliseconds since the session started, less every pause
+    pub fn total_active_time(&self, now: u64) -> u64 {
+        return now.saturating_sub(self.started_at).saturating_sub(self.timing.paused_millis(now));
+    }
+
+    pub fn stats(&self, now: u64) -> SessionStats {
+        let cells_timed = self.timing.cell_millis.iter().filter(|millis| **millis > 0).count();
+        return SessionStats {
+            active_millis: self.total_active_time(now),
+            paused_millis: self.timing.paused_millis(now),
+            timed_moves: self.timing.timed_moves,
+            cells_timed,
+            millis_per_cell: self.timing.cell_millis.iter().sum::<u64>().checked_div(cells_timed as u64).unwrap_or(0),
+            cell_millis: self.timing.cell_millis
+        };
+    }
+
+    // Giving up: every space is filled from the solution, wrong entries included, and returns how many spaces changed.
+    // Each changed space goes into the history with its notes, so `undo` takes the autofill back one space at a time.
+    // Counted apart from the moves, and never as mistakes.
+    pub fn autofill(&mut self) -> usize {
+        let entries: Vec<HistoryEntry> = (0..81).map(|index| (index / 9, index % 9))
+            .filter(|space| self.board[*space] != self.solution[*space])
+            .map(|(row, column)| HistoryEntry {
+                row,
+                column,
+                value: self.solution[(row, column)],
+                previous_value: self.board[(row, column)],
+                previous_notes: vec![(9 * row + column, self.notes.notes_at(row, column))]
+            })
+            .collect();
+
+        let changed = self.board.fill_empty_from_with(&self.solution, &MergeOptions::new().overwrite(true)).expect("The solution agrees with every given");
+        for entry in entries.iter() {
+            self.notes.clear_notes_at(entry.row, entry.column);
+        }
+        self.autofilled += entries.len() as u32;
+        self.history.extend(entries);
+        return changed;
+    }
+
+    // 0 outside the board, so the move itself gets to report the bad coordinates
+    fn value_at(&self, row: usize, column: usize) -> u8 {
+        return if row > 8 || column > 8 { 0 } else { self.board[(row, column)] };
+    }
+
+    // Reverts the last `enter` or `erase` along with any notes it changed, `false` when there's nothing to undo. Undoing
+    // doesn't take back the move or mistake counts.
+    pub fn undo(&mut self) -> bool {
+        let entry = match self.history.pop() {
+            Some(entry) => entry,
+            None => return false
+        };
+        self.board.set_value(entry.row, entry.column, entry.previous_value);
+        for (index, marks) in entry.previous_notes {
+            self.notes.set_notes_at(index / 9, index % 9, marks);
+        }
+        return true;
+    }
+
+    // The earliest move in the history whose value is still on the board and disagrees with the solution. Wrong values
+    // the player already overwrote or erased don't count.
+    pub fn first_mistake(&self) -> Option<MoveRef> {
+        return self.history.iter().enumerate()
+            .filter(|(index, entry)| entry.value != 0 && entry.value != self.solution[(entry.row, entry.column)]
+                && self.history[index + 1..].iter().all(|later| (later.row, later.column) != (entry.row, entry.column)))
+            .map(|(index, entry)| MoveRef { index, row: entry.row, column: entry.column, value: entry.value })
+            .next();
+    }
+
+    // Undoes every move from the first mistake on, notes included, and returns that mistake. The moves made after it
+    // are undone too since they may have been built on it.
+    pub fn rollback_to_before_mistake(&mut self) -> Option<MoveRef> {
+        let mistake = self.first_mistake();
+        if let Some(mistake) = mistake {
+            while self.history.len() > mistake.index {
+                self.undo();
+            }
+        }
+        return mistake;
+    }
+
+    // Only counted when a hint is actually given, the board is left for the player to fill in
+    pub fn request_hint(&mut self) -> Option<Hint> {
+        let hint = self.board.hint();
+        if hint.is_some() {
+            self.count_hint(HintLevel::Full);
+        }
+        return hint;
+    }
+
+    // Like `request_hint`, but may suggest an elimination when no placement is available. The player's own notes are
+    // used as the candidates of the spaces they've marked. With `apply_elimination` an elimination is also made in the
+    // notes, spaces without notes first getting every value still possible there.
+    pub fn request_step_hint(&mut self, apply_elimination: bool) -> Option<StepHint> {
+        let hint = step_hint(&self.candidates())?;
+        self.count_hint(HintLevel::Full);
+        if let (true, HintKind::Eliminate { cells, value }) = (apply_elimination, &hint.kind) {
+            let computed = CandidateBoard::new(&self.board);
+            for &(row, column) in cells.iter() {
+                let mut marks = self.notes.notes_at(row, column);
+                if marks.is_empty() {
+                    marks = computed.get(row, column);
+                }
+                marks.remove(*value);
+                self.notes.set_notes_at(row, column, marks);
+            }
+        }
+        return Some(hint);
+    }
+
+    // Every value still possible in each space, narrowed to the player's notes where there are any
+    fn candidates(&self) -> CandidateBoard {
+        let mut candidates = CandidateBoard::new(&self.board);
+        for index in 0..81 {
+            let marks = self.notes.notes_at(index / 9, index % 9);
+            if !marks.is_empty() {
+                let computed = candidates.get(index / 9, index % 9);
+                candidates.set(index / 9, index % 9, CandidateSet::from_bits(computed.bits() & marks.bits()));
+            }
+        }
+        return candidates;
+    }
+
+    pub fn request_hint_leveled(&mut self, level: HintLevel) -> Option<LeveledHint> {
+        let hint = self.board.hint_leveled(level);
+        if hint.is_some() {
+            self.count_hint(level);
+        }
+        return hint;
+    }
+
+    // `request_hint` counts as `HintLevel::Full`
+    pub fn hints_used_at(&self, level: HintLevel) -> u32 {
+        return self.hints_by_level[level as usize];
+    }
+
+    fn count_hint(&mut self, level: HintLevel) {
+        self.hints_used += 1;
+        self.hints_by_level[level as usize] += 1;
+    }
+
+    pub fn is_complete(&self) -> bool {
+        return self.board.is_solved();
+    }
+
+    pub fn summary(&self, now: u64) -> SessionSummary {
+        return SessionSummary {
+            mistakes: self.mistakes,
+            hints_used: self.hints_used,
+            moves_made: self.moves_made,
+            elapsed_millis: now.saturating_sub(self.started_at),
+            complete: self.is_complete()
+        };
+    }
+
+    // The whole session as JSON. The elapsed time is fixed at `now`, so the clock doesn't run while the game is closed.
+    pub fn to_save(&self, now: u64) -> String {
+        let notes_policy = match self.notes_policy {
+            NotesPolicy::Manual => "manual",
+            NotesPolicy::AutoPrune => "auto_prune"
+        };
+        return JsonValue::Object(vec![
+            (String::from("version"), SAVE_VERSION.into()),
+            (String::from("puzzle"), self.solver.board.to_line().into()),
+            (String::from("board"), self.board.to_line().into()),
+            (String::from("solution"), self.solution.to_line().into()),
+            (String::from("unique"), true.into()), // Always, kept so older saves of ambiguous puzzles are told apart
+            (String::from("notes"), self.notes.to_json()),
+            (String::from("notes_policy"), notes_policy.into()),
+            (String::from("check_mistakes"), self.check_mistakes.into()),
+            (String::from("mistakes"), self.mistakes.into()),
+            (String::from("hints_used"), self.hints_used.into()),
+            (String::from("hints_by_level"), self.hints_by_level.to_vec().into()),
+            (String::from("moves_made"), self.moves_made.into()),
+            (String::from("autofilled"), self.autofilled.into()),
+            (String::from("elapsed_millis"), now.saturating_sub(self.started_at).into()),
+            (String::from("timing"), self.timing.to_json(now)),
+            (String::from("history"), JsonValue::Array(self.history.iter().map(HistoryEntry::to_json).collect()))
+        ]).to_string();
+    }
+
+    // The inverse of `to_save`, with the clock picking up again at `now`. The fields are checked against each other
+    // before anything is handed back: undoing the whole history has to lead from the board back to the puzzle, and the
+    // puzzle still has to have the saved solution as its only one.
+    pub fn from_save(save: &str, now: u64) -> Result<GameSession, SaveError> {
+        let json = JsonValue::parse(save)?;
+        let version = json.get("version").and_then(JsonValue::as_u64).ok_or(SaveError::Malformed("version"))?;
+        if version != 1 && version != SAVE_VERSION {
+            return Err(SaveError::UnsupportedVersion(version));
+        }
+
+        let line = |field| json.get(field).and_then(JsonValue::as_str).and_then(values_from_line).ok_or(SaveError::Malformed(field));
+        let flag = |field| json.get(field).and_then(JsonValue::as_bool).ok_or(SaveError::Malformed(field));
+        let count = |value: Option<&JsonValue>, field| value.and_then(JsonValue::as_u64).and_then(|count| u32::try_from(count).ok()).ok_or(SaveError::Malformed(field));
+
+        let puzzle_values = line("puzzle")?;
+        let values = line("board")?;
+        let solution = SudokuBoard::new(&line("solution")?);
+        let unique = flag("unique")?;
+        let notes = json.get("notes").and_then(Notes::from_json).ok_or(SaveError::Malformed("notes"))?;
+        let notes_policy = match json.get("notes_policy").and_then(JsonValue::as_str) {
+            Some("manual") => NotesPolicy::Manual,
+            Some("auto_prune") => NotesPolicy::AutoPrune,
+            _ => return Err(SaveError::Malformed("notes_policy"))
+        };
+        let check_mistakes = flag("check_mistakes")?;
+        let mistakes = count(json.get("mistakes"), "mistakes")?;
+        let hints_used = count(json.get("hints_used"), "hints_used")?;
+        let mut hints_by_level = [0; 4];
+        let levels = json.get("hints_by_level").and_then(JsonValue::as_array).filter(|levels| levels.len() == 4).ok_or(SaveError::Malformed("hints_by_level"))?;
+        for (hints, level) in hints_by_level.iter_mut().zip(levels.iter()) {
+            *hints = count(Some(level), "hints_by_level")?;
+        }
+        let moves_made = count(json.get("moves_made"), "moves_made")?;
+        let autofilled = match version {
+            1 => 0, // Saved before there was an autofill
+            _ => count(json.get("autofilled"), "autofilled")?
+        };
+        let elapsed_millis = json.get("elapsed_millis").and_then(JsonValue::as_u64).ok_or(SaveError::Malformed("elapsed_millis"))?;
+        let timing = match version {
+            1 => Timing::new(now.saturating_sub(elapsed_millis)), // Nothing timed yet, all the time so far goes to the next timed move
+            _ => json.get("timing").and_then(|timing| Timing::from_json(timing, now)).ok_or(SaveError::Malformed("timing"))?
+        };
+        let history = json.get("history").and_then(JsonValue::as_array)
+            .and_then(|entries| entries.iter().map(HistoryEntry::from_json).collect::<Option<Vec<HistoryEntry>>>())
+            .ok_or(SaveError::Malformed("history"))?;
+
+        let puzzle = SudokuBoard::new(&puzzle_values);
+        if !puzzle.all_spaces_valid() {
+            return Err(SaveError::Inconsistent("the puzzle breaks the rules"));
+        }
+        if !solution.is_solved() || solution.is_consistent_extension_of(&puzzle).is_err() {
+            return Err(SaveError::SolutionMismatch);
+        }
+        if !unique || is_unique(&puzzle) != Uniqueness::Unique {
+            return Err(SaveError::SolutionMismatch);
+        }
+
+        if (0..81).any(|index| values[index] != 0 && !notes.notes_at(index / 9, index % 9).is_empty()) {
+            return Err(SaveError::Inconsistent("a filled space has notes"));
+        }
+        let mut replayed = values;
+        for entry in history.iter().rev() {
+            let index = 9 * entry.row + entry.column;
+            if replayed[index] != entry.value || puzzle.is_given(entry.row, entry.column) {
+                return Err(SaveError::Inconsistent("the history doesn't lead to the board"));
+            }
+            replayed[index] = entry.previous_value;
+        }
+        if replayed != puzzle_values {
+            return Err(SaveError::Inconsistent("the history doesn't lead to the board"));
+        }
+        if history.len() as u64 > u64::from(moves_made) + u64::from(autofilled) {
+            return Err(SaveError::Inconsistent("the history is longer than the moves made"));
+        }
+        if hints_by_level.iter().map(|hints| u64::from(*hints)).sum::<u64>() != u64::from(hints_used) {
+            return Err(SaveError::Inconsistent("the hint counts disagree"));
+        }
+        if timing.paused_millis > elapsed_millis || timing.timed_moves > moves_made {
+            return Err(SaveError::Inconsistent("the timing doesn't fit the session"));
+        }
+
+        let mut board = SudokuBoard::copy(&puzzle);
+        board.lock_givens();
+        for (index, value) in values.iter().enumerate() {
+            board.set_value(index / 9, index % 9, *value); // Leaves the givens as they were
+        }
+
+        return Ok(GameSession {
+            solver: SudokuSolver::new(&puzzle),
+            board,
+            notes,
+            notes_policy,
+            history,
+            solution,
+            check_mistakes,
+            mistakes,
+            hints_used,
+            hints_by_level,
+            moves_made,
+            autofilled,
+            started_at: now.saturating_sub(elapsed_millis),
+            timing
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{ easy, medium };
+
+    #[test]
+    fn short_game_works() {
+        let mut session = GameSession::new(&easy(), 1_000).unwrap();
+
+        assert_eq!(session.enter(0, 0, 6), Ok(true));
+        assert_eq!(session.enter(2, 3, 1), Ok(false));
+        session.erase(2, 3).unwrap();
+        assert_eq!(session.enter(2, 3, 6), Ok(true));
+
+        let hint = session.request_hint().unwrap();
+        assert_eq!((hint.row, hint.column, hint.value), (2, 4, 1));
+        assert_eq!(session.enter(hint.row, hint.column, hint.value), Ok(true));
+        assert_eq!(session.enter(6, 3, 1), Ok(true));
+        assert!(!session.is_complete());
+        assert_eq!(session.enter(8, 8, 8), Ok(true));
+
+        assert!(session.is_complete());
+        assert_eq!(session.summary(91_000), SessionSummary {
+            mistakes: 1,
+            hints_used: 1,
+            moves_made: 7,
+            elapsed_millis: 90_000,
+            complete: true
+        });
+    }
+
+    #[test]
+    fn givens_are_protected() {
+        let mut session = GameSession::new(&easy(), 0).unwrap();
+
+        assert_eq!(session.enter(0, 1, 6), Err(MoveError::LockedGiven { row: 0, column: 1 }));
+        assert_eq!(session.erase(0, 1), Err(MoveError::LockedGiven { row: 0, column: 1 }));
+        assert_eq!(session.summary(0).moves_made, 0);
+    }
+
+    #[test]
+    fn mistake_checking_can_be_disabled() {
+        let mut session = GameSession::new(&easy(), 0).unwrap().check_mistakes(false);
+
+        assert_eq!(session.enter(0, 0, 1), Ok(false));
+        assert_eq!(session.summary(0).mistakes, 0);
+    }
+
+    #[test]
+    fn invalid_puzzle() {
+        let mut values = [0; 81];
+        values[0] = 1;
+        values[1] = 1;
+
+        assert!(matches!(GameSession::new(&SudokuBoard::new(&values), 0), Err(SolveError::InvalidBoard)));
+    }
+
+    #[test]
+    fn notes_work() {
+        let mut session = GameSession::new(&easy(), 0).unwrap();
+
+        assert_eq!(session.toggle_note(2, 3, 1), Ok(true));
+        assert_eq!(session.toggle_note(2, 3, 6), Ok(true));
+        assert_eq!(session.toggle_note(2, 3, 1), Ok(false));
+        assert_eq!(session.notes_at(2, 3).iter().collect::<Vec<u8>>(), vec![6]);
+        assert_eq!(session.toggle_note(0, 1, 6), Err(MoveError::SpaceFilled { row: 0, column: 1 }));
+        assert_eq!(session.toggle_note(0, 0, 10), Err(MoveError::ValueOutOfRange(10)));
+        assert_eq!(session.summary(0).moves_made, 0);
+
+        // Filling the space drops its notes, and new ones are rejected until it's erased again
+        session.enter(2, 3, 6).unwrap();
+        assert!(session.notes_at(2, 3).is_empty());
+        assert_eq!(session.toggle_note(2, 3, 1), Err(MoveError::SpaceFilled { row: 2, column: 3 }));
+        session.erase(2, 3).unwrap();
+        assert_eq!(session.toggle_note(2, 3, 1), Ok(true));
+
+        session.clear_notes_at(2, 3);
+        assert!(session.notes().is_empty());
+    }
+
+    #[test]
+    fn auto_prune_and_undo() {
+        let mut session = GameSession::new(&SudokuBoard::new(&[
+            0,0,3, 8,9,4, 5,1,2,
+            9,1,2, 7,3,5, 4,8,6,
+            8,4,5, 0,0,2, 9,7,3,
+            7,9,8, 2,6,1, 3,5,4,
+            5,2,6, 4,7,3, 8,9,1,
+            1,3,4, 5,8,9, 2,6,7,
+            4,6,9, 0,2,8, 7,3,5,
+            2,8,7, 3,5,6, 1,4,9,
+            3,5,1, 9,4,7, 6,2,0
+        ]), 0).unwrap().notes_policy(NotesPolicy::AutoPrune);
+        for &(row, column, value) in [(0, 0, 6), (0, 0, 7), (0, 1, 6), (0, 1, 7), (2, 3, 6), (2, 4, 1), (6, 3, 6), (8, 8, 8)].iter() {
+            session.toggle_note(row, column, value).unwrap();
+        }
+        let notes_before = *session.notes();
+
+        assert_eq!(session.enter(0, 0, 6), Ok(true));
+        assert!(session.notes_at(0, 0).is_empty());
+        assert_eq!(session.notes_at(0, 1).iter().collect::<Vec<u8>>(), vec![7]);
+        assert_eq!(session.notes_at(2, 3).iter().collect::<Vec<u8>>(), vec![6]); // Not a peer of (0, 0)
+        assert_eq!(session.notes_at(8, 8).iter().collect::<Vec<u8>>(), vec![8]);
+
+        assert!(session.undo());
+        assert_eq!(session.board()[(0, 0)], 0);
+        assert_eq!(*session.notes(), notes_before);
+        assert!(!session.undo());
+    }
+
+    #[test]
+    fn manual_notes_policy_keeps_peers() {
+        let mut session = GameSession::new(&easy(), 0).unwrap();
+        session.toggle_note(2, 3, 6).unwrap();
+
+        session.enter(0, 0, 6).unwrap();
+        assert!(session.notes_at(2, 3).contains(6));
+
+        session.erase(0, 0).unwrap();
+        assert!(session.undo());
+        assert_eq!(session.board()[(0, 0)], 6);
+        assert!(session.undo());
+        assert_eq!(session.board()[(0, 0)], 0);
+        assert_eq!(session.summary(0).moves_made, 2);
+    }
+
+    #[test]
+    fn leveled_hints_are_counted() {
+        let mut session = GameSession::new(&easy(), 0).unwrap();
+
+        let nudge = session.request_hint_leveled(HintLevel::Nudge).unwrap();
+        let cell = session.request_hint_leveled(HintLevel::Cell).unwrap();
+        session.request_hint().unwrap();
+
+        assert_eq!(nudge.nonet, cell.nonet);
+        assert_eq!(session.hints_used_at(HintLevel::Nudge), 1);
+        assert_eq!(session.hints_used_at(HintLevel::House), 0);
+        assert_eq!(session.hints_used_at(HintLevel::Cell), 1);
+        assert_eq!(session.hints_used_at(HintLevel::Full), 1);
+        assert_eq!(session.summary(0).hints_used, 3);
+    }
+
+    #[test]
+    fn first_mistake_and_rollback() {
+        let solution = SudokuSolver::new(&medium()).solve();
+        let mut session = GameSession::new(&medium(), 0).unwrap().notes_policy(NotesPolicy::AutoPrune);
+        let wrong_value = |row: usize, column: usize| (1..=9).find(|value| *value != solution[(row, column)]).unwrap();
+
+        session.enter(0, 2, solution[(0, 2)]).unwrap();
+        session.enter(0, 4, wrong_value(0, 4)).unwrap(); // Corrected straight away, so not a mistake any more
+        session.enter(0, 4, solution[(0, 4)]).unwrap();
+        session.toggle_note(1, 1, wrong_value(1, 2)).unwrap();
+        session.toggle_note(1, 3, wrong_value(1, 2)).unwrap();
+        let board_before = session.board().to_line();
+        let notes_before = *session.notes();
+
+        session.enter(1, 2, wrong_value(1, 2)).unwrap(); // Move 3, counting from 0, prunes the notes in its row
+        session.enter(1, 1, solution[(1, 1)]).unwrap();
+        session.enter(2, 2, wrong_value(2, 2)).unwrap();
+        session.enter(8, 8, solution[(8, 8)]).unwrap();
+
+        assert_eq!(session.first_mistake(), Some(MoveRef { index: 3, row: 1, column: 2, value: wrong_value(1, 2) }));
+        assert_eq!(session.rollback_to_before_mistake(), Some(MoveRef { index: 3, row: 1, column: 2, value: wrong_value(1, 2) }));
+        assert_eq!(session.board().to_line(), board_before);
+        assert_eq!(*session.notes(), notes_before);
+        assert_eq!(session.first_mistake(), None);
+        assert_eq!(session.rollback_to_before_mistake(), None);
+    }
+
+    #[test]
+    fn new_rejects_multiple_solutions() {
+        let mut values = [0; 81];
+        values[0] = 1;
+
+        assert!(matches!(GameSession::new(&SudokuBoard::new(&values), 0), Err(SolveError::MultipleSolutions)));
+    }
+
+    #[test]
+    fn step_hint_applies_elimination() {
+        // Singles have run dry, and the top-left nonet can only have its 4 in the top row
+        let mut session = GameSession::new(&SudokuBoard::new(&[
+            5,0,0, 0,0,8, 0,0,0,
+            2,8,3, 0,0,1, 0,0,0,
+            0,9,7, 0,0,0, 5,8,0,
+            8,0,0, 1,3,0, 0,6,0,
+            7,0,0, 6,0,0, 3,0,8,
+            3,2,6, 8,0,0, 0,5,0,
+            9,0,2, 0,8,0, 0,0,0,
+            0,0,8, 2,4,3, 0,9,5,
+            4,3,5, 9,1,6, 8,0,0
+        ]), 0).unwrap();
+        session.toggle_note(0, 3, 3).unwrap();
+        session.toggle_note(0, 3, 4).unwrap();
+
+        let hint = session.request_step_hint(true).unwrap();
+
+        assert_eq!(hint.kind, HintKind::Eliminate { cells: vec![(0, 3), (0, 6), (0, 7), (0, 8)], value: 4 });
+        assert_eq!(session.notes_at(0, 3).iter().collect::<Vec<u8>>(), vec![3]);
+        assert_eq!(session.notes_at(0, 6).iter().collect::<Vec<u8>>(), vec![1, 2, 6, 7, 9]);
+        assert_eq!(session.summary(0).hints_used, 1);
+
+        // Applied, the same elimination isn't suggested again
+        assert_ne!(session.request_step_hint(false).map(|hint| hint.kind), Some(HintKind::Eliminate { cells: vec![(0, 3), (0, 6), (0, 7), (0, 8)], value: 4 }));
+    }
+
+    // A session partway through the medium puzzle with notes, an erase, a wrong entry and a hint behind it
+    fn mid_game() -> GameSession {
+        let solution = SudokuSolver::new(&medium()).solve();
+        let mut session = GameSession::new(&medium(), 1_000).unwrap().notes_policy(NotesPolicy::AutoPrune);
+        session.toggle_note(0, 2, 3).unwrap();
+        session.toggle_note(0, 2, solution[(0, 2)]).unwrap();
+        session.toggle_note(1, 1, solution[(0, 2)]).unwrap();
+        session.toggle_note(8, 0, 3).unwrap();
+        session.enter(0, 2, solution[(0, 2)]).unwrap();
+        session.enter(0, 4, (1..=9).find(|value| *value != solution[(0, 4)]).unwrap()).unwrap();
+        session.erase(0, 4).unwrap();
+        session.enter(1, 1, solution[(1, 1)]).unwrap();
+        session.request_hint_leveled(HintLevel::Cell).unwrap();
+        return session;
+    }
+
+    #[test]
+    fn save_round_trip() {
+        let mut session = mid_game();
+        let save = session.to_save(61_000);
+
+        let mut resumed = GameSession::from_save(&save, 500_000).unwrap();
+
+        assert_eq!(resumed.board().to_line(), session.board().to_line());
+        assert!(resumed.board().givens_locked());
+        assert!((0..81).all(|index| resumed.board().is_given(index / 9, index % 9) == session.board().is_given(index / 9, index % 9)));
+        assert_eq!(*resumed.notes(), *session.notes());
+        assert_eq!(resumed.summary(530_000), SessionSummary { elapsed_millis: 90_000, ..session.summary(91_000) });
+        assert_eq!(resumed.hints_used_at(HintLevel::Cell), 1);
+        assert_eq!(resumed.to_save(500_000), save);
+
+        // Both go on the same way from here, undoing back through the saved history
+        for _ in 0..4 {
+            assert_eq!(resumed.undo(), session.undo());
+            assert_eq!(resumed.board().to_line(), session.board().to_line());
+            assert_eq!(*resumed.notes(), *session.notes());
+        }
+        assert_eq!(resumed.board().to_line(), medium().to_line());
+        assert!(!resumed.undo());
+        assert_eq!(resumed.enter(0, 2, 3), session.enter(0, 2, 3));
+        assert_eq!(resumed.summary(0).mistakes, session.summary(0).mistakes);
+    }
+
+    #[test]
+    fn timed_session_works() {
+        let mut session = GameSession::new(&easy(), 0).unwrap();
+
+        assert_eq!(session.enter_at(0, 0, 6, 10_000), Ok(true));
+        assert!(session.pause(15_000));
+        assert!(!session.pause(20_000));
+        assert!(session.resume(75_000));
+        assert_eq!(session.enter_at(2, 3, 1, 80_000), Ok(false)); // The 5 seconds before the pause count too
+        assert!(session.enter_at(0, 1, 1, 81_000).is_err());
+        session.erase_at(2, 3, 82_000).unwrap();
+        assert_eq!(session.enter_at(2, 3, 6, 90_000), Ok(true));
+
+        let durations = session.per_cell_durations();
+        assert_eq!((durations[0], durations[21]), (10_000, 20_000));
+        assert_eq!(durations.iter().sum::<u64>(), 30_000);
+        assert_eq!(session.total_active_time(100_000), 40_000);
+        assert_eq!(session.stats(100_000), SessionStats {
+            active_millis: 40_000,
+            paused_millis: 60_000,
+            timed_moves: 4,
+            cells_timed: 2,
+            millis_per_cell: 15_000,
+            cell_millis: durations
+        });
+
+        // Saved while paused, the time the game is closed counts for nothing
+        assert!(session.pause(100_000));
+        let mut resumed = GameSession::from_save(&session.to_save(110_000), 1_000_000).unwrap();
+        assert!(resumed.is_paused());
+        assert_eq!(resumed.stats(1_000_000), session.stats(110_000));
+        assert!(resumed.resume(1_005_000));
+        assert_eq!(resumed.enter_at(2, 4, 1, 1_007_000), Ok(true));
+        assert_eq!(resumed.per_cell_durations()[22], 12_000);
+        assert_eq!(resumed.total_active_time(1_007_000), 42_000);
+    }
+
+    #[test]
+    fn from_save_rejects_multiple_solutions() {
+        let mut values = [0; 81];
+        values[0] = 1;
+        let ambiguous = SudokuBoard::new(&values);
+        let mut json = JsonValue::parse(&GameSession::new(&medium(), 0).unwrap().to_save(0)).unwrap();
+        if let JsonValue::Object(fields) = &mut json {
+            for (name, value) in fields.iter_mut() {
+                match name.as_str() {
+                    "puzzle" | "board" => *value = ambiguous.to_line().into(),
+                    "solution" => *value = SudokuSolver::new(&ambiguous).solve().to_line().into(),
+                    _ => {}
+                }
+            }
+        }
+
+        assert!(matches!(GameSession::from_save(&json.to_string(), 0), Err(SaveError::SolutionMismatch)));
+    }
+
+    // The mid-game save with one field replaced
+    fn with_field(field: &str, value: JsonValue) -> String {
+        let mut json = JsonValue::parse(&mid_game().to_save(0)).unwrap();
+        if let JsonValue::Object(fields) = &mut json {
+            fields.iter_mut().find(|(name, _)| name == field).unwrap().1 = value;
+        }
+        return json.to_string();
+    }
+
+    #[test]
+    fn version_1_save_loads() {
+        let session = mid_game();
+        let mut json = JsonValue::parse(&session.to_save(61_000)).unwrap();
+        if let JsonValue::Object(fields) = &mut json {
+            fields.retain(|(name, _)| name != "timing");
+            fields[0].1 = 1.into();
+        }
+
+        let mut resumed = GameSession::from_save(&json.to_string(), 500_000).unwrap();
+
+        assert_eq!(resumed.board().to_line(), session.board().to_line());
+        assert_eq!(resumed.summary(530_000), SessionSummary { elapsed_millis: 90_000, ..session.summary(91_000) });
+        assert_eq!(resumed.stats(530_000), SessionStats { active_millis: 90_000, paused_millis: 0, timed_moves: 0, cells_timed: 0, millis_per_cell: 0, cell_millis: [0; 81] });
+        assert_eq!(resumed.enter_at(0, 4, session.solution[(0, 4)], 530_000), Ok(true));
+        assert_eq!(resumed.per_cell_durations()[4], 90_000);
+        assert!(JsonValue::parse(&resumed.to_save(530_000)).unwrap().get("timing").is_some());
+    }
+
+    #[test]
+    fn from_save_refuses_deep_nesting() {
+        let deep = "[".repeat(1_000_000);
+
+        assert!(matches!(GameSession::from_save(&deep, 0), Err(SaveError::Json(JsonError { message: "nesting too deep", .. }))));
+    }
+
+    #[test]
+    fn save_keeps_large_millis_exact() {
+        let now = (1 << 53) + 1;
+        let session = GameSession::new(&medium(), 0).unwrap();
+
+        let resumed = GameSession::from_save(&session.to_save(now), now).unwrap();
+
+        assert_eq!(resumed.summary(now).elapsed_millis, now);
+        assert_eq!(resumed.stats(now).active_millis, now);
+    }
+
+    #[test]
+    fn from_save_rejects_bad_saves() {
+        let save = mid_game().to_save(0);
+        let solution = SudokuSolver::new(&medium()).solve();
+        let mut other_solution = solution.to_array();
+        other_solution.swap(0, 1);
+        let mut tampered_board = mid_game().board().to_array();
+        tampered_board[65] = solution[(7, 2)];
+        let line = |values: [u8; 81]| JsonValue::from(SudokuBoard::new(&values).to_line());
+
+        assert!(matches!(GameSession::from_save(&save[..save.len() / 2], 0), Err(SaveError::Json(_))));
+        assert!(matches!(GameSession::from_save("", 0), Err(SaveError::Json(_))));
+        assert!(matches!(GameSession::from_save("[]", 0), Err(SaveError::Malformed("version"))));
+        assert!(matches!(GameSession::from_save(&with_field("version", 3.into()), 0), Err(SaveError::UnsupportedVersion(3))));
+        assert!(matches!(GameSession::from_save(&with_field("board", "123".into()), 0), Err(SaveError::Malformed("board"))));
+        assert!(matches!(GameSession::from_save(&with_field("mistakes", (-1).into()), 0), Err(SaveError::Malformed("mistakes"))));
+        assert!(matches!(GameSession::from_save(&with_field("notes_policy", "sometimes".into()), 0), Err(SaveError::Malformed("notes_policy"))));
+        assert!(matches!(GameSession::from_save(&with_field("history", JsonValue::Array(vec![JsonValue::Null])), 0), Err(SaveError::Malformed("history"))));
+        assert!(matches!(GameSession::from_save(&with_field("solution", line(other_solution)), 0), Err(SaveError::SolutionMismatch)));
+        assert!(matches!(GameSession::from_save(&with_field("unique", false.into()), 0), Err(SaveError::SolutionMismatch)));
+        assert!(matches!(GameSession::from_save(&with_field("board", line(tampered_board)), 0), Err(SaveError::Inconsistent(_))));
+        assert!(matches!(GameSession::from_save(&with_field("history", JsonValue::Array(Vec::new())), 0), Err(SaveError::Inconsistent(_))));
+        assert!(matches!(GameSession::from_save(&with_field("hints_used", 0.into()), 0), Err(SaveError::Inconsistent(_))));
+        assert!(matches!(GameSession::from_save(&with_field("timing", JsonValue::Null), 0), Err(SaveError::Malformed("timing"))));
+        assert!(matches!(GameSession::from_save(&with_field("autofilled", JsonValue::Null), 0), Err(SaveError::Malformed("autofilled"))));
+        assert_eq!(SaveError::UnsupportedVersion(3).to_string(), "Save format version 3 isn't supported, expected 2");
+    }
+
+    #[test]
+    fn autofill_gives_up() {
+        let mut session = GameSession::new(&easy(), 0).unwrap();
+        session.enter(2, 3, 1).unwrap();
+        session.enter(0, 0, 6).unwrap();
+        session.toggle_note(8, 8, 8).unwrap();
+
+        assert_eq!(session.autofill(), 4);
+        assert!(session.is_complete());
+        assert_eq!(*session.board(), SudokuSolver::new(&easy()).solve());
+        assert!(session.notes().is_empty());
+        assert_eq!(session.summary(0).moves_made, 2);
+        assert_eq!(session.summary(0).mistakes, 1);
+        assert_eq!(GameSession::from_save(&session.to_save(0), 0).map(|resumed| resumed.board().to_line()), Ok(session.board().to_line()));
+        let mut json = JsonValue::parse(&session.to_save(0)).unwrap();
+        if let JsonValue::Object(fields) = &mut json {
+            fields.iter_mut().find(|(name, _)| name == "autofilled").unwrap().1 = 0.into();
+        }
+        assert!(matches!(GameSession::from_save(&json.to_string(), 0), Err(SaveError::Inconsistent("the history is longer than the moves made"))));
+
+        // The last space filled was the one with the note, undoing the autofill brings back the player's own board
+        assert!(session.undo());
+        assert_eq!(session.notes_at(8, 8).iter().collect::<Vec<u8>>(), vec![8]);
+        for _ in 0..3 {
+            assert!(session.undo());
+        }
+        assert_eq!(session.board()[(2, 3)], 1);
+        assert_eq!(session.board()[(8, 8)], 0);
+    }
+
+    #[test]
+    fn observer_sees_moves_and_undo() {
+        let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorder = std::sync::Arc::clone(&events);
+        let mut session = GameSession::new(&easy(), 0).unwrap();
+        session.set_observer(move |change| recorder.lock().unwrap().push(change));
+
+        session.enter(0, 0, 6).unwrap();
+        assert!(session.erase(0, 1).is_err()); // A locked given
+        session.enter(2, 3, 1).unwrap();
+        session.toggle_note(2, 4, 1).unwrap();
+        assert!(session.undo());
+
+        assert_eq!(*events.lock().unwrap(), vec![
+            CellChanged { row: 0, column: 0, previous_value: 0, value: 6 },
+            CellChanged { row: 2, column: 3, previous_value: 0, value: 1 },
+            CellChanged { row: 2, column: 3, previous_value: 1, value: 0 }
+        ]);
+    }
+}
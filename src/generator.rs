@@ -0,0 +1,678 @@
+use std::error::Error;
+#[cfg(feature = "parallel")]
+use std::collections::HashSet;
+use std::fmt::{ self, Display, Formatter };
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use crate::random::{ RandomSource, SplitMix64 };
+use crate::rating::Difficulty;
+use crate::sudoku_board::SudokuBoard;
+use crate::sudoku_solver::{ used_values, SudokuSolver };
+use crate::symmetry::Symmetry;
+use crate::techniques::{ Technique, TechniqueSet };
+use crate::uniqueness::Uniqueness;
+#[cfg(not(feature = "parallel"))]
+use crate::uniqueness::is_unique;
+#[cfg(feature = "parallel")]
+use crate::uniqueness::is_unique_parallel as is_unique;
+use crate::walkthrough::WalkthroughEnd;
+
+// Stability policy: the same options and seed give the same puzzle on every platform and in every release with the
+// same major version. All randomness comes from `SplitMix64` seeded by the caller, and nothing in the generation path
+// depends on hash map iteration order. The `seeded_output_is_pinned` test holds the puzzles this promise covers.
+// Every seeded entry point has a `_with_rng` twin drawing everything from a caller's `RandomSource` instead. The
+// seeded ones restart `SplitMix64` from a fresh seed for each attempt, which the twins don't, so the two never agree.
+// With a target difficulty the digging consults `SudokuSolver::rate`, so changing the rating changes those puzzles and
+// counts as a break of this promise. Locked candidates joining `rate` was one: puzzles generated with
+// `options.difficulty` set, and `daily_puzzle` with `weekday_difficulty`, differ from the releases before it.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PatternFit {
+    #[default]
+    Exact, // Every masked space is a given
+    AtMost // Givens only ever sit on masked spaces, more holes are dug inside the mask while the solution stays unique
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GeneratorOptions {
+    pub min_clues: usize, // Digging stops once the puzzle is down to this many givens, 0 digs as far as uniqueness allows
+    pub difficulty: Option<Difficulty>, // The exact grade the puzzle must rate as
+    pub symmetry: Option<Symmetry>, // Holes are dug in mirrored pairs so the givens keep this symmetry
+    pub pattern_fit: PatternFit, // Only used by `generate_with_pattern`
+    pub max_attempts: usize, // Fresh solved grids (or dig orders, for a fixed solution) to try before giving up
+    pub weekday_difficulty: Option<[Difficulty; 7]> // Only used by `daily_puzzle`, Monday first, replaces `difficulty`
+}
+
+impl Default for GeneratorOptions {
+    fn default() -> GeneratorOptions {
+        return GeneratorOptions { min_clues: 0, difficulty: None, symmetry: None, pattern_fit: PatternFit::Exact, max_attempts: 100, weekday_difficulty: None };
+    }
+}
+
+impl GeneratorOptions {
+    pub fn new() -> GeneratorOptions {
+        return GeneratorOptions::default();
+    }
+
+    pub fn min_clues(mut self, min_clues: usize) -> GeneratorOptions {
+        self.min_clues = min_clues;
+        return self;
+    }
+
+    pub fn difficulty(mut self, difficulty: Difficulty) -> GeneratorOptions {
+        self.difficulty = Some(difficulty);
+        return self;
+    }
+
+    pub fn symmetry(mut self, symmetry: Symmetry) -> GeneratorOptions {
+        self.symmetry = Some(symmetry);
+        return self;
+    }
+
+    pub fn pattern_fit(mut self, pattern_fit: PatternFit) -> GeneratorOptions {
+        self.pattern_fit = pattern_fit;
+        return self;
+    }
+
+    pub fn max_attempts(mut self, max_attempts: usize) -> GeneratorOptions {
+        self.max_attempts = max_attempts;
+        return self;
+    }
+
+    pub fn weekday_difficulty(mut self, weekday_difficulty: [Difficulty; 7]) -> GeneratorOptions {
+        self.weekday_difficulty = Some(weekday_difficulty);
+        return self;
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct GeneratedPuzzle {
+    pub puzzle: SudokuBoard, // Always has a unique solution
+    pub solution: SudokuBoard,
+    pub clues: usize,
+    pub difficulty: Difficulty
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GenerateError {
+    InvalidSolution, // The grid passed to `generate_from_solution` isn't complete and valid
+    ConstraintsUnreachable { attempts: usize },
+    // Even the best attempt needed `extra_givens` givens outside the mask before its solution was unique
+    PatternInfeasible { attempts: usize, extra_givens: usize },
+    NotFoundWithinBudget { attempts: usize }, // No puzzle in the budget needed the technique asked for
+    InvalidDate { year: i32, month: u8, day: u8 } // The date passed to `daily_puzzle` isn't in the calendar
+}
+
+impl Display for GenerateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        return match self {
+            GenerateError::InvalidSolution => write!(f, "The solution must be a complete and valid grid"),
+            GenerateError::ConstraintsUnreachable { attempts } => write!(f, "No puzzle met the generator options in {} attempts", attempts),
+            GenerateError::PatternInfeasible { attempts, extra_givens } => write!(f, "No puzzle fit the pattern in {} attempts, the closest needed {} givens outside it", attempts, extra_givens),
+            GenerateError::NotFoundWithinBudget { attempts } => write!(f, "No puzzle needing the technique was found in {} attempts", attempts),
+            GenerateError::InvalidDate { year, month, day } => write!(f, "{}-{}-{} isn't a valid date", year, month, day)
+        };
+    }
+}
+
+impl Error for GenerateError {}
+
+// Digs holes in seeded solved grids, in a seeded order, keeping every removal that leaves a unique solution
+pub fn generate(options: &GeneratorOptions, seed: u64) -> Result<GeneratedPuzzle, GenerateError> {
+    let mut random = SplitMix64::new(seed);
+    for _ in 0..options.max_attempts {
+        let attempt_seed = random.next_u64();
+        let solution = solved_grid(attempt_seed);
+        if let Some(generated_puzzle) = dig(&solution, &solution, options, &mut SplitMix64::new(attempt_seed)) {
+            return Ok(generated_puzzle);
+        }
+    }
+    return Err(GenerateError::ConstraintsUnreachable { attempts: options.max_attempts });
+}
+
+pub fn generate_with_rng<R: RandomSource>(options: &GeneratorOptions, random: &mut R) -> Result<GeneratedPuzzle, GenerateError> {
+    for _ in 0..options.max_attempts {
+        let solution = solved_grid_with_rng(random);
+        if let Some(generated_puzzle) = dig(&solution, &solution, options, random) {
+            return Ok(generated_puzzle);
+        }
+    }
+    return Err(GenerateError::ConstraintsUnreachable { attempts: options.max_attempts });
+}
+
+// A puzzle whose unique solution is exactly `solution`, each attempt digs in a different seeded order
+pub fn generate_from_solution(solution: &SudokuBoard, options: &GeneratorOptions, seed: u64) -> Result<SudokuBoard, GenerateError> {
+    if !solution.is_solved() {
+        return Err(GenerateError::InvalidSolution);
+    }
+
+    let mut random = SplitMix64::new(seed);
+    for _ in 0..options.max_attempts {
+        if let Some(generated_puzzle) = dig(solution, solution, options, &mut SplitMix64::new(random.next_u64())) {
+            return Ok(generated_puzzle.puzzle);
+        }
+    }
+    return Err(GenerateError::ConstraintsUnreachable { attempts: options.max_attempts });
+}
+
+pub fn generate_from_solution_with_rng<R: RandomSource>(solution: &SudokuBoard, options: &GeneratorOptions, random: &mut R) -> Result<SudokuBoard, GenerateError> {
+    if !solution.is_solved() {
+        return Err(GenerateError::InvalidSolution);
+    }
+
+    for _ in 0..options.max_attempts {
+        if let Some(generated_puzzle) = dig(solution, solution, options, random) {
+            return Ok(generated_puzzle.puzzle);
+        }
+    }
+    return Err(GenerateError::ConstraintsUnreachable { attempts: options.max_attempts });
+}
+
+// A puzzle with givens only on the spaces set in `mask`. Most sparse patterns have no unique-solution puzzle at all,
+// so each attempt fills a fresh solved grid into the mask. `options.difficulty` is honored, `min_clues` and `symmetry`
+// are left to the mask.
+pub fn generate_with_pattern(mask: &[bool; 81], options: &GeneratorOptions, seed: u64) -> Result<SudokuBoard, GenerateError> {
+    let mut random = SplitMix64::new(seed);
+    let mut fewest_extra_givens = 81;
+    for _ in 0..options.max_attempts {
+        let attempt_seed = random.next_u64();
+        let solution = solved_grid(attempt_seed);
+        if let Some(puzzle) = pattern_attempt(mask, options, &solution, &mut SplitMix64::new(attempt_seed), &mut fewest_extra_givens) {
+            return Ok(puzzle);
+        }
+    }
+    return Err(GenerateError::PatternInfeasible { attempts: options.max_attempts, extra_givens: fewest_extra_givens });
+}
+
+pub fn generate_with_pattern_with_rng<R: RandomSource>(mask: &[bool; 81], options: &GeneratorOptions, random: &mut R) -> Result<SudokuBoard, GenerateError> {
+    let mut fewest_extra_givens = 81;
+    for _ in 0..options.max_attempts {
+        let solution = solved_grid_with_rng(random);
+        if let Some(puzzle) = pattern_attempt(mask, options, &solution, random, &mut fewest_extra_givens) {
+            return Ok(puzzle);
+        }
+    }
+    return Err(GenerateError::PatternInfeasible { attempts: options.max_attempts, extra_givens: fewest_extra_givens });
+}
+
+// `solution` cut down to `mask`, or the fewest givens outside the mask it would have needed recorded instead
+fn pattern_attempt<R: RandomSource>(mask: &[bool; 81], options: &GeneratorOptions, solution: &SudokuBoard, random: &mut R, fewest_extra_givens: &mut usize) -> Option<SudokuBoard> {
+    let mut puzzle = SudokuBoard::copy(solution);
+    for index in (0..81).filter(|index| !mask[*index]) {
+        puzzle.clear_value(index / 9, index % 9);
+    }
+
+    if is_unique(&puzzle) != Uniqueness::Unique {
+        *fewest_extra_givens = (*fewest_extra_givens).min(extra_givens_needed(&puzzle, solution, random));
+        return None;
+    }
+
+    let mut pattern_options = GeneratorOptions { min_clues: 0, symmetry: None, ..*options };
+    if options.pattern_fit == PatternFit::Exact {
+        pattern_options.min_clues = 81;
+    }
+    return dig(&puzzle, solution, &pattern_options, random).map(|generated_puzzle| generated_puzzle.puzzle);
+}
+
+// Greedily adds givens from `solution` outside the pattern, in a random order, until the solution is unique
+fn extra_givens_needed<R: RandomSource>(puzzle: &SudokuBoard, solution: &SudokuBoard, random: &mut R) -> usize {
+    let mut puzzle = SudokuBoard::copy(puzzle);
+    let mut spaces = puzzle.get_unsolved_spaces();
+    random.shuffle(&mut spaces);
+
+    let mut extra_givens = 0;
+    for (row_index, column_index) in spaces {
+        if is_unique(&puzzle) == Uniqueness::Unique {
+            break;
+        }
+        puzzle.set_value(row_index, column_index, solution[(row_index, column_index)]);
+        extra_givens += 1;
+    }
+    return extra_givens;
+}
+
+// A practice puzzle where `technique` is the key step: its walkthrough completes with every technique but stalls once
+// `technique` is taken away. Digging goes as far as every technique together can still solve, the walkthrough
+// without `technique` then decides.
+pub fn generate_for_technique(technique: Technique, seed: u64, attempts_budget: usize) -> Result<GeneratedPuzzle, GenerateError> {
+    let mut random = SplitMix64::new(seed);
+    for _ in 0..attempts_budget {
+        let attempt_seed = random.next_u64();
+        let solution = solved_grid(attempt_seed);
+        if let Some(generated_puzzle) = technique_attempt(technique, &solution, &mut SplitMix64::new(attempt_seed)) {
+            return Ok(generated_puzzle);
+        }
+    }
+    return Err(GenerateError::NotFoundWithinBudget { attempts: attempts_budget });
+}
+
+pub fn generate_for_technique_with_rng<R: RandomSource>(technique: Technique, random: &mut R, attempts_budget: usize) -> Result<GeneratedPuzzle, GenerateError> {
+    for _ in 0..attempts_budget {
+        let solution = solved_grid_with_rng(random);
+        if let Some(generated_puzzle) = technique_attempt(technique, &solution, random) {
+            return Ok(generated_puzzle);
+        }
+    }
+    return Err(GenerateError::NotFoundWithinBudget { attempts: attempts_budget });
+}
+
+fn technique_attempt<R: RandomSource>(technique: Technique, solution: &SudokuBoard, random: &mut R) -> Option<GeneratedPuzzle> {
+    let solvable = |solver: &SudokuSolver| solver.walkthrough(&TechniqueSet::all()).end == WalkthroughEnd::Solved;
+    let generated_puzzle = dig_while(solution, solution, &GeneratorOptions::new(), random, solvable)?;
+    let without_technique = SudokuSolver::new(&generated_puzzle.puzzle).walkthrough(&TechniqueSet::all().without(technique));
+    if without_technique.end != WalkthroughEnd::Solved {
+        return Some(generated_puzzle);
+    }
+    return None;
+}
+
+// `count` puzzles generated across all cores. Index `n` always uses the same seed, derived from `seed` and `n`, and
+// results are kept in index order, so thread scheduling never changes the batch. Duplicates and puzzles that failed
+// the options are replaced from further indices, up to `count * options.max_attempts` of them in total, after which
+// the batch comes back short.
+#[cfg(feature = "parallel")]
+pub fn generate_batch(count: usize, options: &GeneratorOptions, seed: u64) -> Vec<GeneratedPuzzle> {
+    let single_attempt = GeneratorOptions { max_attempts: 1, ..*options };
+    let index_limit = count.saturating_mul(options.max_attempts.max(1));
+
+    let mut batch = Vec::with_capacity(count);
+    let mut keys = HashSet::new();
+    let mut next_index = 0;
+    while batch.len() < count && next_index < index_limit {
+        let indices = next_index..(next_index + count - batch.len()).min(index_limit);
+        next_index = indices.end;
+
+        let results: Vec<Option<GeneratedPuzzle>> = indices.into_par_iter().map(|index| generate(&single_attempt, index_seed(seed, index)).ok()).collect();
+        for generated_puzzle in results.into_iter().flatten() {
+            if batch.len() < count && keys.insert(generated_puzzle.puzzle.to_line()) {
+                batch.push(generated_puzzle);
+            }
+        }
+    }
+    return batch;
+}
+
+// The puzzle for a (year, month, day) date, the same for that date under the stability policy above. The seed is the
+// 64-bit FNV-1a hash of the date written as `YYYY-MM-DD` (years zero-padded to at least 4 digits, a `-` in front of
+// negative ones), passed to `generate` with the difficulty for the date's weekday when `weekday_difficulty` is set.
+pub fn daily_puzzle(date: (i32, u8, u8), options: &GeneratorOptions) -> Result<GeneratedPuzzle, GenerateError> {
+    if !is_valid_date(date) {
+        let (year, month, day) = date;
+        return Err(GenerateError::InvalidDate { year, month, day });
+    }
+
+    let days = days_since_epoch(date);
+    let difficulty = options.weekday_difficulty.map(|difficulties| difficulties[weekday_index(days)]).or(options.difficulty);
+    return generate(&GeneratorOptions { difficulty, ..*options }, daily_seed(date));
+}
+
+fn daily_seed((year, month, day): (i32, u8, u8)) -> u64 {
+    return format!("{:04}-{:02}-{:02}", year, month, day).bytes()
+        .fold(0xCBF2_9CE4_8422_2325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01B3));
+}
+
+// In the proleptic Gregorian calendar
+fn is_valid_date((year, month, day): (i32, u8, u8)) -> bool {
+    let is_leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let month_length = match month {
+        2 if is_leap_year => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31
+    };
+    return (1..=12).contains(&month) && day != 0 && day <= month_length;
+}
+
+// Days from 1970-01-01 in the proleptic Gregorian calendar, negative before it. `date` has to be valid.
+fn days_since_epoch((year, month, day): (i32, u8, u8)) -> i64 {
+    // Counts years from March so the leap day falls at the end of each
+    let year = year as i64 - if month <= 2 { 1 } else { 0 };
+    let era = year.div_euclid(400);
+    let year_of_era = year - 400 * era;
+    let day_of_year = (153 * ((month as i64 + 9) % 12) + 2) / 5 + day as i64 - 1;
+    let day_of_era = 365 * year_of_era + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    return 146_097 * era + day_of_era - 719_468;
+}
+
+// 0 for Monday, 1970-01-01 was a Thursday
+fn weekday_index(days: i64) -> usize {
+    return (days + 3).rem_euclid(7) as usize;
+}
+
+#[cfg(feature = "parallel")]
+fn index_seed(seed: u64, index: usize) -> u64 {
+    return SplitMix64::new(seed ^ (index as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)).next_u64();
+}
+
+// A complete valid grid, filled by a depth first search that tries values in a random order. Each seed always gives
+// the same grid.
+pub fn solved_grid(seed: u64) -> SudokuBoard {
+    return solved_grid_with_rng(&mut SplitMix64::new(seed));
+}
+
+// `solved_grid` drawing the value orders from `random`
+pub fn solved_grid_with_rng<R: RandomSource>(random: &mut R) -> SudokuBoard {
+    let mut board = SudokuBoard::new(&[0; 81]);
+    fill(&mut board, random, 0);
+    return board;
+}
+
+fn fill<R: RandomSource>(board: &mut SudokuBoard, random: &mut R, index: usize) -> bool {
+    if index == 81 {
+        return true;
+    }
+
+    let (row_index, column_index) = (index / 9, index % 9);
+    let mut values = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+    random.shuffle(&mut values);
+    let used = used_values(board, row_index, column_index);
+    for value in values.iter().copied().filter(|value| used & (1 << value) == 0) {
+        board.set_value(row_index, column_index, value);
+        if fill(board, random, index + 1) {
+            return true;
+        }
+    }
+
+    board.clear_value(row_index, column_index);
+    return false;
+}
+
+// Digs holes in `start`, a puzzle with a unique `solution`, in a random order
+fn dig<R: RandomSource>(start: &SudokuBoard, solution: &SudokuBoard, options: &GeneratorOptions, random: &mut R) -> Option<GeneratedPuzzle> {
+    let keeps_grade = |solver: &SudokuSolver| options.difficulty.map_or(true, |difficulty| solver.rate().difficulty <= difficulty);
+    return dig_while(start, solution, options, random, keeps_grade);
+}
+
+// `dig` with `keeps` deciding which holes stay, besides uniqueness. `options.difficulty` is still checked at the end.
+fn dig_while<R: RandomSource, F: Fn(&SudokuSolver) -> bool>(start: &SudokuBoard, solution: &SudokuBoard, options: &GeneratorOptions, random: &mut R, keeps: F) -> Option<GeneratedPuzzle> {
+    let mut spaces: Vec<usize> = (0..81).collect();
+    random.shuffle(&mut spaces);
+
+    let mut puzzle = SudokuBoard::copy(start);
+    for index in spaces {
+        // A space and its mirror image are dug together, a space on the axis is its own mirror image
+        let space = (index / 9, index % 9);
+        let holes = match options.symmetry.map(|symmetry| symmetry.mirror(space)) {
+            Some(mirrored_space) if mirrored_space != space => vec![space, mirrored_space],
+            _ => vec![space]
+        };
+        if puzzle[space] == 0 || 81 - puzzle.unsolved_count() < options.min_clues + holes.len() {
+            continue;
+        }
+
+        for hole in holes.iter() {
+            puzzle.clear_value(hole.0, hole.1);
+        }
+        if !keeps(&SudokuSolver::new(&puzzle)) || is_unique(&puzzle) != Uniqueness::Unique {
+            for hole in holes.iter() {
+                puzzle.set_value(hole.0, hole.1, solution[*hole]);
+            }
+        }
+    }
+
+    let difficulty = SudokuSolver::new(&puzzle).rate().difficulty;
+    if options.difficulty.is_some_and(|target| target != difficulty) {
+        return None;
+    }
+
+    return Some(GeneratedPuzzle {
+        clues: 81 - puzzle.unsolved_count(),
+        puzzle: SudokuBoard::new(&puzzle.to_array()), // Rebuilt so the remaining values are recorded as givens
+        solution: SudokuBoard::copy(solution),
+        difficulty
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_works() {
+        let generated_puzzle = generate(&GeneratorOptions::new(), 1).unwrap();
+        let solver = SudokuSolver::new(&generated_puzzle.puzzle);
+
+        assert_eq!(solver.count_solutions(2), 1);
+        assert_eq!(solver.solve(), generated_puzzle.solution);
+        assert_eq!(generated_puzzle.clues, 81 - generated_puzzle.puzzle.unsolved_count());
+        assert_eq!(generated_puzzle.difficulty, solver.rate().difficulty);
+        assert!((0..81).all(|index| generated_puzzle.puzzle.is_given(index / 9, index % 9) == (generated_puzzle.puzzle[(index / 9, index % 9)] != 0)));
+    }
+
+    #[test]
+    fn generate_options_work() {
+        let generated_puzzle = generate(&GeneratorOptions::new().min_clues(40), 2).unwrap();
+        assert_eq!(generated_puzzle.clues, 40);
+
+        let easy = generate(&GeneratorOptions::new().difficulty(Difficulty::Easy).min_clues(30), 3).unwrap();
+        assert_eq!(easy.difficulty, Difficulty::Easy);
+
+        let unreachable = GeneratorOptions::new().difficulty(Difficulty::Hard).min_clues(70).max_attempts(3);
+        assert_eq!(generate(&unreachable, 4), Err(GenerateError::ConstraintsUnreachable { attempts: 3 }));
+    }
+
+    #[test]
+    fn generate_from_solution_works() {
+        let solution = SudokuBoard::new(&[
+            4,3,9, 6,8,2, 7,1,5,
+            6,7,2, 1,3,5, 9,4,8,
+            1,5,8, 7,4,9, 3,6,2,
+            8,1,5, 9,6,7, 4,2,3,
+            7,2,6, 4,5,3, 8,9,1,
+            9,4,3, 8,2,1, 5,7,6,
+            3,6,1, 5,9,4, 2,8,7,
+            2,9,7, 3,1,8, 6,5,4,
+            5,8,4, 2,7,6, 1,3,9
+        ]);
+        let options = GeneratorOptions::new().symmetry(Symmetry::Rotational).min_clues(28);
+
+        let puzzle = generate_from_solution(&solution, &options, 5).unwrap();
+        let solver = SudokuSolver::new(&puzzle);
+
+        assert_eq!(solver.count_solutions(2), 1);
+        assert_eq!(solver.solve(), solution);
+        assert!(81 - puzzle.unsolved_count() >= 28);
+        assert!((0..81).map(|index| (index / 9, index % 9)).all(|space| (puzzle[space] == 0) == (puzzle[Symmetry::Rotational.mirror(space)] == 0)));
+    }
+
+    #[test]
+    fn generate_from_solution_invalid_input() {
+        let mut duplicate = SudokuBoard::new(&[
+            4,3,9, 6,8,2, 7,1,5,
+            6,7,2, 1,3,5, 9,4,8,
+            1,5,8, 7,4,9, 3,6,2,
+            8,1,5, 9,6,7, 4,2,3,
+            7,2,6, 4,5,3, 8,9,1,
+            9,4,3, 8,2,1, 5,7,6,
+            3,6,1, 5,9,4, 2,8,7,
+            2,9,7, 3,1,8, 6,5,4,
+            5,8,4, 2,7,6, 1,3,9
+        ]);
+        duplicate.set_value(0, 0, 3);
+
+        assert_eq!(generate_from_solution(&duplicate, &GeneratorOptions::new(), 0), Err(GenerateError::InvalidSolution));
+        assert_eq!(generate_from_solution(&SudokuBoard::new(&[0; 81]), &GeneratorOptions::new(), 0), Err(GenerateError::InvalidSolution));
+    }
+
+    fn symmetric_mask() -> [bool; 81] {
+        // Every other space of the first 40, and their 180° mirror images
+        let mut mask = [false; 81];
+        for index in (0..40).step_by(2) {
+            mask[index] = true;
+            mask[80 - index] = true;
+        }
+        return mask;
+    }
+
+    #[test]
+    fn generate_with_pattern_works() {
+        let mask = symmetric_mask();
+        assert_eq!(mask.iter().filter(|masked| **masked).count(), 40);
+
+        let exact = generate_with_pattern(&mask, &GeneratorOptions::new(), 6).unwrap();
+        assert_eq!(SudokuSolver::new(&exact).count_solutions(2), 1);
+        assert!((0..81).all(|index| (exact[(index / 9, index % 9)] != 0) == mask[index]));
+
+        let at_most = generate_with_pattern(&mask, &GeneratorOptions::new().pattern_fit(PatternFit::AtMost), 6).unwrap();
+        assert_eq!(SudokuSolver::new(&at_most).count_solutions(2), 1);
+        assert!((0..81).all(|index| at_most[(index / 9, index % 9)] == 0 || mask[index]));
+        assert!(at_most.unsolved_count() > exact.unsolved_count());
+    }
+
+    #[test]
+    fn generate_with_pattern_infeasible() {
+        let mut mask = [false; 81];
+        for masked in mask.iter_mut().take(10) {
+            *masked = true;
+        }
+
+        match generate_with_pattern(&mask, &GeneratorOptions::new().max_attempts(2), 7) {
+            Err(GenerateError::PatternInfeasible { attempts, extra_givens }) => {
+                assert_eq!(attempts, 2);
+                assert!(extra_givens >= 7); // 17 givens is the least any unique puzzle has
+            },
+            other => panic!("Expected an infeasible pattern, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn generate_for_technique_works() {
+        for technique in [Technique::NakedSingle, Technique::HiddenSingle, Technique::LockedCandidates].iter() {
+            let generated_puzzle = generate_for_technique(*technique, 5, 50).unwrap();
+            let solver = SudokuSolver::new(&generated_puzzle.puzzle);
+
+            assert_eq!(solver.walkthrough(&TechniqueSet::all()).end, WalkthroughEnd::Solved);
+            assert!(matches!(solver.walkthrough(&TechniqueSet::all().without(*technique)).end, WalkthroughEnd::StuckAt { .. }), "{:?}", technique);
+            assert_eq!(is_unique(&generated_puzzle.puzzle), Uniqueness::Unique);
+        }
+    }
+
+    #[test]
+    fn generate_for_technique_budget() {
+        assert_eq!(generate_for_technique(Technique::HiddenSingle, 5, 0), Err(GenerateError::NotFoundWithinBudget { attempts: 0 }));
+    }
+
+    // These pin the stability policy, see the top of this file. Changing one is a breaking change of the generator.
+    #[test]
+    fn seeded_output_is_pinned() {
+        assert_eq!(solved_grid(2024).to_line(), "652879134819463527743215689431528976987641253526397418398156742265734891174982365");
+        assert_eq!(generate(&GeneratorOptions::new(), 2024).unwrap().puzzle.to_line(), "007000050080490020950108000090003000000000000003800491010000006000006700800010500");
+
+        let rotational = GeneratorOptions::new().symmetry(Symmetry::Rotational);
+        assert_eq!(generate_from_solution(&solved_grid(7), &rotational, 2024).unwrap().to_line(), "081074060020800500760009000070000900010060080006000020000700042007003090040210350");
+
+        let at_most = GeneratorOptions::new().pattern_fit(PatternFit::AtMost);
+        assert_eq!(generate_with_pattern(&symmetric_mask(), &at_most, 2024).unwrap().to_line(), "503090107000000000006010302000002070907000600000000050305070001060100030000050806");
+
+        let by_technique: Vec<String> = [Technique::NakedSingle, Technique::HiddenSingle, Technique::LockedCandidates].iter()
+            .map(|technique| generate_for_technique(*technique, 5, 50).unwrap().puzzle.to_line())
+            .collect();
+        assert_eq!(by_technique, vec![
+            "060000007270536000890000300980010000000004200000720008010009003000000060000480100",
+            "060000007270536000890000300980010000000004200000720008010009003000000060000480100",
+            "065007100030000900004300000009030007700400850000000600000000006000170500006590708"
+        ]);
+
+        #[cfg(feature = "parallel")]
+        {
+            let batch: Vec<String> = generate_batch(3, &GeneratorOptions::new().min_clues(30), 2024).iter().map(|generated_puzzle| generated_puzzle.puzzle.to_line()).collect();
+            assert_eq!(batch, vec![
+                "520030100040109630000000490900603001000000800400000029800000200304058000016004708",
+                "000000020000300170050000403100006290702030000900082300200000010583200640071060002",
+                "900000030602000048085032709008701000200005000719000080006010020000520090000008603"
+            ]);
+        }
+    }
+
+    #[test]
+    fn daily_puzzle_is_pinned() {
+        assert_eq!(daily_seed((2024, 3, 9)), 0xD791_7D89_C2C1_6063);
+        assert_eq!(daily_puzzle((2024, 3, 9), &GeneratorOptions::new()).unwrap().puzzle.fingerprint(), "NWPC4ALIT336O");
+        assert_eq!(daily_puzzle((1999, 12, 31), &GeneratorOptions::new()).unwrap().puzzle.fingerprint(), "JZWSXLVLTC7V6");
+
+        let weekends_hard = GeneratorOptions::new().weekday_difficulty([Difficulty::Easy, Difficulty::Easy, Difficulty::Medium, Difficulty::Medium, Difficulty::Medium, Difficulty::Hard, Difficulty::Hard]);
+        assert_eq!(daily_puzzle((2024, 3, 6), &weekends_hard).unwrap().puzzle.fingerprint(), "HQVHXWSWUDGOX"); // A Wednesday, medium
+        assert_eq!(daily_puzzle((2024, 3, 9), &weekends_hard).unwrap().puzzle.fingerprint(), "MPZ7SAO3YAQBF"); // A Saturday, hard
+    }
+
+    #[test]
+    fn daily_puzzle_follows_weekday() {
+        assert_eq!(days_since_epoch((1970, 1, 1)), 0);
+        assert_eq!(days_since_epoch((2000, 3, 1)), 11_017);
+        assert_eq!(days_since_epoch((1969, 12, 31)), -1);
+        assert_eq!(weekday_index(days_since_epoch((2024, 2, 29))), 3); // A Thursday
+        assert_eq!(weekday_index(days_since_epoch((1969, 12, 28))), 6); // A Sunday
+
+        let weekends_hard = GeneratorOptions::new().weekday_difficulty([Difficulty::Easy, Difficulty::Easy, Difficulty::Medium, Difficulty::Medium, Difficulty::Medium, Difficulty::Hard, Difficulty::Hard]);
+        // Monday 2024-03-04 through Sunday 2024-03-10
+        for (day, expected) in (4..=10).zip(weekends_hard.weekday_difficulty.unwrap().iter()) {
+            let generated_puzzle = daily_puzzle((2024, 3, day), &weekends_hard).unwrap();
+            assert_eq!(generated_puzzle.difficulty, *expected, "2024-03-{}", day);
+            assert_eq!(Some(generated_puzzle), daily_puzzle((2024, 3, day), &weekends_hard).ok());
+        }
+    }
+
+    #[test]
+    fn daily_puzzle_invalid_date() {
+        assert_eq!(daily_puzzle((2023, 2, 29), &GeneratorOptions::new()), Err(GenerateError::InvalidDate { year: 2023, month: 2, day: 29 }));
+        assert_eq!(daily_puzzle((2024, 13, 1), &GeneratorOptions::new()), Err(GenerateError::InvalidDate { year: 2024, month: 13, day: 1 }));
+        assert_eq!(GenerateError::InvalidDate { year: 2023, month: 2, day: 29 }.to_string(), "2023-2-29 isn't a valid date");
+        assert!(daily_puzzle((2024, 2, 29), &GeneratorOptions::new()).is_ok());
+    }
+
+    // Replays `SplitMix64`, counting every draw
+    struct CountingRandom {
+        inner: SplitMix64,
+        draws: usize
+    }
+
+    impl RandomSource for CountingRandom {
+        fn next_u64(&mut self) -> u64 {
+            self.draws += 1;
+            return self.inner.next_u64();
+        }
+    }
+
+    #[test]
+    fn generate_with_rng_draws_only_from_source() {
+        let options = GeneratorOptions::new().min_clues(30);
+        let mut first = CountingRandom { inner: SplitMix64::new(5), draws: 0 };
+        let mut second = CountingRandom { inner: SplitMix64::new(5), draws: 0 };
+
+        // The same draws give the same puzzle, so nothing else fed into it
+        let generated_puzzle = generate_with_rng(&options, &mut first).unwrap();
+        assert_eq!(generate_with_rng(&options, &mut second).unwrap().puzzle, generated_puzzle.puzzle);
+        assert!(first.draws > 0);
+        assert_eq!(first.draws, second.draws);
+        assert_eq!(SudokuSolver::new(&generated_puzzle.puzzle).count_solutions(2), 1);
+
+        let mut other = CountingRandom { inner: SplitMix64::new(6), draws: 0 };
+        assert_ne!(generate_with_rng(&options, &mut other).unwrap().puzzle, generated_puzzle.puzzle);
+
+        let solution = solved_grid(7);
+        let mut random = CountingRandom { inner: SplitMix64::new(5), draws: 0 };
+        let puzzle = generate_from_solution_with_rng(&solution, &GeneratorOptions::new(), &mut random).unwrap();
+        assert_eq!(SudokuSolver::new(&puzzle).solve(), solution);
+        assert!(random.draws > 0);
+
+        let mut random = CountingRandom { inner: SplitMix64::new(5), draws: 0 };
+        let at_most = GeneratorOptions::new().pattern_fit(PatternFit::AtMost);
+        let puzzle = generate_with_pattern_with_rng(&symmetric_mask(), &at_most, &mut random).unwrap();
+        assert!((0..81).all(|index| symmetric_mask()[index] || puzzle[(index / 9, index % 9)] == 0));
+        assert!(random.draws > 0);
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn generate_batch_is_reproducible() {
+        let options = GeneratorOptions::new().min_clues(32);
+
+        let first = generate_batch(6, &options, 99);
+        let second = generate_batch(6, &options, 99);
+
+        assert_eq!(first.len(), 6);
+        assert_eq!(first, second);
+        assert_eq!(first.iter().map(|generated_puzzle| generated_puzzle.puzzle.to_line()).collect::<HashSet<String>>().len(), 6);
+        assert_ne!(generate_batch(1, &options, 100)[0], first[0]);
+    }
+}
@@ -0,0 +1,184 @@
+use std::error::Error;
+use std::fmt::{ self, Display, Formatter };
+use crate::candidate_board::CandidateBoard;
+use crate::sudoku_board::SudokuBoard;
+
+// One line of a HoDoKu library, `:code:candidates:grid:deleted:eliminations:placements:extra:`. In the grid a bare
+// digit is a given, `+` in front of a digit marks a placed value and `.` or `0` a blank. Deleted candidates are `drc`
+// triples (digit, row, column, counting from 1) separated by spaces.
+#[derive(Debug)]
+pub struct HodokuEntry {
+    pub board: SudokuBoard,
+    pub candidates: Option<CandidateBoard>, // Only when the line deletes candidates, otherwise they follow from `board`
+    fields: Vec<String> // Everything else is written back untouched
+}
+
+const GRID_FIELD: usize = 3;
+const DELETED_FIELD: usize = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HodokuError {
+    MissingField(usize), // The line ends before the grid or deleted candidates field
+    Grid { cells_found: usize },
+    Candidate(String) // A deleted candidate that isn't a `drc` triple
+}
+
+impl Display for HodokuError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        return match self {
+            HodokuError::MissingField(index) => write!(f, "Missing field {} of the library line", index),
+            HodokuError::Grid { cells_found } => write!(f, "Expected 81 cells, found {}", cells_found),
+            HodokuError::Candidate(text) => write!(f, "Invalid deleted candidate '{}'", text)
+        };
+    }
+}
+
+impl Error for HodokuError {}
+
+impl HodokuEntry {
+    pub fn parse(line: &str) -> Result<HodokuEntry, HodokuError> {
+        let fields: Vec<String> = line.trim().split(':').map(String::from).collect();
+        let grid = fields.get(GRID_FIELD).ok_or(HodokuError::MissingField(GRID_FIELD))?;
+
+        let mut givens = [0; 81];
+        let mut placed = Vec::new();
+        let mut cells_found = 0;
+        let mut is_placed = false;
+        for character in grid.chars() {
+            let value = match character {
+                '+' => {
+                    is_placed = true;
+                    continue;
+                },
+                '.' => 0,
+                _ => match character.to_digit(10) {
+                    Some(value) => value as u8,
+                    None => continue
+                }
+            };
+            if cells_found < 81 {
+                if is_placed {
+                    placed.push((cells_found, value));
+                }
+                else {
+                    givens[cells_found] = value;
+                }
+            }
+            cells_found += 1;
+            is_placed = false;
+        }
+        if cells_found != 81 {
+            return Err(HodokuError::Grid { cells_found });
+        }
+
+        let mut board = SudokuBoard::new(&givens);
+        for (index, value) in placed {
+            board.set_value(index / 9, index % 9, value);
+        }
+
+        let deleted = fields.get(DELETED_FIELD).map_or("", String::as_str);
+        let mut candidates = None;
+        for triple in deleted.split_whitespace() {
+            let digits: Vec<u8> = triple.chars().filter_map(|character| character.to_digit(10)).map(|digit| digit as u8).collect();
+            if triple.len() != 3 || digits.len() != 3 || digits.contains(&0) {
+                return Err(HodokuError::Candidate(String::from(triple)));
+            }
+            candidates.get_or_insert_with(|| CandidateBoard::new(&board)).remove(digits[1] as usize - 1, digits[2] as usize - 1, digits[0]);
+        }
+
+        return Ok(HodokuEntry { board, candidates, fields });
+    }
+
+    // The technique the line is a test case for, e.g. `0300` for an X-Wing
+    pub fn technique_code(&self) -> &str {
+        return self.fields.get(1).map_or("", String::as_str);
+    }
+
+    // The grid and deleted candidates are rebuilt from `board` and `candidates`, deletions in row-major order
+    pub fn to_line(&self) -> String {
+        let mut grid = String::with_capacity(81);
+        for index in 0..81 {
+            let (row_index, column_index) = (index / 9, index % 9);
+            match self.board[(row_index, column_index)] {
+                0 => grid.push('.'),
+                value if self.board.is_given(row_index, column_index) => grid.push(char::from(b'0' + value)),
+                value => {
+                    grid.push('+');
+                    grid.push(char::from(b'0' + value));
+                }
+            }
+        }
+
+        let mut deleted = Vec::new();
+        if let Some(candidates) = &self.candidates {
+            let full_candidates = CandidateBoard::new(&self.board);
+            for index in 0..81 {
+                let (row_index, column_index) = (index / 9, index % 9);
+                let remaining = candidates.get(row_index, column_index);
+                for value in full_candidates.get(row_index, column_index).iter().filter(|value| !remaining.contains(*value)) {
+                    deleted.push(format!("{}{}{}", value, row_index + 1, column_index + 1));
+                }
+            }
+        }
+
+        let mut fields = self.fields.clone();
+        if fields.len() <= DELETED_FIELD {
+            fields.resize(DELETED_FIELD + 1, String::new());
+        }
+        fields[GRID_FIELD] = grid;
+        fields[DELETED_FIELD] = deleted.join(" ");
+        return fields.join(":");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Both lines use the same puzzle, the first with a placement in the center and two deleted candidates
+    const DELETIONS: &str = ":0300:1:53..7....6..195....98....6.8...6...34..8+53..17...2...6.6....28....419..5....8..79:113 614:115 116::";
+    const SINGLE: &str = ":0003:5:53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79::555::";
+
+    #[test]
+    fn parse_works() {
+        let entry = HodokuEntry::parse(DELETIONS).unwrap();
+
+        assert_eq!(entry.technique_code(), "0300");
+        assert_eq!(entry.board.to_line(), "530070000600195000098000060800060003400853001700020006060000280000419005000080079");
+        assert!(entry.board.is_given(0, 0));
+        assert!(!entry.board.is_given(4, 4));
+
+        let candidates = entry.candidates.unwrap();
+        assert_eq!(candidates.get(0, 2).to_string(), "24");
+        assert_eq!(candidates.get(0, 3).to_string(), "2");
+        assert_eq!(candidates.get(0, 5).to_string(), "2468");
+        assert!(candidates.get(4, 4).is_empty());
+    }
+
+    #[test]
+    fn parse_without_deletions() {
+        let entry = HodokuEntry::parse(SINGLE).unwrap();
+
+        assert_eq!(entry.technique_code(), "0003");
+        assert_eq!(entry.board.unsolved_count(), 51);
+        assert!(entry.candidates.is_none());
+    }
+
+    #[test]
+    fn round_trip_works() {
+        for line in [DELETIONS, SINGLE].iter() {
+            assert_eq!(HodokuEntry::parse(line).unwrap().to_line(), *line);
+        }
+
+        let short = HodokuEntry::parse(":0000:x:530070000600195000098000060800060003400803001700020006060000280000419005000080079").unwrap();
+        assert_eq!(short.to_line(), ":0000:x:53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79:");
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(HodokuEntry::parse(":0300:1").unwrap_err(), HodokuError::MissingField(3));
+        assert_eq!(HodokuEntry::parse(":0300:1:53..7:::").unwrap_err(), HodokuError::Grid { cells_found: 5 });
+        assert_eq!(HodokuEntry::parse(&DELETIONS.replace("113", "1x3")).unwrap_err(), HodokuError::Candidate(String::from("1x3")));
+        assert_eq!(HodokuEntry::parse(&DELETIONS.replace("113", "013")).unwrap_err().to_string(), "Invalid deleted candidate '013'");
+    }
+}
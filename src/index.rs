@@ -0,0 +1,98 @@
+use std::ops::Index;
+use crate::sudoku_board::{ HouseView, SudokuBoard, NONET_OF };
+
+// Board positions checked once at construction, so the typed accessors below can't be handed a column where a row
+// belongs or an index past 8. The `usize` methods on `SudokuBoard` still work as before.
+macro_rules! board_index {
+    ($($name:ident),*) => {
+        $(#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(usize);
+
+        impl $name {
+            // `None` unless `index` is [0..8] inclusive
+            pub fn new(index: usize) -> Option<$name> {
+                return if index <= 8 { Some($name(index)) } else { None };
+            }
+
+            pub fn get(self) -> usize {
+                return self.0;
+            }
+
+            // All 9 in ascending order
+            pub fn all() -> impl Iterator<Item = $name> {
+                return (0..=8).map($name);
+            }
+        })*
+    };
+}
+
+board_index!(RowIndex, ColumnIndex, NonetIndex);
+
+impl NonetIndex {
+    pub fn containing(row_index: RowIndex, column_index: ColumnIndex) -> NonetIndex {
+        return NonetIndex(NONET_OF[9 * row_index.0 + column_index.0]);
+    }
+}
+
+impl SudokuBoard {
+    // Transposed arguments don't compile, the doctest below checks it
+    #[doc = r#"
+```compile_fail,E0308
+use solv_a_line::index::ColumnIndex;
+use solv_a_line::sudoku_board::SudokuBoard;
+
+let board = SudokuBoard::new(&[0; 81]);
+let column_index = ColumnIndex::new(3).unwrap();
+board.row(column_index);
+```
+"#]
+    pub fn row(&self, row_index: RowIndex) -> HouseView<'_> {
+        return self.row_view(row_index.0);
+    }
+
+    pub fn column(&self, column_index: ColumnIndex) -> HouseView<'_> {
+        return self.column_view(column_index.0);
+    }
+
+    pub fn nonet(&self, nonet_index: NonetIndex) -> HouseView<'_> {
+        return self.nonet_view(nonet_index.0);
+    }
+}
+
+impl Index<(RowIndex, ColumnIndex)> for SudokuBoard {
+    type Output = u8;
+
+    fn index(&self, index: (RowIndex, ColumnIndex)) -> &Self::Output {
+        return &self[(index.0.0, index.1.0)];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::easy;
+
+    #[test]
+    fn index_bounds() {
+        assert_eq!(RowIndex::new(0).map(RowIndex::get), Some(0));
+        assert_eq!(ColumnIndex::new(8).map(ColumnIndex::get), Some(8));
+        assert_eq!(NonetIndex::new(9), None);
+        assert_eq!(RowIndex::new(usize::MAX), None);
+        assert_eq!(ColumnIndex::all().map(ColumnIndex::get).collect::<Vec<usize>>(), (0..=8).collect::<Vec<usize>>());
+        assert_eq!(NonetIndex::containing(RowIndex::new(4).unwrap(), ColumnIndex::new(7).unwrap()), NonetIndex::new(5).unwrap());
+    }
+
+    #[test]
+    fn typed_accessors_match_usize() {
+        let board = easy();
+
+        for (row_index, column_index) in RowIndex::all().zip(ColumnIndex::all()) {
+            assert_eq!(board.row(row_index).iter().collect::<Vec<u8>>(), board.get_row(row_index.get()));
+            assert_eq!(board.column(column_index).iter().collect::<Vec<u8>>(), board.get_column(column_index.get()));
+            assert_eq!(board[(row_index, column_index)], board[(row_index.get(), column_index.get())]);
+        }
+        for nonet_index in NonetIndex::all() {
+            assert_eq!(board.nonet(nonet_index).iter().collect::<Vec<u8>>(), board.get_nonet(nonet_index.get()));
+        }
+    }
+}
@@ -0,0 +1,430 @@
+use std::error::Error;
+use std::fmt::{ self, Display, Formatter, Write };
+
+// Minimal JSON document model behind the string formats that work without the `serde` feature, the game saves and the
+// solve traces. Public types are serialized through serde's derives instead; where one also has a `ToJson` form, both
+// write the same schema and the tests pin them to each other. Objects keep their keys in insertion order so the output
+// is deterministic.
+// Schema rules for every `ToJson` type: field names are snake_case and never renamed, readers ignore fields they don't
+// know so adding one stays compatible, and enums are written as strings (or objects tagged by a `type` string), so a
+// new variant is a new string that older readers reject with `None` instead of misreading.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Integer(u64), // Whole non-negative numbers, kept apart so counts and millis past 2^53 stay exact
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>)
+}
+
+pub trait ToJson {
+    fn to_json(&self) -> JsonValue;
+}
+
+impl JsonValue {
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        return match self {
+            JsonValue::Object(members) => members.iter().find(|(name, _)| name == key).map(|(_, value)| value),
+            _ => None
+        };
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        return match self {
+            JsonValue::Integer(number) => Some(*number),
+            JsonValue::Number(number) if number.fract() == 0.0 && *number >= 0.0 && *number < u64::MAX as f64 => Some(*number as u64),
+            _ => None
+        };
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        return match self {
+            JsonValue::Bool(value) => Some(*value),
+            _ => None
+        };
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        return match self {
+            JsonValue::String(value) => Some(value),
+            _ => None
+        };
+    }
+
+    pub fn as_array(&self) -> Option<&[JsonValue]> {
+        return match self {
+            JsonValue::Array(values) => Some(values),
+            _ => None
+        };
+    }
+
+    pub fn parse(text: &str) -> Result<JsonValue, JsonError> {
+        let mut parser = Parser { characters: text.char_indices().peekable(), text, depth: 0 };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        return match parser.characters.next() {
+            None => Ok(value),
+            Some((offset, _)) => Err(JsonError { offset, message: "trailing characters" })
+        };
+    }
+}
+
+impl From<bool> for JsonValue {
+    fn from(value: bool) -> JsonValue {
+        return JsonValue::Bool(value);
+    }
+}
+
+impl From<&str> for JsonValue {
+    fn from(value: &str) -> JsonValue {
+        return JsonValue::String(value.to_string());
+    }
+}
+
+impl From<String> for JsonValue {
+    fn from(value: String) -> JsonValue {
+        return JsonValue::String(value);
+    }
+}
+
+macro_rules! json_number_from {
+    ($variant:ident as $inner:ty: $($number:ty),*) => {
+        $(impl From<$number> for JsonValue {
+            fn from(value: $number) -> JsonValue {
+                return JsonValue::$variant(value as $inner);
+            }
+        })*
+    };
+}
+
+json_number_from!(Integer as u64: u8, u16, u32, u64, usize);
+json_number_from!(Number as f64: f32, f64);
+
+macro_rules! json_signed_from {
+    ($($number:ty),*) => {
+        $(impl From<$number> for JsonValue {
+            fn from(value: $number) -> JsonValue {
+                return if value < 0 { JsonValue::Number(value as f64) } else { JsonValue::Integer(value as u64) };
+            }
+        })*
+    };
+}
+
+json_signed_from!(i32, i64);
+
+impl<T: Into<JsonValue>> From<Option<T>> for JsonValue {
+    fn from(value: Option<T>) -> JsonValue {
+        return value.map_or(JsonValue::Null, Into::into);
+    }
+}
+
+impl<T: Into<JsonValue>> From<Vec<T>> for JsonValue {
+    fn from(values: Vec<T>) -> JsonValue {
+        return JsonValue::Array(values.into_iter().map(Into::into).collect());
+    }
+}
+
+// Compact output, no whitespace between tokens
+impl Display for JsonValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        return match self {
+            JsonValue::Null => write!(f, "null"),
+            JsonValue::Bool(value) => write!(f, "{}", value),
+            JsonValue::Integer(number) => write!(f, "{}", number),
+            JsonValue::Number(number) if !number.is_finite() => write!(f, "null"),
+            JsonValue::Number(number) => write!(f, "{:?}", number), // Always with a fraction or exponent, so it reads back as a `Number`
+            JsonValue::String(value) => write_string(f, value),
+            JsonValue::Array(values) => {
+                f.write_char('[')?;
+                for (index, value) in values.iter().enumerate() {
+                    if index != 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                f.write_char(']')
+            },
+            JsonValue::Object(members) => {
+                f.write_char('{')?;
+                for (index, (name, value)) in members.iter().enumerate() {
+                    if index != 0 {
+                        f.write_char(',')?;
+                    }
+                    write_string(f, name)?;
+                    write!(f, ":{}", value)?;
+                }
+                f.write_char('}')
+            }
+        };
+    }
+}
+
+fn write_string(f: &mut Formatter<'_>, value: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for character in value.chars() {
+        match character {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            character if (character as u32) < 0x20 => write!(f, "\\u{:04x}", character as u32)?,
+            character => f.write_char(character)?
+        }
+    }
+    return f.write_char('"');
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonError {
+    pub offset: usize, // Byte offset into the parsed text
+    pub message: &'static str
+}
+
+impl Display for JsonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        return write!(f, "Invalid JSON at byte {}: {}", self.offset, self.message);
+    }
+}
+
+impl Error for JsonError {}
+
+// Arrays and objects nested deeper than this are refused rather than recursed into, so hostile input can't overflow
+// the stack
+const MAX_DEPTH: usize = 128;
+
+struct Parser<'a> {
+    characters: std::iter::Peekable<std::str::CharIndices<'a>>,
+    text: &'a str,
+    depth: usize // Arrays and objects currently open
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.characters.next_if(|(_, character)| character.is_ascii_whitespace()).is_some() {}
+    }
+
+    fn offset(&mut self) -> usize {
+        return self.characters.peek().map_or(self.text.len(), |(offset, _)| *offset);
+    }
+
+    fn error<T>(&mut self, message: &'static str) -> Result<T, JsonError> {
+        return Err(JsonError { offset: self.offset(), message });
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), JsonError> {
+        self.skip_whitespace();
+        return match self.characters.next_if(|(_, character)| *character == expected) {
+            Some(_) => Ok(()),
+            None => self.error("unexpected character")
+        };
+    }
+
+    fn literal(&mut self, literal: &str, value: JsonValue) -> Result<JsonValue, JsonError> {
+        for expected in literal.chars() {
+            if self.characters.next_if(|(_, character)| *character == expected).is_none() {
+                return self.error("invalid literal");
+            }
+        }
+        return Ok(value);
+    }
+
+    fn value(&mut self) -> Result<JsonValue, JsonError> {
+        self.skip_whitespace();
+        return match self.characters.peek().map(|(_, character)| *character) {
+            Some('n') => self.literal("null", JsonValue::Null),
+            Some('t') => self.literal("true", JsonValue::Bool(true)),
+            Some('f') => self.literal("false", JsonValue::Bool(false)),
+            Some('"') => self.string().map(JsonValue::String),
+            Some('[') => self.nested(Parser::array),
+            Some('{') => self.nested(Parser::object),
+            Some(character) if character == '-' || character.is_ascii_digit() => self.number(),
+            Some(_) => self.error("unexpected character"),
+            None => self.error("unexpected end of input")
+        };
+    }
+
+    fn nested(&mut self, parse: fn(&mut Self) -> Result<JsonValue, JsonError>) -> Result<JsonValue, JsonError> {
+        if self.depth == MAX_DEPTH {
+            return self.error("nesting too deep");
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        return value;
+    }
+
+    fn number(&mut self) -> Result<JsonValue, JsonError> {
+        let start = self.offset();
+        while self.characters.next_if(|(_, character)| character.is_ascii_digit() || "+-.eE".contains(*character)).is_some() {}
+        let end = self.offset();
+        if let Ok(number) = self.text[start..end].parse::<u64>() {
+            return Ok(JsonValue::Integer(number));
+        }
+        return match self.text[start..end].parse::<f64>() {
+            Ok(number) => Ok(JsonValue::Number(number)),
+            Err(_) => Err(JsonError { offset: start, message: "invalid number" })
+        };
+    }
+
+    fn string(&mut self) -> Result<String, JsonError> {
+        self.expect('"')?;
+        let mut value = String::new();
+        loop {
+            let character = match self.characters.next() {
+                Some((_, character)) => character,
+                None => return self.error("unterminated string")
+            };
+            match character {
+                '"' => return Ok(value),
+                '\\' => {
+                    let escaped = match self.characters.next() {
+                        Some((_, escaped)) => escaped,
+                        None => return self.error("unterminated string")
+                    };
+                    value.push(match escaped {
+                        '"' => '"',
+                        '\\' => '\\',
+                        '/' => '/',
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'u' => self.unicode_escape()?,
+                        _ => return self.error("invalid escape")
+                    });
+                },
+                character => value.push(character)
+            }
+        }
+    }
+
+    // The 4 hex digits after `\u`, joined with the `\u` escape of the low half when they're the high half of a
+    // surrogate pair
+    fn unicode_escape(&mut self) -> Result<char, JsonError> {
+        let start = self.offset();
+        let invalid = JsonError { offset: start, message: "invalid unicode escape" };
+        let code = self.hex_digits().ok_or(invalid)?;
+        if !(0xD800..=0xDBFF).contains(&code) {
+            return char::from_u32(code).ok_or(invalid);
+        }
+
+        if self.characters.next_if(|(_, character)| *character == '\\').is_none() || self.characters.next_if(|(_, character)| *character == 'u').is_none() {
+            return Err(invalid);
+        }
+        let low = self.hex_digits().filter(|low| (0xDC00..=0xDFFF).contains(low)).ok_or(invalid)?;
+        return char::from_u32(0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00)).ok_or(invalid);
+    }
+
+    fn hex_digits(&mut self) -> Option<u32> {
+        let digits: String = (0..4).filter_map(|_| self.characters.next().map(|(_, digit)| digit)).collect();
+        return u32::from_str_radix(&digits, 16).ok().filter(|_| digits.len() == 4);
+    }
+
+    fn array(&mut self) -> Result<JsonValue, JsonError> {
+        self.expect('[')?;
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.characters.next_if(|(_, character)| *character == ']').is_some() {
+            return Ok(JsonValue::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+            match self.characters.next() {
+                Some((_, ',')) => continue,
+                Some((_, ']')) => return Ok(JsonValue::Array(values)),
+                _ => return self.error("expected ',' or ']'")
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<JsonValue, JsonError> {
+        self.expect('{')?;
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if self.characters.next_if(|(_, character)| *character == '}').is_some() {
+            return Ok(JsonValue::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            let name = self.string()?;
+            self.expect(':')?;
+            members.push((name, self.value()?));
+            self.skip_whitespace();
+            match self.characters.next() {
+                Some((_, ',')) => continue,
+                Some((_, '}')) => return Ok(JsonValue::Object(members)),
+                _ => return self.error("expected ',' or '}'")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_string_works() {
+        let value = JsonValue::Object(vec![
+            (String::from("name"), "a \"quoted\"\nline".into()),
+            (String::from("values"), vec![1u8, 2, 3].into()),
+            (String::from("ratio"), 0.5.into()),
+            (String::from("missing"), None::<u8>.into()),
+            (String::from("ok"), true.into())
+        ]);
+
+        assert_eq!(value.to_string(), r#"{"name":"a \"quoted\"\nline","values":[1,2,3],"ratio":0.5,"missing":null,"ok":true}"#);
+    }
+
+    #[test]
+    fn parse_round_trip() {
+        let text = r#"{"name":"a \"quoted\"\nline é","values":[1,-2.5,3e2],"nested":{"empty":[],"none":null},"ok":false}"#;
+
+        let value = JsonValue::parse(text).unwrap();
+
+        assert_eq!(value.get("name").and_then(JsonValue::as_str), Some("a \"quoted\"\nline é"));
+        assert_eq!(value.get("values").and_then(JsonValue::as_array).map(|values| values.len()), Some(3));
+        assert_eq!(value.get("values").unwrap().as_array().unwrap()[2].as_u64(), Some(300));
+        assert_eq!(value.get("ok").and_then(JsonValue::as_bool), Some(false));
+        assert_eq!(JsonValue::parse(&value.to_string()), Ok(value));
+    }
+
+    #[test]
+    fn large_integers_are_exact() {
+        let value = JsonValue::from(vec![(1u64 << 53) + 1, u64::MAX]);
+
+        assert_eq!(value.to_string(), "[9007199254740993,18446744073709551615]");
+        assert_eq!(JsonValue::parse(&value.to_string()), Ok(value));
+        assert_eq!(JsonValue::parse("9007199254740993").unwrap().as_u64(), Some((1 << 53) + 1));
+        assert_eq!(JsonValue::parse("1e2").unwrap().as_u64(), Some(100));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(JsonValue::parse("[1, 2").unwrap_err().message, "expected ',' or ']'");
+        assert_eq!(JsonValue::parse("{} x").unwrap_err(), JsonError { offset: 3, message: "trailing characters" });
+        assert_eq!(JsonValue::parse("tru").unwrap_err().message, "invalid literal");
+        assert_eq!(JsonValue::parse("\"open").unwrap_err().message, "unterminated string");
+        assert_eq!(JsonValue::parse("\"\\uD83D\"").unwrap_err(), JsonError { offset: 3, message: "invalid unicode escape" });
+        assert_eq!(JsonValue::parse("\"\\uDE00\"").unwrap_err().message, "invalid unicode escape");
+    }
+
+    #[test]
+    fn surrogate_pairs_are_joined() {
+        assert_eq!(JsonValue::parse(r#""\uD83D\uDE00 \u00e9""#), Ok(JsonValue::from("\u{1F600} é")));
+    }
+
+    #[test]
+    fn nesting_is_limited() {
+        let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+
+        assert!(JsonValue::parse(&nested(MAX_DEPTH)).is_ok());
+        assert_eq!(JsonValue::parse(&nested(MAX_DEPTH + 1)).unwrap_err(), JsonError { offset: MAX_DEPTH, message: "nesting too deep" });
+        assert_eq!(JsonValue::parse(&"{\"a\":".repeat(1_000)).unwrap_err().message, "nesting too deep");
+    }
+}
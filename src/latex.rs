@@ -0,0 +1,120 @@
+use crate::sudoku_board::SudokuBoard;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LatexStyle {
+    Tabular, // Plain `tabular` environment, needs no extra packages
+    SudokuPackage // The `sudoku` package's `|`-delimited nine line syntax
+}
+
+impl SudokuBoard {
+    pub fn to_latex(&self, style: LatexStyle) -> String {
+        let mut latex = String::new();
+        match style {
+            LatexStyle::Tabular => {
+                latex.push_str("\\begin{tabular}{|ccc|ccc|ccc|}\n\\hline\n");
+                for row in 0..=8 {
+                    let spaces: Vec<String> = self.get_row(row).iter().map(|value| latex_space(*value)).collect();
+                    latex.push_str(&spaces.join(" & "));
+                    latex.push_str(" \\\\\n");
+                    if row % 3 == 2 {
+                        latex.push_str("\\hline\n");
+                    }
+                }
+                latex.push_str("\\end{tabular}\n");
+            },
+            LatexStyle::SudokuPackage => {
+                latex.push_str("\\begin{sudoku}\n");
+                for row in 0..=8 {
+                    for value in self.get_row(row) {
+                        latex.push('|');
+                        latex.push_str(&latex_space(value));
+                    }
+                    latex.push_str("|.\n");
+                }
+                latex.push_str("\\end{sudoku}\n");
+            }
+        }
+        return latex;
+    }
+}
+
+pub fn puzzles_to_latex(puzzles: &[SudokuBoard], per_page: usize, style: LatexStyle) -> String {
+    if per_page == 0 {
+        panic!("At least one puzzle must fit on a page");
+    }
+
+    let pages: Vec<String> = puzzles.chunks(per_page).map(|page| {
+        let rendered_puzzles: Vec<String> = page.iter().map(|puzzle| puzzle.to_latex(style)).collect();
+        return rendered_puzzles.join("\n");
+    }).collect();
+    return pages.join("\\newpage\n");
+}
+
+fn latex_space(value: u8) -> String {
+    return if value == 0 { String::from(" ") } else { value.to_string() };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::hard;
+
+    #[test]
+    fn to_latex_tabular_works() {
+        assert_eq!(hard().to_latex(LatexStyle::Tabular), concat!(
+            "\\begin{tabular}{|ccc|ccc|ccc|}\n",
+            "\\hline\n",
+            "  &   &   &   &   &   &   &   &   \\\\\n",
+            "  &   & 2 &   &   & 5 &   & 4 &   \\\\\n",
+            "1 &   & 8 &   & 4 &   &   &   &   \\\\\n",
+            "\\hline\n",
+            "  &   &   &   &   &   & 4 &   & 3 \\\\\n",
+            "  &   & 6 &   & 5 &   &   &   & 1 \\\\\n",
+            "  &   &   &   & 2 &   &   &   & 6 \\\\\n",
+            "\\hline\n",
+            "3 &   & 1 &   &   &   &   & 8 &   \\\\\n",
+            "2 &   & 7 &   &   &   & 6 &   &   \\\\\n",
+            "  &   &   &   &   & 6 & 1 & 3 & 9 \\\\\n",
+            "\\hline\n",
+            "\\end{tabular}\n"
+        ));
+    }
+
+    #[test]
+    fn to_latex_sudoku_package_works() {
+        assert_eq!(hard().to_latex(LatexStyle::SudokuPackage), concat!(
+            "\\begin{sudoku}\n",
+            "| | | | | | | | | |.\n",
+            "| | |2| | |5| |4| |.\n",
+            "|1| |8| |4| | | | |.\n",
+            "| | | | | | |4| |3|.\n",
+            "| | |6| |5| | | |1|.\n",
+            "| | | | |2| | | |6|.\n",
+            "|3| |1| | | | |8| |.\n",
+            "|2| |7| | | |6| | |.\n",
+            "| | | | | |6|1|3|9|.\n",
+            "\\end{sudoku}\n"
+        ));
+    }
+
+    #[test]
+    fn puzzles_to_latex_paginates() {
+        let puzzles = vec![hard(), hard(), hard(), hard(), hard()];
+
+        let latex = puzzles_to_latex(&puzzles, 2, LatexStyle::SudokuPackage);
+        let pages: Vec<&str> = latex.split("\\newpage\n").collect();
+
+        assert_eq!(pages.len(), 3);
+        assert_eq!(pages[0].matches("\\begin{sudoku}").count(), 2);
+        assert_eq!(pages[1].matches("\\begin{sudoku}").count(), 2);
+        assert_eq!(pages[2], hard().to_latex(LatexStyle::SudokuPackage));
+        assert_eq!(puzzles_to_latex(&puzzles, 5, LatexStyle::Tabular).matches("\\newpage").count(), 0);
+        assert_eq!(puzzles_to_latex(&[], 2, LatexStyle::Tabular), "");
+    }
+
+    #[test]
+    #[should_panic]
+    fn puzzles_to_latex_zero_per_page() {
+        puzzles_to_latex(&[hard()], 0, LatexStyle::Tabular);
+    }
+}
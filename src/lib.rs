@@ -1,2 +1,136 @@
+pub mod analysis;
+#[cfg(feature = "async")]
+pub mod async_solve;
+pub mod background;
+pub mod board_builder;
+pub mod candidate_board;
+pub mod checkpoint;
+pub mod compact;
+pub mod comparison;
+pub mod conflict_tracker;
+#[cfg(feature = "datasets")]
+pub mod datasets;
+pub mod digit;
+pub mod file_io;
+pub mod fingerprint;
+pub mod game_session;
+pub mod generator;
+pub mod hodoku;
+pub mod index;
+pub(crate) mod json;
+pub mod latex;
+pub mod markdown;
+pub mod moves;
+pub mod notes;
+pub mod parse;
+pub mod peers;
+#[cfg(feature = "image")]
+pub mod png;
+pub mod puzzle_info;
+pub mod qqwing;
+pub mod random;
+pub mod rating;
+pub mod render;
+#[cfg(feature = "simd")]
+mod simd;
+pub mod simple_sudoku;
+pub mod solver_config;
+#[cfg(any(test, feature = "proptest"))]
+pub mod strategies;
 pub mod sudoku_board;
-pub mod sudoku_solver;
\ No newline at end of file
+#[cfg(feature = "interop-sudoku")]
+pub mod sudoku_interop;
+pub mod sudoku_solver;
+pub mod svg;
+pub mod symmetry;
+pub mod techniques;
+#[cfg(test)]
+mod test_fixtures;
+pub mod trace;
+pub mod tsv;
+pub mod uniqueness;
+pub mod validation;
+pub mod walkthrough;
+
+use sudoku_board::SudokuBoard;
+use sudoku_solver::SudokuSolver;
+
+// One-shot solve of a row-major puzzle, any invalid or unsolvable input gives `None` instead of a panic
+pub fn solve_puzzle(puzzle: &[u8; 81]) -> Option<[u8; 81]> {
+    let board = SudokuBoard::try_new(puzzle).ok()?;
+    if !board.all_spaces_valid() {
+        return None;
+    }
+
+    return SudokuSolver::new(&board).try_solve().ok().map(|solved_board| solved_board.to_array());
+}
+
+// Same as `solve_puzzle` for 81 character strings, with `0` or `.` for empty spaces
+pub fn solve_str(puzzle: &str) -> Option<String> {
+    let mut values = [0; 81];
+    let mut characters = puzzle.chars();
+    for value in values.iter_mut() {
+        *value = match characters.next()? {
+            '.' => 0,
+            character => character.to_digit(10)? as u8
+        };
+    }
+    if characters.next().is_some() {
+        return None;
+    }
+
+    return solve_puzzle(&values).map(|solution| solution.iter().map(|value| char::from(b'0' + value)).collect());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "000260701680070090190004500820100040004602900050003028009300074040050036703018000";
+    const SOLUTION: &str = "435269781682571493197834562826195347374682915951743628519326874248957136763418259";
+
+    #[test]
+    fn solve_puzzle_works() {
+        let mut puzzle = [0; 81];
+        for (value, character) in puzzle.iter_mut().zip(PUZZLE.chars()) {
+            *value = character.to_digit(10).unwrap() as u8;
+        }
+
+        let solution = solve_puzzle(&puzzle).unwrap();
+
+        assert_eq!(solution.iter().map(|value| char::from(b'0' + value)).collect::<String>(), SOLUTION);
+    }
+
+    #[test]
+    fn solve_str_works() {
+        assert_eq!(solve_str(PUZZLE).as_deref(), Some(SOLUTION));
+        assert_eq!(solve_str(&PUZZLE.replace('0', ".")).as_deref(), Some(SOLUTION));
+        assert_eq!(solve_str(&PUZZLE[1..]), None);
+        assert_eq!(solve_str(&format!("{}0", PUZZLE)), None);
+        assert_eq!(solve_str(&PUZZLE.replacen('0', "x", 1)), None);
+    }
+
+    #[test]
+    fn solve_puzzle_invalid_board() {
+        let mut out_of_range = [0; 81];
+        out_of_range[0] = 10;
+        let mut far_out_of_range = [0; 81];
+        far_out_of_range[40] = 17; // Unique in all of its houses, so only the range check catches it
+        let mut duplicate = [0; 81];
+        duplicate[0] = 5;
+        duplicate[8] = 5;
+
+        assert_eq!(solve_puzzle(&out_of_range), None);
+        assert_eq!(solve_puzzle(&far_out_of_range), None);
+        assert_eq!(solve_puzzle(&duplicate), None);
+    }
+
+    #[test]
+    fn solve_puzzle_unsolvable_board() {
+        let mut unsolvable = [0; 81];
+        unsolvable[..8].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        unsolvable[35] = 9;
+
+        assert_eq!(solve_puzzle(&unsolvable), None);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::sudoku_board::SudokuBoard;
+
+impl SudokuBoard {
+    // Markdown tables can't draw nonet borders, use `to_markdown_code_block` when those matter
+    pub fn to_markdown(&self, givens: Option<&SudokuBoard>) -> String {
+        let mut markdown = String::from("| 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9 |\n|:-:|:-:|:-:|:-:|:-:|:-:|:-:|:-:|:-:|\n");
+        for row in 0..=8 {
+            markdown.push('|');
+            for column in 0..=8 {
+                let value = self[(row, column)];
+                let is_given = givens.is_some_and(|givens| givens[(row, column)] != 0);
+                if value == 0 {
+                    markdown.push_str("   |");
+                }
+                else if is_given {
+                    markdown.push_str(&format!(" **{}** |", value));
+                }
+                else {
+                    markdown.push_str(&format!(" {} |", value));
+                }
+            }
+            markdown.push('\n');
+        }
+        return markdown;
+    }
+
+    pub fn to_markdown_code_block(&self) -> String {
+        return format!("```text\n{}\n```\n", self.to_string().trim_matches('\n'));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_fixtures::easy;
+
+    #[test]
+    fn to_markdown_works() {
+        let markdown = easy().to_markdown(None);
+
+        assert_eq!(markdown, concat!(
+            "| 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9 |\n",
+            "|:-:|:-:|:-:|:-:|:-:|:-:|:-:|:-:|:-:|\n",
+            "|   | 7 | 3 | 8 | 9 | 4 | 5 | 1 | 2 |\n",
+            "| 9 | 1 | 2 | 7 | 3 | 5 | 4 | 8 | 6 |\n",
+            "| 8 | 4 | 5 |   |   | 2 | 9 | 7 | 3 |\n",
+            "| 7 | 9 | 8 | 2 | 6 | 1 | 3 | 5 | 4 |\n",
+            "| 5 | 2 | 6 | 4 | 7 | 3 | 8 | 9 | 1 |\n",
+            "| 1 | 3 | 4 | 5 | 8 | 9 | 2 | 6 | 7 |\n",
+            "| 4 | 6 | 9 |   | 2 | 8 | 7 | 3 | 5 |\n",
+            "| 2 | 8 | 7 | 3 | 5 | 6 | 1 | 4 | 9 |\n",
+            "| 3 | 5 | 1 | 9 | 4 | 7 | 6 | 2 |   |\n"
+        ));
+
+        let data_rows: Vec<&str> = markdown.lines().skip(2).collect();
+        assert_eq!(data_rows.len(), 9);
+        assert!(data_rows.iter().all(|row| row.matches('|').count() == 10));
+    }
+
+    #[test]
+    fn to_markdown_bold_givens_works() {
+        let givens = easy();
+        let mut board = easy();
+        board.set_value(0, 0, 6);
+
+        let markdown = board.to_markdown(Some(&givens));
+
+        assert_eq!(markdown.lines().nth(2).unwrap(), "| 6 | **7** | **3** | **8** | **9** | **4** | **5** | **1** | **2** |");
+        assert_eq!(markdown.matches("**").count(), 2 * 76);
+    }
+
+    #[test]
+    fn to_markdown_code_block_works() {
+        assert_eq!(easy().to_markdown_code_block(), concat!(
+            "```text\n",
+            "  ┌                   ┐\n",
+            "  │ 0 7 3 8 9 4 5 1 2 │\n",
+            "  │ 9 1 2 7 3 5 4 8 6 │\n",
+            "  │ 8 4 5 0 0 2 9 7 3 │\n",
+            "  │ 7 9 8 2 6 1 3 5 4 │\n",
+            "  │ 5 2 6 4 7 3 8 9 1 │\n",
+            "  │ 1 3 4 5 8 9 2 6 7 │\n",
+            "  │ 4 6 9 0 2 8 7 3 5 │\n",
+            "  │ 2 8 7 3 5 6 1 4 9 │\n",
+            "  │ 3 5 1 9 4 7 6 2 0 │\n",
+            "  └                   ┘\n",
+            "```\n"
+        ));
+    }
+}
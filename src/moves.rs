@@ -0,0 +1,268 @@
+use std::error::Error;
+use std::fmt::{ self, Display, Formatter };
+use crate::sudoku_board::{ House, SudokuBoard };
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Move {
+    pub row: usize,
+    pub column: usize,
+    pub value: Option<u8> // `None` erases the space
+}
+
+impl Move {
+    pub fn place(row: usize, column: usize, value: u8) -> Move {
+        return Move { row, column, value: Some(value) };
+    }
+
+    pub fn erase(row: usize, column: usize) -> Move {
+        return Move { row, column, value: None };
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveError {
+    OutOfBounds { row: usize, column: usize },
+    ValueOutOfRange(u8), // Placements must be [1..9] inclusive, erasing is spelled `None`
+    LockedGiven { row: usize, column: usize },
+    Conflict { house: House, value: u8 }, // Only reported when `MoveOptions::reject_conflicts` is set
+    SpaceFilled { row: usize, column: usize } // Notes can only be kept on empty spaces
+}
+
+impl Display for MoveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        return match self {
+            MoveError::OutOfBounds { row, column } => write!(f, "({}, {}) is outside the board", row, column),
+            MoveError::ValueOutOfRange(value) => write!(f, "Placed values must be [1..9] inclusive, it was {}", value),
+            MoveError::LockedGiven { row, column } => write!(f, "({}, {}) is a locked given", row, column),
+            MoveError::Conflict { house, value } => write!(f, "{} is already used in {:?}", value, house),
+            MoveError::SpaceFilled { row, column } => write!(f, "({}, {}) already holds a value", row, column)
+        };
+    }
+}
+
+impl Error for MoveError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MoveOptions {
+    pub reject_conflicts: bool
+}
+
+impl MoveOptions {
+    pub fn new() -> MoveOptions {
+        return MoveOptions::default();
+    }
+
+    pub fn reject_conflicts(mut self, reject_conflicts: bool) -> MoveOptions {
+        self.reject_conflicts = reject_conflicts;
+        return self;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeError {
+    Disagreement { row: usize, column: usize, value: u8, source_value: u8 }, // Only reported without `MergeOptions::overwrite`
+    LockedGiven { row: usize, column: usize } // Locked givens are never overwritten
+}
+
+impl Display for MergeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        return match self {
+            MergeError::Disagreement { row, column, value, source_value } => write!(f, "({}, {}) holds {} but the source has {}", row, column, value, source_value),
+            MergeError::LockedGiven { row, column } => write!(f, "({}, {}) is a locked given", row, column)
+        };
+    }
+}
+
+impl Error for MergeError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MergeOptions {
+    pub overwrite: bool // Replace filled spaces the source disagrees with instead of erroring
+}
+
+impl MergeOptions {
+    pub fn new() -> MergeOptions {
+        return MergeOptions::default();
+    }
+
+    pub fn overwrite(mut self, overwrite: bool) -> MergeOptions {
+        self.overwrite = overwrite;
+        return self;
+    }
+}
+
+impl SudokuBoard {
+    // Copies the source's values into the empty spaces and returns how many spaces changed. Empty spaces of the source
+    // are skipped. The board is left untouched when an error is returned.
+    pub fn fill_empty_from(&mut self, source: &SudokuBoard) -> Result<usize, MergeError> {
+        return self.fill_empty_from_with(source, &MergeOptions::default());
+    }
+
+    pub fn fill_empty_from_with(&mut self, source: &SudokuBoard, options: &MergeOptions) -> Result<usize, MergeError> {
+        let mut changed_spaces = Vec::new();
+        for index in 0..81 {
+            let (row, column) = (index / 9, index % 9);
+            let (value, source_value) = (self[(row, column)], source[(row, column)]);
+            if source_value == 0 || value == source_value {
+                continue;
+            }
+            if value != 0 {
+                if self.givens_locked() && self.is_given(row, column) {
+                    return Err(MergeError::LockedGiven { row, column });
+                }
+                if !options.overwrite {
+                    return Err(MergeError::Disagreement { row, column, value, source_value });
+                }
+            }
+            changed_spaces.push((row, column, source_value));
+        }
+
+        for &(row, column, value) in changed_spaces.iter() {
+            self.set_value(row, column, value);
+        }
+        return Ok(changed_spaces.len());
+    }
+
+    // Givens are only protected while they're locked, see `lock_givens`
+    pub fn apply_move(&mut self, player_move: &Move) -> Result<(), MoveError> {
+        return self.apply_move_with(player_move, &MoveOptions::default());
+    }
+
+    pub fn apply_move_with(&mut self, player_move: &Move, options: &MoveOptions) -> Result<(), MoveError> {
+        let Move { row, column, value } = *player_move;
+        if row > 8 || column > 8 {
+            return Err(MoveError::OutOfBounds { row, column });
+        }
+        if let Some(value) = value {
+            if !(1..=9).contains(&value) {
+                return Err(MoveError::ValueOutOfRange(value));
+            }
+        }
+        if self.givens_locked() && self.is_given(row, column) && value != Some(self[(row, column)]) {
+            return Err(MoveError::LockedGiven { row, column });
+        }
+        if let (true, Some(value)) = (options.reject_conflicts, value) {
+            let houses = [House::Row(row), House::Column(column), House::Nonet(SudokuBoard::nonet_index_of(row, column))];
+            for house in houses.iter() {
+                if house.spaces().iter().any(|&space| space != (row, column) && self[space] == value) {
+                    return Err(MoveError::Conflict { house: *house, value });
+                }
+            }
+        }
+
+        self.set_value(row, column, value.unwrap_or(0));
+        return Ok(());
+    }
+
+    // Stops at the first rejected move, every move before it stays applied
+    pub fn apply_moves(&mut self, moves: &[Move]) -> Result<(), (usize, MoveError)> {
+        return self.apply_moves_with(moves, &MoveOptions::default());
+    }
+
+    pub fn apply_moves_with(&mut self, moves: &[Move], options: &MoveOptions) -> Result<(), (usize, MoveError)> {
+        for (index, player_move) in moves.iter().enumerate() {
+            self.apply_move_with(player_move, options).map_err(|error| (index, error))?;
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::easy;
+
+    #[test]
+    fn apply_moves_replay_works() {
+        let mut board = easy();
+        board.lock_givens();
+
+        let moves = [
+            Move::place(0, 0, 6),
+            Move::place(2, 3, 9),
+            Move::erase(2, 3),
+            Move::place(2, 3, 6),
+            Move::place(2, 4, 1),
+            Move::place(6, 3, 1),
+            Move::place(8, 8, 8)
+        ];
+
+        assert_eq!(board.apply_moves(&moves), Ok(()));
+        assert!(board.is_solved());
+    }
+
+    #[test]
+    fn apply_moves_reports_first_failure() {
+        let mut board = easy();
+        board.lock_givens();
+
+        let moves = [
+            Move::place(0, 0, 6),
+            Move::place(0, 1, 1), // Overwrites a given
+            Move::place(8, 8, 8)
+        ];
+
+        assert_eq!(board.apply_moves(&moves), Err((1, MoveError::LockedGiven { row: 0, column: 1 })));
+        assert_eq!(board[(0, 0)], 6);
+        assert_eq!(board[(0, 1)], 7);
+        assert_eq!(board[(8, 8)], 0);
+    }
+
+    #[test]
+    fn apply_move_validation_works() {
+        let mut board = easy();
+
+        assert_eq!(board.apply_move(&Move::place(9, 0, 1)), Err(MoveError::OutOfBounds { row: 9, column: 0 }));
+        assert_eq!(board.apply_move(&Move::place(0, 0, 0)), Err(MoveError::ValueOutOfRange(0)));
+        assert_eq!(board.apply_move(&Move::place(0, 0, 10)), Err(MoveError::ValueOutOfRange(10)));
+
+        let reject_conflicts = MoveOptions::new().reject_conflicts(true);
+        assert_eq!(board.apply_move_with(&Move::place(2, 3, 8), &reject_conflicts), Err(MoveError::Conflict { house: House::Row(2), value: 8 }));
+        assert_eq!(board.apply_move_with(&Move::place(2, 3, 6), &reject_conflicts), Ok(()));
+
+        // Without the option conflicting placements go through, as do edits to unlocked givens
+        assert_eq!(board.apply_move(&Move::place(2, 4, 9)), Ok(()));
+        assert_eq!(board.apply_move(&Move::erase(0, 1)), Ok(()));
+        assert_eq!(board.unsolved_count(), 4);
+    }
+
+    #[test]
+    fn fill_empty_from_works() {
+        let mut board = easy();
+        board.lock_givens();
+        board.apply_move(&Move::place(2, 3, 6)).unwrap();
+        let solution = crate::sudoku_solver::SudokuSolver::new(&easy()).solve();
+
+        assert_eq!(board.fill_empty_from(&solution), Ok(4));
+        assert_eq!(board, solution);
+        assert_eq!(board.fill_empty_from(&solution), Ok(0));
+        assert_eq!(board.fill_empty_from(&SudokuBoard::new(&[0; 81])), Ok(0));
+    }
+
+    #[test]
+    fn fill_empty_from_disagreement() {
+        let mut board = easy();
+        board.lock_givens();
+        board.apply_move(&Move::place(2, 3, 1)).unwrap();
+        let solution = crate::sudoku_solver::SudokuSolver::new(&easy()).solve();
+
+        assert_eq!(board.fill_empty_from(&solution), Err(MergeError::Disagreement { row: 2, column: 3, value: 1, source_value: 6 }));
+        assert_eq!(board.unsolved_count(), 4); // Nothing was filled
+
+        assert_eq!(board.fill_empty_from_with(&solution, &MergeOptions::new().overwrite(true)), Ok(5));
+        assert_eq!(board, solution);
+    }
+
+    #[test]
+    fn fill_empty_from_keeps_locked_givens() {
+        let mut board = easy();
+        board.lock_givens();
+        let mut source = easy();
+        source.set_value(0, 1, 6);
+
+        assert_eq!(board.fill_empty_from_with(&source, &MergeOptions::new().overwrite(true)), Err(MergeError::LockedGiven { row: 0, column: 1 }));
+        board.unlock_givens();
+        assert_eq!(board.fill_empty_from_with(&source, &MergeOptions::new().overwrite(true)), Ok(1));
+        assert_eq!(board[(0, 1)], 6);
+    }
+}
@@ -0,0 +1,156 @@
+#[cfg(feature = "serde")]
+use std::convert::TryFrom;
+use crate::candidate_board::CandidateSet;
+use crate::json::{ JsonValue, ToJson };
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NotesPolicy {
+    #[default]
+    Manual, // Notes only change when the player changes them
+    AutoPrune // Placing a value also removes it from the notes of the space's 20 peers
+}
+
+// A player's own pencil marks. Unlike `CandidateBoard` nothing here is derived from the board, players forget marks
+// and add wrong ones and the notes keep exactly what they entered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(into = "NotesRecord", try_from = "NotesRecord"))]
+pub struct Notes {
+    marks: [CandidateSet; 81]
+}
+
+impl Default for Notes {
+    fn default() -> Notes {
+        return Notes { marks: [CandidateSet::new(); 81] };
+    }
+}
+
+impl Notes {
+    pub fn new() -> Notes {
+        return Notes::default();
+    }
+
+    // Whether the mark is set afterwards. `value` must be [1..9] inclusive.
+    pub fn toggle_note(&mut self, row: usize, column: usize, value: u8) -> bool {
+        let marks = &mut self.marks[9 * row + column];
+        if marks.contains(value) {
+            marks.remove(value);
+            return false;
+        }
+        marks.insert(value);
+        return true;
+    }
+
+    pub fn notes_at(&self, row: usize, column: usize) -> CandidateSet {
+        return self.marks[9 * row + column];
+    }
+
+    pub fn set_notes_at(&mut self, row: usize, column: usize, marks: CandidateSet) {
+        self.marks[9 * row + column] = marks;
+    }
+
+    pub fn clear_notes_at(&mut self, row: usize, column: usize) {
+        self.marks[9 * row + column] = CandidateSet::new();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.marks.iter().all(CandidateSet::is_empty);
+    }
+
+    // The inverse of `to_json`, `None` unless `value` is an array of 81 strings of the digits 1 to 9
+    pub(crate) fn from_json(value: &JsonValue) -> Option<Notes> {
+        let spaces = value.as_array().filter(|spaces| spaces.len() == 81)?;
+        let mut notes = Notes::new();
+        for (index, space) in spaces.iter().enumerate() {
+            for character in space.as_str()?.chars() {
+                match character.to_digit(10) {
+                    Some(value) if value != 0 => notes.marks[index].insert(value as u8),
+                    _ => return None
+                }
+            }
+        }
+        return Some(notes);
+    }
+}
+
+// Row-major array of 81 strings, each listing the marked digits of one space in ascending order
+impl ToJson for Notes {
+    fn to_json(&self) -> JsonValue {
+        return JsonValue::Array(self.marks.iter().map(|marks| JsonValue::from(marks.to_string())).collect());
+    }
+}
+
+// The serde form of `Notes`, the same array of strings as its `ToJson` form
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+struct NotesRecord(Vec<String>);
+
+#[cfg(feature = "serde")]
+impl From<Notes> for NotesRecord {
+    fn from(notes: Notes) -> NotesRecord {
+        return NotesRecord(notes.marks.iter().map(CandidateSet::to_string).collect());
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<NotesRecord> for Notes {
+    type Error = &'static str;
+
+    fn try_from(record: NotesRecord) -> Result<Notes, &'static str> {
+        return Notes::from_json(&JsonValue::from(record.0)).ok_or("expected 81 strings of the digits 1 to 9");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggle_note_works() {
+        let mut notes = Notes::new();
+
+        assert!(notes.toggle_note(0, 0, 6));
+        assert!(notes.toggle_note(0, 0, 1));
+        assert!(!notes.toggle_note(0, 0, 6));
+        assert_eq!(notes.notes_at(0, 0).iter().collect::<Vec<u8>>(), vec![1]);
+        assert!(notes.notes_at(0, 1).is_empty());
+
+        notes.clear_notes_at(0, 0);
+        assert!(notes.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn toggle_note_invalid_value() {
+        Notes::new().toggle_note(0, 0, 0);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut notes = Notes::new();
+        notes.toggle_note(0, 0, 6);
+        notes.toggle_note(0, 0, 1);
+        notes.toggle_note(8, 8, 9);
+
+        let json = JsonValue::parse(&notes.to_json().to_string()).unwrap();
+
+        assert_eq!(json.as_array().unwrap()[0].as_str(), Some("16"));
+        assert_eq!(Notes::from_json(&json), Some(notes));
+        assert_eq!(Notes::from_json(&JsonValue::parse("[\"1\"]").unwrap()), None);
+        assert_eq!(Notes::from_json(&JsonValue::Array(vec![JsonValue::from("0"); 81])), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_matches_to_json() {
+        let mut notes = Notes::new();
+        notes.toggle_note(4, 4, 3);
+        notes.toggle_note(4, 4, 2);
+
+        let json = serde_json::to_string(&notes).unwrap();
+
+        assert_eq!(json, notes.to_json().to_string());
+        assert_eq!(serde_json::from_str::<Notes>(&json).unwrap(), notes);
+        assert!(serde_json::from_str::<Notes>("[\"1\"]").is_err());
+    }
+}
@@ -0,0 +1,174 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt::{ self, Display, Formatter };
+use std::str::FromStr;
+use crate::board_builder::BuildError;
+use crate::sudoku_board::SudokuBoard;
+use crate::sudoku_solver::SudokuSolver;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseGridError {
+    pub cells_found: usize,
+    // Where it went wrong, counting characters from 1: the 82nd cell, or just past the last cell when there are too few
+    pub line: usize,
+    pub column: usize
+}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        return write!(f, "Expected 81 cells, found {}", self.cells_found);
+    }
+}
+
+impl Error for ParseGridError {}
+
+// Why a string couldn't be turned into a solver: it didn't hold 81 cells, or the board it held breaks the rules
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseSolverError {
+    Parse(ParseGridError),
+    Board(BuildError)
+}
+
+impl Display for ParseSolverError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        return match self {
+            ParseSolverError::Parse(error) => write!(f, "{}", error),
+            ParseSolverError::Board(error) => write!(f, "{}", error)
+        };
+    }
+}
+
+impl Error for ParseSolverError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        return match self {
+            ParseSolverError::Parse(error) => Some(error),
+            ParseSolverError::Board(error) => Some(error)
+        };
+    }
+}
+
+// Accepts everything `parse_grid` does. Unlike `SudokuSolver::new`, a board breaking the rules is an error instead of
+// a panic, listing every conflict.
+impl TryFrom<&str> for SudokuSolver {
+    type Error = ParseSolverError;
+
+    fn try_from(text: &str) -> Result<SudokuSolver, ParseSolverError> {
+        let board = SudokuBoard::parse_grid(text).map_err(ParseSolverError::Parse)?;
+        let report = board.validate();
+        if !report.is_valid() {
+            return Err(ParseSolverError::Board(BuildError::Conflicts(report.conflicts)));
+        }
+        return Ok(SudokuSolver::new(&board));
+    }
+}
+
+impl FromStr for SudokuSolver {
+    type Err = ParseSolverError;
+
+    fn from_str(text: &str) -> Result<SudokuSolver, ParseSolverError> {
+        return SudokuSolver::try_from(text);
+    }
+}
+
+impl SudokuBoard {
+    // Forgiving parser for pasted puzzles. Only digits and the blanks `.`, `_` and `*` count as cells, every other
+    // character (borders, pipes, whitespace) is dropped before the 81 cells are read row-major.
+    pub fn parse_grid(text: &str) -> Result<SudokuBoard, ParseGridError> {
+        let mut values = Vec::with_capacity(81);
+        let (mut line, mut column) = (1, 1); // Just past the last cell
+        let mut extra_cell = None;
+        for (line_index, text_line) in text.lines().enumerate() {
+            for (column_index, character) in text_line.chars().enumerate() {
+                let value = match character {
+                    '.' | '_' | '*' => 0,
+                    _ => match character.to_digit(10) {
+                        Some(value) => value as u8,
+                        None => continue
+                    }
+                };
+                if values.len() == 81 && extra_cell.is_none() {
+                    extra_cell = Some((line_index + 1, column_index + 1));
+                }
+                values.push(value);
+                line = line_index + 1;
+                column = column_index + 2;
+            }
+        }
+
+        if values.len() != 81 {
+            let (line, column) = extra_cell.unwrap_or((line, column));
+            return Err(ParseGridError { cells_found: values.len(), line, column });
+        }
+
+        let mut puzzle = [0; 81];
+        puzzle.copy_from_slice(&values);
+        return Ok(SudokuBoard::new(&puzzle));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::classic;
+    use std::convert::TryInto;
+
+    #[test]
+    fn parse_grid_round_trip() {
+        assert_eq!(SudokuBoard::parse_grid(&classic().to_string()), Ok(classic()));
+    }
+
+    #[test]
+    fn parse_grid_forum_post() {
+        let text = "
++-------+-------+-------+
+| 5 3 . | . 7 . | . . . |
+| 6 . . | 1 9 5 | . . . |
+| . 9 8 | . . . | . 6 . |
++-------+-------+-------+
+| 8 _ _ | _ 6 _ | _ _ 3 |
+| 4 * * | 8 * 3 | * * 1 |
+| 7 0 0 | 0 2 0 | 0 0 6 |
++-------+-------+-------+
+
+| . 6 . | . . . | 2 8 . |
+| . . . | 4 1 9 | . . 5 |
+| . . . | . 8 . | . 7 9 |
++-------+-------+-------+
+";
+
+        assert_eq!(SudokuBoard::parse_grid(text), Ok(classic()));
+    }
+
+    #[test]
+    fn parse_grid_wrong_cell_count() {
+        let text: String = classic().to_array().iter().skip(2).map(|value| char::from(b'0' + value)).collect();
+
+        let error = SudokuBoard::parse_grid(&text).unwrap_err();
+
+        assert_eq!(error, ParseGridError { cells_found: 79, line: 1, column: 80 });
+        assert_eq!(error.to_string(), "Expected 81 cells, found 79");
+
+        let too_many = format!("{}\n| 1 2 |\n", classic().to_line());
+        assert_eq!(SudokuBoard::parse_grid(&too_many), Err(ParseGridError { cells_found: 83, line: 2, column: 3 }));
+    }
+
+    #[test]
+    fn solver_try_from_works() {
+        let solver: SudokuSolver = "..53.....8......2..7..1.5..4....53...1..7...6..32...8..6.5....9..4....3......97..".try_into().unwrap();
+        assert_eq!(solver.board[(0, 2)], 5);
+        assert!(solver.solve().is_solved());
+
+        let solver: SudokuSolver = classic().to_string().parse().unwrap();
+        assert_eq!(solver.board, classic());
+    }
+
+    #[test]
+    fn solver_try_from_errors() {
+        let parse_error = SudokuSolver::try_from("53..7").err().unwrap();
+        let board_error = "5".repeat(81).parse::<SudokuSolver>().err().unwrap();
+
+        assert_eq!(parse_error, ParseSolverError::Parse(ParseGridError { cells_found: 5, line: 1, column: 6 }));
+        assert!(matches!(board_error, ParseSolverError::Board(BuildError::Conflicts(ref conflicts)) if conflicts.len() == 27));
+        assert_eq!(parse_error.to_string(), "Expected 81 cells, found 5");
+    }
+}
@@ -0,0 +1,282 @@
+use crate::sudoku_board::SudokuBoard;
+
+// Which spaces constrain each other. Every consumer of peers (candidates, the solvers' used values, conflict tracking,
+// notes pruning) reads these tables instead of walking the houses itself.
+
+// The 20 spaces sharing a row, column or nonet with every space, as row-major indices in ascending order
+pub const PEERS: [[usize; 20]; 81] = peers_table();
+
+// `PEERS` as bitboards, bit `9 * row + column` set for every peer
+pub const PEER_MASKS: [u128; 81] = peer_masks_table();
+
+const fn shares_house(index: usize, other: usize) -> bool {
+    let same_nonet = 3 * (other / 27) + (other % 9) / 3 == 3 * (index / 27) + (index % 9) / 3;
+    return other / 9 == index / 9 || other % 9 == index % 9 || same_nonet;
+}
+
+const fn peers_table() -> [[usize; 20]; 81] {
+    let mut table = [[0; 20]; 81];
+    let mut index = 0;
+    while index < 81 {
+        let mut peer_count = 0;
+        let mut other = 0;
+        while other < 81 {
+            if other != index && shares_house(index, other) {
+                table[index][peer_count] = other;
+                peer_count += 1;
+            }
+            other += 1;
+        }
+        index += 1;
+    }
+    return table;
+}
+
+const fn peer_masks_table() -> [u128; 81] {
+    let peers = peers_table();
+    let mut table = [0; 81];
+    let mut index = 0;
+    while index < 81 {
+        let mut peer = 0;
+        while peer < 20 {
+            table[index] |= 1 << peers[index][peer];
+            peer += 1;
+        }
+        index += 1;
+    }
+    return table;
+}
+
+// Extra constraints a variant puts between spaces on top of the houses
+pub trait PeerRule {
+    // Bitboard of the spaces the variant makes peers of `index`, which never includes `index` itself
+    fn peers_of(&self, index: usize) -> u128;
+}
+
+// No two spaces a chess knight's move apart may hold the same value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AntiKnight;
+
+// No two diagonally or orthogonally touching spaces may hold the same value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AntiKing;
+
+const KNIGHT_MOVES: [(isize, isize); 8] = [(-2, -1), (-2, 1), (-1, -2), (-1, 2), (1, -2), (1, 2), (2, -1), (2, 1)];
+const KING_MOVES: [(isize, isize); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+
+// The spaces reached from `index` by each offset that stays on the board
+fn offsets_mask(index: usize, offsets: &[(isize, isize)]) -> u128 {
+    let (row_index, column_index) = ((index / 9) as isize, (index % 9) as isize);
+    return offsets.iter()
+        .map(|(row_offset, column_offset)| (row_index + row_offset, column_index + column_offset))
+        .filter(|(row, column)| (0..9).contains(row) && (0..9).contains(column))
+        .fold(0, |mask, (row, column)| mask | 1 << (9 * row + column));
+}
+
+impl PeerRule for AntiKnight {
+    fn peers_of(&self, index: usize) -> u128 {
+        return offsets_mask(index, &KNIGHT_MOVES);
+    }
+}
+
+impl PeerRule for AntiKing {
+    fn peers_of(&self, index: usize) -> u128 {
+        return offsets_mask(index, &KING_MOVES);
+    }
+}
+
+// Nine spaces that must hold every value once on top of the rows, columns and nonets, like the diagonals of diagonal
+// Sudoku or the windows of Windoku. As a `PeerRule` the spaces become each other's peers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtraHouse {
+    spaces: [usize; 9] // Row-major indices in ascending order
+}
+
+impl ExtraHouse {
+    pub fn new(spaces: [(usize, usize); 9]) -> ExtraHouse {
+        let mut indices = [0; 9];
+        for (index, &(row_index, column_index)) in indices.iter_mut().zip(spaces.iter()) {
+            if row_index > 8 || column_index > 8 {
+                panic!("An invalid space was passed into 'ExtraHouse::new', it was ({}, {})", row_index, column_index);
+            }
+            *index = 9 * row_index + column_index;
+        }
+        indices.sort_unstable();
+        if indices.windows(2).any(|pair| pair[0] == pair[1]) {
+            panic!("The spaces passed into 'ExtraHouse::new' must all be different");
+        }
+        return ExtraHouse { spaces: indices };
+    }
+
+    // Top left to bottom right
+    pub fn main_diagonal() -> ExtraHouse {
+        return ExtraHouse { spaces: [0, 10, 20, 30, 40, 50, 60, 70, 80] };
+    }
+
+    // Top right to bottom left
+    pub fn anti_diagonal() -> ExtraHouse {
+        return ExtraHouse { spaces: [8, 16, 24, 32, 40, 48, 56, 64, 72] };
+    }
+
+    // The four 3x3 windows of Windoku, each one space in from a corner, row-major
+    pub fn windows() -> [ExtraHouse; 4] {
+        let window = |top: usize, left: usize| {
+            let mut spaces = [0; 9];
+            for (offset, index) in spaces.iter_mut().enumerate() {
+                *index = 9 * (top + offset / 3) + left + offset % 3;
+            }
+            return ExtraHouse { spaces };
+        };
+        return [window(1, 1), window(1, 5), window(5, 1), window(5, 5)];
+    }
+
+    // Row-major
+    pub fn spaces(&self) -> Vec<(usize, usize)> {
+        return self.spaces.iter().map(|index| (index / 9, index % 9)).collect();
+    }
+
+    pub fn contains(&self, row_index: usize, column_index: usize) -> bool {
+        return self.spaces.contains(&(9 * row_index + column_index));
+    }
+
+    // The board's values in the order of `spaces`
+    pub fn values(&self, board: &SudokuBoard) -> [u8; 9] {
+        let mut values = [0; 9];
+        for (value, index) in values.iter_mut().zip(self.spaces.iter()) {
+            *value = board[(index / 9, index % 9)];
+        }
+        return values;
+    }
+}
+
+impl PeerRule for ExtraHouse {
+    fn peers_of(&self, index: usize) -> u128 {
+        if !self.spaces.contains(&index) {
+            return 0;
+        }
+        return self.spaces.iter().fold(0, |mask, space| mask | 1 << space) & !(1 << index);
+    }
+}
+
+// The standard peers with any number of variant rules folded in, built once when a variant is set up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeerTable {
+    masks: [u128; 81]
+}
+
+impl Default for PeerTable {
+    fn default() -> PeerTable {
+        return PeerTable { masks: PEER_MASKS };
+    }
+}
+
+impl PeerTable {
+    pub fn standard() -> PeerTable {
+        return PeerTable::default();
+    }
+
+    pub fn with_rule(mut self, rule: &dyn PeerRule) -> PeerTable {
+        for (index, mask) in self.masks.iter_mut().enumerate() {
+            *mask |= rule.peers_of(index) & !(1 << index);
+        }
+        return self;
+    }
+
+    pub fn mask(&self, index: usize) -> u128 {
+        if index > 80 {
+            panic!("An invalid index was passed into 'mask', it was {}", index);
+        }
+        return self.masks[index];
+    }
+
+    pub fn peer_count(&self, index: usize) -> usize {
+        return self.mask(index).count_ones() as usize;
+    }
+
+    // Row-major indices in ascending order
+    pub fn peers(&self, index: usize) -> impl Iterator<Item = usize> {
+        let mut mask = self.mask(index);
+        return std::iter::from_fn(move || {
+            if mask == 0 {
+                return None;
+            }
+            let peer = mask.trailing_zeros() as usize;
+            mask &= mask - 1;
+            return Some(peer);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn peer_masks_match_brute_force() {
+        for index in 0..81 {
+            let (row_index, column_index) = (index / 9, index % 9);
+            let expected: Vec<usize> = (0..81)
+                .filter(|other| *other != index)
+                .filter(|other| other / 9 == row_index || other % 9 == column_index || (other / 27, (other % 9) / 3) == (index / 27, column_index / 3))
+                .collect();
+
+            assert_eq!(PEERS[index].to_vec(), expected);
+            assert_eq!(PeerTable::standard().peers(index).collect::<Vec<usize>>(), expected);
+            assert_eq!(PEER_MASKS[index].count_ones(), 20);
+        }
+    }
+
+    #[test]
+    fn composed_tables_have_expected_sizes() {
+        let anti_knight = PeerTable::standard().with_rule(&AntiKnight);
+        let anti_king = PeerTable::standard().with_rule(&AntiKing);
+        let both = anti_knight.with_rule(&AntiKing);
+
+        // Every knight's move and king's move from a corner stays inside its nonet
+        assert_eq!(anti_knight.peer_count(0), 20);
+        assert_eq!(anti_king.peer_count(80), 20);
+        // From the middle of an edge, two of the four knight's moves leave the nonet
+        assert_eq!(anti_knight.peer_count(4), 22);
+        // From the center all eight do, while every king's move stays inside
+        assert_eq!(anti_knight.peer_count(40), 28);
+        assert_eq!(anti_king.peer_count(40), 20);
+        // A nonet's corner touches three spaces of the neighbouring nonets outside its own row and column
+        assert_eq!(anti_king.peer_count(20), 23);
+        assert_eq!(both.peer_count(40), 28);
+
+        for index in 0..81 {
+            assert_eq!(anti_knight.mask(index) & 1 << index, 0);
+            assert_eq!(both.mask(index), anti_knight.mask(index) | anti_king.mask(index));
+            assert!(anti_knight.peers(index).all(|peer| anti_knight.mask(peer) & 1 << index != 0)); // Peers are mutual
+        }
+    }
+
+    #[test]
+    fn extra_houses_work() {
+        let diagonal = PeerTable::standard().with_rule(&ExtraHouse::main_diagonal()).with_rule(&ExtraHouse::anti_diagonal());
+
+        // The center is on both diagonals, two spaces of each are already in its nonet
+        assert_eq!(diagonal.peer_count(40), 32);
+        assert_eq!(diagonal.peer_count(0), 26);
+        assert_eq!(diagonal.peer_count(1), 20);
+        assert_eq!(ExtraHouse::main_diagonal(), ExtraHouse::new([(8, 8), (7, 7), (6, 6), (5, 5), (4, 4), (3, 3), (2, 2), (1, 1), (0, 0)]));
+        assert_eq!(ExtraHouse::anti_diagonal().spaces(), (0..9).map(|row_index| (row_index, 8 - row_index)).collect::<Vec<(usize, usize)>>());
+
+        let windows = ExtraHouse::windows();
+        assert_eq!(windows[3].spaces()[0], (5, 5));
+        assert!(windows[0].contains(3, 3) && !windows[0].contains(4, 4));
+        // The windows cover 36 spaces without overlapping
+        assert_eq!((0..81).filter(|index| windows.iter().any(|window| window.contains(index / 9, index % 9))).count(), 36);
+
+        let mut values = [0; 81];
+        values[40] = 5;
+        values[80] = 9;
+        assert_eq!(ExtraHouse::main_diagonal().values(&SudokuBoard::new(&values)), [0, 0, 0, 0, 5, 0, 0, 0, 9]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn extra_house_repeated_space() {
+        ExtraHouse::new([(0, 0), (0, 1), (0, 2), (0, 3), (0, 4), (0, 5), (0, 6), (0, 7), (0, 0)]);
+    }
+}
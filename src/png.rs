@@ -0,0 +1,224 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{ self, Display, Formatter };
+use crate::sudoku_board::SudokuBoard;
+
+// Digits come from a tiny bitmap font, the `png` crate handles filtering and compression
+
+pub const MIN_CELL_SIZE: u32 = 10;
+pub const MAX_CELL_SIZE: u32 = 128; // About 1150 pixels square, plenty for a chat message or social card
+
+const BACKGROUND: [u8; 3] = [255, 255, 255];
+const HIGHLIGHT: [u8; 3] = [255, 200, 200];
+const THIN_LINE: [u8; 3] = [128, 128, 128];
+const THICK_LINE: [u8; 3] = [0, 0, 0];
+const GIVEN: [u8; 3] = [0, 0, 0];
+const ENTERED: [u8; 3] = [30, 80, 200];
+
+// 3x5 glyphs for [1..9], one row per entry with the most significant of the three bits on the left
+const DIGIT_GLYPHS: [[u8; 5]; 9] = [
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b010, 0b010, 0b010],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111]
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderError {
+    CellSizeOutOfRange(u32)
+}
+
+impl Display for RenderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RenderError::CellSizeOutOfRange(cell_size) => write!(f, "The cell size must be [{}..{}] inclusive, it was {}", MIN_CELL_SIZE, MAX_CELL_SIZE, cell_size)
+        }
+    }
+}
+
+impl Error for RenderError {}
+
+pub struct PngOptions<'a> {
+    pub cell_size: u32,
+    pub givens: Option<&'a SudokuBoard>, // The starting puzzle, when absent every filled space is drawn as a given
+    pub highlights: HashSet<(usize, usize)>
+}
+
+impl<'a> Default for PngOptions<'a> {
+    fn default() -> PngOptions<'a> {
+        return PngOptions {
+            cell_size: 50,
+            givens: None,
+            highlights: HashSet::new()
+        }
+    }
+}
+
+struct Canvas {
+    size: u32,
+    pixels: Vec<u8>
+}
+
+impl Canvas {
+    fn fill(&mut self, x: u32, y: u32, width: u32, height: u32, color: [u8; 3]) {
+        for pixel_y in y..(y + height).min(self.size) {
+            for pixel_x in x..(x + width).min(self.size) {
+                let offset = 3 * (pixel_y * self.size + pixel_x) as usize;
+                self.pixels[offset..offset + 3].copy_from_slice(&color);
+            }
+        }
+    }
+}
+
+impl SudokuBoard {
+    pub fn render_png(&self, options: &PngOptions) -> Result<Vec<u8>, RenderError> {
+        let cell_size = options.cell_size;
+        if !(MIN_CELL_SIZE..=MAX_CELL_SIZE).contains(&cell_size) {
+            return Err(RenderError::CellSizeOutOfRange(cell_size));
+        }
+
+        let size = 9 * cell_size + 1; // The extra pixel holds the closing right and bottom lines
+        let mut canvas = Canvas { size, pixels: BACKGROUND.repeat((size * size) as usize) };
+        let thick_width = (cell_size / 16).max(2);
+        let glyph_scale = cell_size / 10;
+
+        for &(row, column) in options.highlights.iter().filter(|&&(row, column)| row <= 8 && column <= 8) {
+            canvas.fill(column as u32 * cell_size, row as u32 * cell_size, cell_size, cell_size, HIGHLIGHT);
+        }
+
+        for line in 0..=9 {
+            canvas.fill(line * cell_size, 0, 1, size, THIN_LINE);
+            canvas.fill(0, line * cell_size, size, 1, THIN_LINE);
+        }
+        for line in 0..=3 {
+            // Thick lines grow inwards at the outer border so they stay inside the image
+            let start = (line * 3 * cell_size).saturating_sub(thick_width / 2).min(size - thick_width);
+            canvas.fill(start, 0, thick_width, size, THICK_LINE);
+            canvas.fill(0, start, size, thick_width, THICK_LINE);
+        }
+
+        for row in 0..=8 {
+            for column in 0..=8 {
+                let value = self[(row, column)];
+                if value == 0 {
+                    continue;
+                }
+
+                let is_given = options.givens.map_or(true, |givens| givens[(row, column)] != 0);
+                let glyph_x = column as u32 * cell_size + (cell_size - 3 * glyph_scale) / 2;
+                let glyph_y = row as u32 * cell_size + (cell_size - 5 * glyph_scale) / 2;
+                for (glyph_row, bits) in DIGIT_GLYPHS[value as usize - 1].iter().enumerate() {
+                    for glyph_column in 0..3 {
+                        if bits & (0b100 >> glyph_column) == 0 {
+                            continue;
+                        }
+
+                        let x = glyph_x + glyph_column * glyph_scale;
+                        let y = glyph_y + glyph_row as u32 * glyph_scale;
+                        if is_given { // Bold is emulated by smearing each stroke a little to the right
+                            canvas.fill(x, y, glyph_scale + (glyph_scale / 3).max(1), glyph_scale, GIVEN);
+                        }
+                        else {
+                            canvas.fill(x, y, glyph_scale, glyph_scale, ENTERED);
+                        }
+                    }
+                }
+            }
+        }
+
+        return Ok(encode_png(size, &canvas.pixels));
+    }
+}
+
+fn encode_png(size: u32, pixels: &[u8]) -> Vec<u8> {
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, size, size);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_compression(png::Compression::Best); // Boards are mostly flat color, so this shrinks them a lot
+
+    // The header is always valid and the pixels always fill the image, so only the `Vec` could fail and it can't
+    let mut writer = encoder.write_header().expect("Writing a PNG into memory can't fail");
+    writer.write_image_data(pixels).expect("Writing a PNG into memory can't fail");
+    writer.finish().expect("Writing a PNG into memory can't fail");
+    return png;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::easy;
+
+    fn decode(png: &[u8]) -> (u32, u32, Vec<u8>) {
+        let mut reader = png::Decoder::new(png).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.color_type, info.bit_depth), (png::ColorType::Rgb, png::BitDepth::Eight));
+
+        pixels.truncate(info.buffer_size());
+        return (info.width, info.height, pixels);
+    }
+
+    fn pixel(pixels: &[u8], width: u32, x: u32, y: u32) -> [u8; 3] {
+        let offset = 3 * (y * width + x) as usize;
+        return [pixels[offset], pixels[offset + 1], pixels[offset + 2]];
+    }
+
+    #[test]
+    fn render_png_dimensions_work() {
+        let board = easy();
+
+        for cell_size in [10, 32, 50] {
+            let png = board.render_png(&PngOptions { cell_size, ..PngOptions::default() }).unwrap();
+            let (width, height, pixels) = decode(&png);
+
+            assert_eq!(width, 9 * cell_size + 1);
+            assert_eq!(height, 9 * cell_size + 1);
+            assert_eq!(pixels.len(), (3 * width * height) as usize);
+        }
+    }
+
+    #[test]
+    fn render_png_grid_lines_work() {
+        let givens = easy();
+        let mut board = easy();
+        board.set_value(0, 0, 6);
+        let mut highlights = HashSet::new();
+        highlights.insert((2, 4));
+
+        let png = board.render_png(&PngOptions { cell_size: 40, givens: Some(&givens), highlights }).unwrap();
+        let (width, _, pixels) = decode(&png);
+
+        assert_eq!(pixel(&pixels, width, 0, 200), THICK_LINE); // Outer border
+        assert_eq!(pixel(&pixels, width, 360, 200), THICK_LINE);
+        assert_eq!(pixel(&pixels, width, 120, 5), THICK_LINE); // Nonet border
+        assert_eq!(pixel(&pixels, width, 200, 240), THICK_LINE);
+        assert_eq!(pixel(&pixels, width, 40, 100), THIN_LINE); // Cell border
+        assert_eq!(pixel(&pixels, width, 250, 160), THIN_LINE);
+        assert_eq!(pixel(&pixels, width, 165, 85), HIGHLIGHT); // Corner of the highlighted space
+        assert_eq!(pixel(&pixels, width, 5, 5), BACKGROUND);
+        assert!(pixels.chunks(3).any(|color| color == ENTERED));
+        assert!(pixels.chunks(3).any(|color| color == GIVEN));
+    }
+
+    #[test]
+    fn render_png_invalid_cell_size() {
+        let board = easy();
+
+        assert_eq!(board.render_png(&PngOptions { cell_size: 9, ..PngOptions::default() }), Err(RenderError::CellSizeOutOfRange(9)));
+        assert_eq!(board.render_png(&PngOptions { cell_size: 129, ..PngOptions::default() }), Err(RenderError::CellSizeOutOfRange(129)));
+    }
+
+    #[test]
+    fn render_png_is_compressed() {
+        let board = easy();
+
+        assert!(board.render_png(&PngOptions::default()).unwrap().len() < 20_000);
+        assert!(board.render_png(&PngOptions { cell_size: MAX_CELL_SIZE, ..PngOptions::default() }).unwrap().len() < 100_000);
+    }
+}
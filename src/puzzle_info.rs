@@ -0,0 +1,152 @@
+use std::fmt::{ self, Display, Formatter };
+use crate::rating::Difficulty;
+use crate::sudoku_board::House;
+use crate::sudoku_solver::SudokuSolver;
+use crate::symmetry::Symmetry;
+use crate::techniques::all_houses;
+
+// Structural statistics of a puzzle's starting position, everything an index keeps per puzzle in one place
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PuzzleInfo {
+    pub clues: usize,
+    pub digit_counts: [u8; 9], // How often each digit is given, `digit_counts[0]` is for 1
+    pub empty_houses: Vec<House>, // Houses without a single given, rows first, then columns, then nonets
+    pub symmetry: Vec<Symmetry>, // Every symmetry the positions of the givens have, ignoring their values
+    pub solution_count: usize, // Counted up to 2, so 2 means at least 2
+    pub difficulty: Option<Difficulty>, // Only graded when the solution is unique
+    pub key: String, // `SudokuBoard::to_line` of the givens
+    pub fingerprint: String // `SudokuBoard::fingerprint` of the givens
+}
+
+impl PuzzleInfo {
+    pub fn is_unique(&self) -> bool {
+        return self.solution_count == 1;
+    }
+}
+
+impl From<&SudokuSolver> for PuzzleInfo {
+    fn from(solver: &SudokuSolver) -> PuzzleInfo {
+        let board = &solver.board;
+        let mut digit_counts = [0; 9];
+        digit_counts.copy_from_slice(&board.digit_counts()[1..]);
+
+        let solution_count = solver.count_solutions(2);
+
+        return PuzzleInfo {
+            clues: 81 - board.unsolved_count(),
+            digit_counts,
+            empty_houses: all_houses().filter(|house| house.spaces().iter().all(|&space| board[space] == 0)).collect(),
+            symmetry: board.givens_symmetry(),
+            solution_count,
+            difficulty: if solution_count == 1 { Some(solver.rate().difficulty) } else { None },
+            key: board.to_line(),
+            fingerprint: board.fingerprint()
+        };
+    }
+}
+
+impl Display for PuzzleInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let digit_counts: Vec<String> = self.digit_counts.iter().enumerate().map(|(index, count)| format!("{}:{}", index + 1, count)).collect();
+        let empty_houses: Vec<String> = self.empty_houses.iter().map(House::label).collect();
+        let symmetry: Vec<String> = self.symmetry.iter().map(|symmetry| format!("{:?}", symmetry)).collect();
+        let or_none = |values: Vec<String>| if values.is_empty() { String::from("none") } else { values.join(" ") };
+
+        writeln!(f, "Clues:        {}", self.clues)?;
+        writeln!(f, "Digits:       {}", digit_counts.join(" "))?;
+        writeln!(f, "Empty houses: {}", or_none(empty_houses))?;
+        writeln!(f, "Symmetry:     {}", or_none(symmetry))?;
+        writeln!(f, "Solutions:    {}", if self.solution_count >= 2 { String::from("2+") } else { self.solution_count.to_string() })?;
+        writeln!(f, "Difficulty:   {}", self.difficulty.map_or(String::from("-"), |difficulty| difficulty.to_string()))?;
+        writeln!(f, "Key:          {}", self.key)?;
+        write!(f, "Fingerprint:  {}", self.fingerprint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{ easy, medium };
+    use crate::sudoku_board::SudokuBoard;
+
+    fn hard() -> SudokuSolver {
+        return SudokuSolver::new(&SudokuBoard::new(&[
+            9,0,0, 0,1,0, 8,0,0,
+            0,0,7, 0,5,0, 0,0,6,
+            3,0,0, 0,0,0, 0,0,7,
+            6,4,0, 5,0,0, 0,7,0,
+            0,5,8, 0,0,0, 9,0,0,
+            0,0,0, 0,7,4, 0,0,5,
+            0,0,0, 0,0,0, 4,0,0,
+            0,3,0, 0,0,9, 5,0,0,
+            0,0,0, 2,8,6, 0,0,0
+        ]));
+    }
+
+    #[test]
+    fn from_solver_works() {
+        let easy = PuzzleInfo::from(&SudokuSolver::new(&easy()));
+        let medium = PuzzleInfo::from(&SudokuSolver::new(&medium()));
+        let hard = PuzzleInfo::from(&hard());
+
+        assert_eq!((easy.clues, medium.clues, hard.clues), (76, 38, 25));
+        assert_eq!(easy.digit_counts, [7, 9, 9, 9, 9, 7, 9, 8, 9]);
+        assert_eq!(hard.digit_counts.iter().map(|count| *count as usize).sum::<usize>(), hard.clues);
+        assert!(easy.empty_houses.is_empty() && hard.empty_houses.is_empty());
+        assert!(easy.is_unique() && medium.is_unique() && hard.is_unique());
+        assert!(easy.difficulty <= medium.difficulty);
+        assert!(medium.difficulty < hard.difficulty);
+        assert_eq!(hard.difficulty, Some(Difficulty::Hard));
+        assert_eq!(hard.key, "900010800007050006300000007640500070058000900000074005000000400030009500000286000");
+    }
+
+    #[test]
+    fn from_solver_empty_board() {
+        let empty = PuzzleInfo::from(&SudokuSolver::new(&SudokuBoard::new(&[0; 81])));
+
+        assert_eq!(empty.solution_count, 2);
+        assert_eq!(empty.difficulty, None);
+        assert_eq!(empty.empty_houses.len(), 27);
+        assert_eq!(empty.symmetry, Symmetry::ALL.to_vec());
+
+        let mut board = medium();
+        for column_index in 0..=8 {
+            board.clear_value(0, column_index);
+        }
+        let info = PuzzleInfo::from(&SudokuSolver::new(&board));
+        assert_eq!(info.empty_houses, vec![House::Row(0)]);
+        assert_eq!(info.clues, 33);
+    }
+
+    #[test]
+    fn display_works() {
+        let info = PuzzleInfo::from(&hard());
+
+        assert_eq!(info.to_string(), concat!(
+            "Clues:        25\n",
+            "Digits:       1:1 2:1 3:2 4:3 5:5 6:3 7:4 8:3 9:3\n",
+            "Empty houses: none\n",
+            "Symmetry:     none\n",
+            "Solutions:    1\n",
+            "Difficulty:   Hard\n",
+            "Key:          900010800007050006300000007640500070058000900000074005000000400030009500000286000\n",
+            "Fingerprint:  NO23ZZFIHI5IU"
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let info = PuzzleInfo::from(&SudokuSolver::new(&easy()));
+        let json = serde_json::to_string(&info).unwrap();
+
+        assert_eq!(json, "{\"clues\":76,\"digit_counts\":[7,9,9,9,9,7,9,8,9],\"empty_houses\":[],\"symmetry\":[],\"solution_count\":1,\"difficulty\":\"easy\",\"key\":\"073894512912735486845002973798261354526473891134589267469028735287356149351947620\",\"fingerprint\":\"KPIDIAW6V7KCG\"}");
+        assert_eq!(serde_json::from_str::<PuzzleInfo>(&json).unwrap(), info);
+        assert!(serde_json::from_str::<PuzzleInfo>(&json.replace("easy", "trivial")).is_err());
+
+        let hard_info = serde_json::to_value(PuzzleInfo::from(&hard())).unwrap();
+        assert_eq!(hard_info["difficulty"], "hard");
+        assert_eq!(hard_info["symmetry"], serde_json::json!([]));
+    }
+}
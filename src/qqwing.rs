@@ -0,0 +1,101 @@
+use crate::rating::RatingReport;
+use crate::sudoku_board::SudokuBoard;
+use crate::sudoku_solver::{ SolveStats, SudokuSolver };
+use crate::techniques::Technique;
+
+// QQWing's `--csv` layout with `--stats`, `--puzzle` and `--solution` on. Where the two don't line up:
+// - Naked Pairs, Hidden Pairs and Box/Line Intersections aren't techniques here and are always 0
+// - Locked candidates placements count as Pointing Pairs/Triples, QQWing counts eliminations there instead
+// - Guesses and Backtracks come from the search that finishes a puzzle once logic stalls. It places a value in every
+//   space it visits, so Guesses counts every placement, where QQWing only counts the branches it had to pick between.
+// - Difficulty is Simple for naked singles only, Easy with hidden singles, Intermediate with locked candidates and
+//   Expert when guessing, like QQWing's grades, and Unknown for boards without a solution, which QQWing never prints
+pub const QQWING_CSV_HEADER: &str = "Puzzle Count,Puzzle,Solution,Givens,Singles,Hidden Singles,Naked Pairs,Hidden Pairs,Pointing Pairs/Triples,Box/Line Intersections,Guesses,Backtracks,Difficulty";
+
+// The row for a board rated as `report`, `count` is QQWing's 1-based puzzle count. Boards without a solution get an
+// empty Solution and zeros.
+pub fn qqwing_csv_row(count: usize, board: &SudokuBoard, report: Option<&RatingReport>) -> String {
+    let puzzle = board.to_line().replace('0', ".");
+    let givens = 81 - board.unsolved_count();
+    let report = match report {
+        Some(report) => report,
+        None => return format!("{},{},,{},0,0,0,0,0,0,0,0,Unknown", count, puzzle, givens)
+    };
+
+    let mut partial = SudokuBoard::copy(board);
+    for step in report.steps.iter() {
+        partial.set_value(step.row, step.column, step.value);
+    }
+    let (solution, stats) = match SudokuSolver::new(board).solve_from_with_stats(&partial) {
+        Ok(solved) => solved,
+        Err(_) => return format!("{},{},,{},0,0,0,0,0,0,0,0,Unknown", count, puzzle, givens)
+    };
+    let SolveStats { searched_cells, iterations, .. } = stats;
+
+    let technique_count = |technique| report.technique_counts.get(&technique).copied().unwrap_or(0);
+    let difficulty = if report.guessing_required {
+        "Expert"
+    } else if technique_count(Technique::LockedCandidates) > 0 {
+        "Intermediate"
+    } else if technique_count(Technique::HiddenSingle) > 0 {
+        "Easy"
+    } else {
+        "Simple"
+    };
+    return format!(
+        "{},{},{},{},{},{},0,0,{},0,{},{},{}",
+        count,
+        puzzle,
+        solution.to_line(),
+        givens,
+        technique_count(Technique::NakedSingle),
+        technique_count(Technique::HiddenSingle),
+        technique_count(Technique::LockedCandidates),
+        iterations,
+        iterations - searched_cells as u64,
+        difficulty
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::medium;
+
+    #[test]
+    fn qqwing_csv_rows_are_pinned() {
+        let medium = medium();
+        let report = SudokuSolver::new(&medium).rate();
+
+        assert_eq!(QQWING_CSV_HEADER.split(',').count(), 13);
+        assert_eq!(qqwing_csv_row(1, &medium, Some(&report)), "1,78.4..12.6...75..9...6.1.78..7.4.26...1.5.93.9.4.6...5.7.3...1212...74...492.6..7,785439126612875349493621578857943261261758934934162785578394612126587493349216857,38,43,0,0,0,0,0,0,0,Simple");
+    }
+
+    #[test]
+    fn qqwing_csv_row_guessing() {
+        // AI Escargot
+        let board = SudokuBoard::new(&[
+            1,0,0, 0,0,7, 0,9,0,
+            0,3,0, 0,2,0, 0,0,8,
+            0,0,9, 6,0,0, 5,0,0,
+            0,0,5, 3,0,0, 9,0,0,
+            0,1,0, 0,8,0, 0,0,2,
+            6,0,0, 0,0,4, 0,0,0,
+            3,0,0, 0,0,0, 0,1,0,
+            0,4,0, 0,0,0, 0,0,7,
+            0,0,7, 0,0,0, 3,0,0
+        ]);
+        let row = qqwing_csv_row(2, &board, Some(&SudokuSolver::new(&board).rate()));
+        let fields: Vec<&str> = row.split(',').collect();
+
+        assert_eq!(fields.len(), 13);
+        assert_eq!(fields[12], "Expert");
+        assert_eq!(&fields[6..8], &["0", "0"]); // Unsupported columns are zeros, not left out
+        assert!(fields[10].parse::<u64>().unwrap() > fields[11].parse::<u64>().unwrap());
+
+        let mut unsolvable = [0; 81];
+        unsolvable[..8].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        unsolvable[17] = 9;
+        assert_eq!(qqwing_csv_row(3, &SudokuBoard::new(&unsolvable), None), "3,12345678.........9...............................................................,,9,0,0,0,0,0,0,0,0,Unknown");
+    }
+}
@@ -0,0 +1,112 @@
+// Where every randomized API gets its randomness. Implement `next_u64` to plug in any generator, e.g. a cryptographic
+// one for public puzzles or a recording one for replay tests. With the `rand` feature every `rand::RngCore` already is
+// one. `SplitMix64` is the default behind the `u64` seed entry points.
+pub trait RandomSource {
+    fn next_u64(&mut self) -> u64;
+
+    // Uniform in [0..bound), `bound` must not be 0. Rejection sampling keeps it free of modulo bias.
+    fn below(&mut self, bound: u64) -> u64 {
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return value % bound;
+            }
+        }
+    }
+
+    // Fisher-Yates
+    fn shuffle<T>(&mut self, items: &mut [T]) where Self: Sized {
+        for index in (1..items.len()).rev() {
+            let other = self.below(index as u64 + 1) as usize;
+            items.swap(index, other);
+        }
+    }
+}
+
+// SplitMix64 (Steele, Lea & Flood), small and fully specified so seeded output is the same on every platform
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitMix64 {
+    state: u64
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> SplitMix64 {
+        return SplitMix64 { state: seed };
+    }
+}
+
+impl RandomSource for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut value = self.state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        return value ^ (value >> 31);
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<R: rand_core::RngCore> RandomSource for R {
+    fn next_u64(&mut self) -> u64 {
+        return rand_core::RngCore::next_u64(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_mix_64_reference_values() {
+        // First outputs for seed 0 from the reference implementation
+        let mut random = SplitMix64::new(0);
+
+        assert_eq!(random.next_u64(), 0xE220_A839_7B1D_CDAF);
+        assert_eq!(random.next_u64(), 0x6E78_9E6A_A1B9_65F4);
+        assert_eq!(random.next_u64(), 0x06C4_5D18_8009_454F);
+    }
+
+    #[test]
+    fn shuffle_is_a_permutation() {
+        let mut values = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        SplitMix64::new(42).shuffle(&mut values);
+        let mut sorted = values;
+        sorted.sort();
+
+        assert_eq!(sorted, [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert!((0..1000).all(|seed| SplitMix64::new(seed).below(9) < 9));
+    }
+
+    // Hands out `SplitMix64`'s values through `rand_core`, so it has to reproduce the seeded output exactly
+    #[cfg(feature = "rand")]
+    struct RandSplitMix64(SplitMix64);
+
+    #[cfg(feature = "rand")]
+    impl rand_core::RngCore for RandSplitMix64 {
+        fn next_u32(&mut self) -> u32 {
+            return RandomSource::next_u64(&mut self.0) as u32;
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            return RandomSource::next_u64(&mut self.0);
+        }
+
+        fn fill_bytes(&mut self, destination: &mut [u8]) {
+            for chunk in destination.chunks_mut(8) {
+                chunk.copy_from_slice(&RandomSource::next_u64(&mut self.0).to_le_bytes()[..chunk.len()]);
+            }
+        }
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn rand_rngs_are_random_sources() {
+        use crate::generator::{ generate_with_rng, GeneratorOptions };
+
+        let seeded = generate_with_rng(&GeneratorOptions::new(), &mut SplitMix64::new(2024)).unwrap();
+        let through_rand = generate_with_rng(&GeneratorOptions::new(), &mut RandSplitMix64(SplitMix64::new(2024))).unwrap();
+
+        assert_eq!(through_rand, seeded);
+    }
+}
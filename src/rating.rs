@@ -0,0 +1,552 @@
+use std::collections::BTreeMap;
+use std::fmt::{ self, Display, Formatter };
+#[cfg(feature = "parallel")]
+use std::io::{ self, Write };
+#[cfg(feature = "parallel")]
+use std::sync::atomic::{ AtomicUsize, Ordering };
+use std::time::Duration;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use crate::peers::PEERS;
+#[cfg(feature = "parallel")]
+use crate::qqwing::{ qqwing_csv_row, QQWING_CSV_HEADER };
+use crate::sudoku_board::SudokuBoard;
+#[cfg(feature = "parallel")]
+use crate::sudoku_solver::{ SearchScratch, SolveError };
+use crate::sudoku_solver::{ used_values, SudokuSolver };
+use crate::techniques::{ logical_solve, Hint, Technique, TechniqueSet };
+use crate::uniqueness::HOUSES;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "snake_case"))]
+pub enum Difficulty {
+    Easy, // Naked singles only
+    Medium, // Needs hidden singles or locked candidates
+    Hard // Logic stalls and the solution needs guessing
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        return match self {
+            Difficulty::Easy => write!(f, "Easy"),
+            Difficulty::Medium => write!(f, "Medium"),
+            Difficulty::Hard => write!(f, "Hard")
+        };
+    }
+}
+
+impl Difficulty {
+    // The name in JSON, which stays put when the `Display` wording changes
+    pub fn tag(&self) -> &'static str {
+        return match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard"
+        };
+    }
+
+    // The inverse of `tag`
+    pub fn from_tag(tag: &str) -> Option<Difficulty> {
+        return [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard].iter().copied().find(|difficulty| difficulty.tag() == tag);
+    }
+}
+
+impl Display for Technique {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        return match self {
+            Technique::NakedSingle => write!(f, "Naked single"),
+            Technique::HiddenSingle => write!(f, "Hidden single"),
+            Technique::LockedCandidates => write!(f, "Locked candidates")
+        };
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RatingReport {
+    pub difficulty: Difficulty,
+    pub steps: Vec<Hint>, // The logical placements in the order they were made, always taking the easiest available
+    pub technique_counts: BTreeMap<Technique, usize>,
+    pub guessing_required: bool,
+    pub singles_solvable_cells: usize // Spaces filled before logic stalled
+}
+
+impl Display for RatingReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Difficulty: {}", self.difficulty)?;
+        writeln!(f, "Logical steps: {}", self.steps.len())?;
+        for (technique, count) in self.technique_counts.iter() {
+            writeln!(f, "  {}: {}", technique, count)?;
+        }
+        writeln!(f, "Cells solvable by singles: {}", self.singles_solvable_cells)?;
+        write!(f, "Guessing required: {}", if self.guessing_required { "yes" } else { "no" })
+    }
+}
+
+// Seconds a player takes for each part of a solve, to calibrate against timings of real players
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SolverProfile {
+    pub per_cell: f64, // Every logical placement, on top of finding it with its technique
+    pub naked_single: f64,
+    pub hidden_single: f64,
+    pub locked_candidates: f64,
+    pub guessing: f64 // Once for a puzzle whose logic stalls, however many spaces are left
+}
+
+impl Default for SolverProfile {
+    fn default() -> SolverProfile {
+        return SolverProfile::average();
+    }
+}
+
+impl SolverProfile {
+    pub fn beginner() -> SolverProfile {
+        return SolverProfile { per_cell: 8.0, naked_single: 20.0, hidden_single: 35.0, locked_candidates: 90.0, guessing: 1200.0 };
+    }
+
+    pub fn average() -> SolverProfile {
+        return SolverProfile { per_cell: 4.0, naked_single: 8.0, hidden_single: 15.0, locked_candidates: 40.0, guessing: 600.0 };
+    }
+
+    pub fn expert() -> SolverProfile {
+        return SolverProfile { per_cell: 2.0, naked_single: 3.0, hidden_single: 5.0, locked_candidates: 15.0, guessing: 240.0 };
+    }
+
+    pub fn technique_seconds(&self, technique: Technique) -> f64 {
+        return match technique {
+            Technique::NakedSingle => self.naked_single,
+            Technique::HiddenSingle => self.hidden_single,
+            Technique::LockedCandidates => self.locked_candidates
+        };
+    }
+}
+
+impl RatingReport {
+    // Every step costs `per_cell` plus its technique's time, and a stalled solve adds `guessing`. Negative totals from a
+    // badly calibrated profile come out as zero.
+    pub fn estimated_solve_time(&self, profile: &SolverProfile) -> Duration {
+        let steps: f64 = self.technique_counts.iter().map(|(technique, count)| *count as f64 * (profile.per_cell + profile.technique_seconds(*technique))).sum();
+        let guessing = if self.guessing_required { profile.guessing } else { 0.0 };
+        return Duration::from_secs_f64((steps + guessing).max(0.0));
+    }
+}
+
+impl SudokuSolver {
+    pub fn rate(&self) -> RatingReport {
+        let (steps, board) = logical_solve(&self.board, &TechniqueSet::all());
+        let mut technique_counts = BTreeMap::new();
+        for step in steps.iter() {
+            *technique_counts.entry(step.technique).or_insert(0) += 1;
+        }
+
+        let guessing_required = !board.all_spaces_solved();
+        let difficulty = if guessing_required {
+            Difficulty::Hard
+        }
+        else if technique_counts.contains_key(&Technique::HiddenSingle) || technique_counts.contains_key(&Technique::LockedCandidates) {
+            Difficulty::Medium
+        }
+        else {
+            Difficulty::Easy
+        };
+
+        return RatingReport {
+            difficulty,
+            singles_solvable_cells: steps.len(),
+            steps,
+            technique_counts,
+            guessing_required
+        };
+    }
+
+    // The fewest nested guesses the puzzle needs when only singles are allowed between them: 0 when singles alone
+    // solve it, 1 when every candidate that has to go can be refuted by guessing it and following singles to a
+    // contradiction, 2 when those refutations themselves need a guess, and so on. Depths are tried in increasing order
+    // and none past `max_depth`, which returns `None` instead, as do boards breaking the rules or without a unique
+    // solution. Every extra level multiplies the work by up to 729 guesses, so keep `max_depth` small.
+    pub fn guess_depth(&self, max_depth: usize) -> Option<usize> {
+        if !self.board.all_spaces_valid() {
+            return None;
+        }
+
+        let marks = Pencilmarks::new(&self.board);
+        for depth in 0..=max_depth {
+            let mut reduced = marks;
+            reduced.reduce(depth).ok()?;
+            if reduced.empties == 0 {
+                return Some(depth);
+            }
+        }
+        return None;
+    }
+}
+
+// Candidates of every empty space, with placing a value removing it from the space's peers
+#[derive(Clone, Copy)]
+struct Pencilmarks {
+    values: [u8; 81],
+    candidates: [u16; 81], // Bit `n` is set when `n` is a candidate, 0 for filled spaces
+    empties: u128
+}
+
+// Returned when the pencilmarks contradict themselves, a space or a house with no place left for some value
+struct Contradiction;
+
+impl Pencilmarks {
+    fn new(board: &SudokuBoard) -> Pencilmarks {
+        let mut marks = Pencilmarks { values: board.to_array(), candidates: [0; 81], empties: 0 };
+        for index in 0..81 {
+            if marks.values[index] == 0 {
+                marks.candidates[index] = 0b11_1111_1110 & !used_values(board, index / 9, index % 9);
+                marks.empties |= 1 << index;
+            }
+        }
+        return marks;
+    }
+
+    fn place(&mut self, index: usize, value: u8) {
+        self.values[index] = value;
+        self.candidates[index] = 0;
+        self.empties &= !(1 << index);
+        for peer in PEERS[index].iter() {
+            self.candidates[*peer] &= !(1 << value);
+        }
+    }
+
+    // Places naked and hidden singles until neither is left
+    fn propagate(&mut self) -> Result<(), Contradiction> {
+        let mut progressed = true;
+        while progressed {
+            progressed = false;
+            for index in 0..81 {
+                if self.empties & 1 << index == 0 {
+                    continue;
+                }
+                match self.candidates[index].count_ones() {
+                    0 => return Err(Contradiction),
+                    1 => {
+                        self.place(index, self.candidates[index].trailing_zeros() as u8);
+                        progressed = true;
+                    },
+                    _ => {}
+                }
+            }
+
+            for house in HOUSES.iter() {
+                for value in 1..=9 {
+                    if house.iter().any(|index| self.values[*index] == value) {
+                        continue;
+                    }
+                    let mut spaces = house.iter().filter(|index| self.candidates[**index] & 1 << value != 0);
+                    match (spaces.next(), spaces.next()) {
+                        (None, _) => return Err(Contradiction),
+                        (Some(index), None) => {
+                            self.place(*index, value);
+                            progressed = true;
+                        },
+                        _ => {}
+                    }
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    // Propagates, then removes every candidate that guessing refutes within `depth - 1` further guesses, until the
+    // board is solved or nothing more can be removed
+    fn reduce(&mut self, depth: usize) -> Result<(), Contradiction> {
+        loop {
+            self.propagate()?;
+            if depth == 0 || self.empties == 0 {
+                return Ok(());
+            }
+
+            let mut eliminated = false;
+            for index in 0..81 {
+                for value in 1..=9 {
+                    if self.candidates[index] & 1 << value == 0 {
+                        continue;
+                    }
+
+                    let mut guess = *self;
+                    guess.place(index, value);
+                    if guess.reduce(depth - 1).is_err() {
+                        self.candidates[index] &= !(1 << value);
+                        self.propagate()?;
+                        eliminated = true;
+                    }
+                }
+            }
+            if !eliminated {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(feature = "parallel")]
+fn rate_one(board: &SudokuBoard, scratch: &mut SearchScratch) -> Result<RatingReport, SolveError> {
+    if !board.all_spaces_valid() {
+        return Err(SolveError::InvalidBoard);
+    }
+    let solver = SudokuSolver::new_unchecked(board);
+    solver.solve_with_scratch(scratch)?;
+    return Ok(solver.rate());
+}
+
+// Rates every board across all cores, results in the same order as `boards`. Boards breaking the rules or without a
+// solution get the error instead of a report.
+#[cfg(feature = "parallel")]
+pub fn rate_batch(boards: &[SudokuBoard]) -> Vec<Result<RatingReport, SolveError>> {
+    return rate_batch_with_progress(boards, 0, |_| {});
+}
+
+// Like `rate_batch`, calling `progress` with the number of boards rated so far after every `every` of them. `progress`
+// runs on rayon's worker threads, an `every` of 0 never calls it. Each worker reuses one search scratch.
+#[cfg(feature = "parallel")]
+pub fn rate_batch_with_progress<F: Fn(usize) + Sync>(boards: &[SudokuBoard], every: usize, progress: F) -> Vec<Result<RatingReport, SolveError>> {
+    let finished = AtomicUsize::new(0);
+    return boards.par_iter().map_init(SearchScratch::new, |scratch, board| {
+        let report = rate_one(board, scratch);
+        let finished_count = finished.fetch_add(1, Ordering::Relaxed) + 1;
+        if every != 0 && finished_count % every == 0 {
+            progress(finished_count);
+        }
+        report
+    }).collect();
+}
+
+// One row per board in input order under an `index,puzzle,difficulty,steps,singles_solvable_cells,guessing_required,error`
+// header. Rated boards leave `error` empty, failed ones fill in only `index`, `puzzle` and `error`.
+#[cfg(feature = "parallel")]
+pub fn rate_batch_to_csv<W: Write>(mut writer: W, boards: &[SudokuBoard]) -> io::Result<()> {
+    writeln!(writer, "index,puzzle,difficulty,steps,singles_solvable_cells,guessing_required,error")?;
+    for (index, (board, report)) in boards.iter().zip(rate_batch(boards)).enumerate() {
+        match report {
+            Ok(report) => writeln!(writer, "{},{},{},{},{},{},", index, board.to_line(), report.difficulty, report.steps.len(), report.singles_solvable_cells, report.guessing_required)?,
+            Err(error) => writeln!(writer, "{},{},,,,,{}", index, board.to_line(), error)?
+        }
+    }
+    return writer.flush();
+}
+
+#[cfg(feature = "parallel")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsFormat {
+    Csv, // `rate_batch_to_csv`'s own layout
+    QqwingCsv // QQWing's `--csv --stats` columns, see `qqwing`
+}
+
+// Like `rate_batch_to_csv`, in the given format
+#[cfg(feature = "parallel")]
+pub fn rate_batch_to_csv_as<W: Write>(mut writer: W, boards: &[SudokuBoard], format: StatsFormat) -> io::Result<()> {
+    if format == StatsFormat::Csv {
+        return rate_batch_to_csv(writer, boards);
+    }
+    writeln!(writer, "{}", QQWING_CSV_HEADER)?;
+    for (index, (board, report)) in boards.iter().zip(rate_batch(boards)).enumerate() {
+        writeln!(writer, "{}", qqwing_csv_row(index + 1, board, report.as_ref().ok()))?;
+    }
+    return writer.flush();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{ easy, hard, medium };
+
+    #[test]
+    fn rate_easy_works() {
+        let solver = SudokuSolver::new(&easy());
+
+        let report = solver.rate();
+
+        assert_eq!(report.difficulty, Difficulty::Easy);
+        assert!(!report.guessing_required);
+        assert_eq!(report.singles_solvable_cells, 5);
+        assert_eq!(report.technique_counts.keys().copied().collect::<Vec<Technique>>(), vec![Technique::NakedSingle]);
+        assert_eq!(report.technique_counts[&Technique::NakedSingle], 5);
+        assert_eq!(report.to_string(), "Difficulty: Easy\nLogical steps: 5\n  Naked single: 5\nCells solvable by singles: 5\nGuessing required: no");
+    }
+
+    #[test]
+    fn rate_medium_works() {
+        let solver = SudokuSolver::new(&medium());
+
+        let report = solver.rate();
+
+        assert!(report.difficulty <= Difficulty::Medium);
+        assert!(!report.guessing_required);
+        assert_eq!(report.singles_solvable_cells, solver.unsolved_spaces.len());
+        assert!(report.technique_counts.keys().all(|technique| *technique <= Technique::HiddenSingle));
+
+        // Replaying the steps reaches the solution
+        let mut board = SudokuBoard::copy(&solver.board);
+        for step in report.steps.iter() {
+            board.set_value(step.row, step.column, step.value);
+        }
+        assert_eq!(board, solver.solve());
+    }
+
+    #[test]
+    fn rate_hard_works() {
+        let solver = SudokuSolver::new(&hard());
+
+        let report = solver.rate();
+
+        assert_eq!(report.difficulty, Difficulty::Hard);
+        assert!(report.guessing_required);
+        assert!(report.singles_solvable_cells < solver.unsolved_spaces.len());
+        assert!(report.to_string().ends_with("Guessing required: yes"));
+    }
+
+    #[test]
+    fn guess_depth_works() {
+        let easy = SudokuSolver::new(&easy());
+        let one_guess = SudokuSolver::new(&SudokuBoard::new(&[
+            0,7,0, 9,0,0, 1,8,0,
+            0,0,0, 0,0,0, 0,9,2,
+            0,0,0, 0,0,0, 4,0,0,
+            0,8,0, 0,0,0, 0,0,0,
+            0,0,3, 0,0,5, 0,7,0,
+            0,6,7, 1,0,0, 0,0,0,
+            0,1,0, 0,0,3, 8,6,0,
+            4,0,0, 6,0,0, 9,0,0,
+            0,2,6, 0,0,1, 0,0,5
+        ]));
+        // AI Escargot
+        let two_guesses = SudokuSolver::new(&SudokuBoard::new(&[
+            1,0,0, 0,0,7, 0,9,0,
+            0,3,0, 0,2,0, 0,0,8,
+            0,0,9, 6,0,0, 5,0,0,
+            0,0,5, 3,0,0, 9,0,0,
+            0,1,0, 0,8,0, 0,0,2,
+            6,0,0, 0,0,4, 0,0,0,
+            3,0,0, 0,0,0, 0,1,0,
+            0,4,0, 0,0,0, 0,0,7,
+            0,0,7, 0,0,0, 3,0,0
+        ]));
+
+        assert_eq!(easy.guess_depth(0), Some(0));
+        assert_eq!(one_guess.guess_depth(0), None);
+        assert_eq!(one_guess.guess_depth(3), Some(1));
+        assert_eq!(two_guesses.guess_depth(1), None);
+        assert_eq!(two_guesses.guess_depth(2), Some(2));
+        // Never solved without a unique solution, however deep the guessing goes
+        assert_eq!(SudokuSolver::new(&SudokuBoard::new(&[0; 81])).guess_depth(1), None);
+    }
+
+    #[test]
+    fn difficulty_ordering_works() {
+        let easy = SudokuSolver::new(&easy()).rate();
+        let medium = SudokuSolver::new(&medium()).rate();
+        let hard = SudokuSolver::new(&hard()).rate();
+
+        assert!(easy.difficulty <= medium.difficulty);
+        assert!(medium.difficulty < hard.difficulty);
+        assert!(Difficulty::Easy < Difficulty::Medium);
+    }
+
+    #[test]
+    fn estimated_solve_time_works() {
+        let easy = SudokuSolver::new(&easy()).rate();
+        let hard = SudokuSolver::new(&hard()).rate();
+
+        for profile in [SolverProfile::beginner(), SolverProfile::average(), SolverProfile::expert()].iter() {
+            assert!(hard.estimated_solve_time(profile) > easy.estimated_solve_time(profile), "{:?}", profile);
+        }
+        assert!(easy.estimated_solve_time(&SolverProfile::beginner()) > easy.estimated_solve_time(&SolverProfile::expert()));
+
+        // 5 naked singles
+        let profile = SolverProfile { per_cell: 1.0, naked_single: 2.0, hidden_single: 0.0, locked_candidates: 0.0, guessing: 100.0 };
+        assert_eq!(easy.estimated_solve_time(&profile), Duration::from_secs(15));
+        assert_eq!(easy.estimated_solve_time(&SolverProfile { naked_single: 5.0, ..profile }), Duration::from_secs(30));
+        assert_eq!(hard.estimated_solve_time(&SolverProfile { guessing: 200.0, ..profile }) - hard.estimated_solve_time(&profile), Duration::from_secs(100));
+        assert_eq!(easy.estimated_solve_time(&SolverProfile { per_cell: -10.0, ..profile }), Duration::from_secs(0));
+        assert_eq!(SolverProfile::default(), SolverProfile::average());
+    }
+
+    #[cfg(feature = "parallel")]
+    fn mixed_batch() -> Vec<SudokuBoard> {
+        let mut invalid = [0; 81];
+        invalid[0] = 5;
+        invalid[1] = 5;
+        let mut unsolvable = [0; 81];
+        unsolvable[..8].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        unsolvable[17] = 9;
+
+        return vec![
+            easy(),
+            SudokuBoard::new(&invalid),
+            SudokuBoard::new(&unsolvable),
+            hard()
+        ];
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn rate_batch_works() {
+        let progress_calls = AtomicUsize::new(0);
+
+        let reports = rate_batch_with_progress(&mixed_batch(), 2, |_| { progress_calls.fetch_add(1, Ordering::Relaxed); });
+
+        assert_eq!(reports.len(), 4);
+        assert_eq!(reports[0].as_ref().map(|report| report.difficulty), Ok(Difficulty::Easy));
+        assert_eq!(reports[1].as_ref().unwrap_err(), &SolveError::InvalidBoard);
+        assert!(matches!(reports[2], Err(SolveError::NoSolution(_))));
+        assert_eq!(reports[3].as_ref().map(|report| report.difficulty), Ok(Difficulty::Hard));
+        assert_eq!(progress_calls.into_inner(), 2);
+        assert!(rate_batch(&[]).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn rate_batch_to_csv_works() {
+        let mut csv = Vec::new();
+
+        rate_batch_to_csv(&mut csv, &mixed_batch()).unwrap();
+
+        let csv = String::from_utf8(csv).unwrap();
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[0], "index,puzzle,difficulty,steps,singles_solvable_cells,guessing_required,error");
+        assert!(rows[1].starts_with("0,073894512") && rows[1].ends_with(",Easy,5,5,false,"));
+        assert!(rows[2].starts_with("1,55") && rows[2].ends_with(",,,,,An invalid starting board configuration was passed."));
+        assert!(rows[3].starts_with("2,") && rows[3].ends_with("This board is unsolvable: R1C9 has no candidates left"));
+        assert!(rows[4].starts_with("3,000000000002005040") && rows[4].contains(",Hard,"));
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn rate_batch_to_csv_as_qqwing() {
+        let mut plain = Vec::new();
+        let mut csv = Vec::new();
+        rate_batch_to_csv(&mut plain, &mixed_batch()).unwrap();
+        rate_batch_to_csv_as(&mut csv, &mixed_batch(), StatsFormat::Csv).unwrap();
+        assert_eq!(plain, csv);
+
+        let mut csv = Vec::new();
+        rate_batch_to_csv_as(&mut csv, &mixed_batch(), StatsFormat::QqwingCsv).unwrap();
+
+        let csv = String::from_utf8(csv).unwrap();
+        let rows: Vec<Vec<&str>> = csv.lines().map(|line| line.split(',').collect()).collect();
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[0].join(","), QQWING_CSV_HEADER);
+        assert!(rows.iter().all(|row| row.len() == 13));
+        assert_eq!((rows[1][0], rows[1][4], rows[1][12]), ("1", "5", "Simple"));
+        assert_eq!((rows[2][2], rows[2][12]), ("", "Unknown"));
+        assert_eq!(rows[4][12], "Expert");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let mut one_left = SudokuSolver::new(&easy()).solve(); // Only R1C1 empty
+        one_left.clear_value(0, 0);
+        let report = SudokuSolver::new(&one_left).rate();
+        let json = serde_json::to_string(&report).unwrap();
+
+        assert_eq!(json, "{\"difficulty\":\"easy\",\"steps\":[{\"technique\":\"naked_single\",\"row\":0,\"col\":0,\"value\":6,\"house\":null,\"cells\":[[0,0]],\"eliminated\":[[0,0,1],[0,0,2],[0,0,3],[0,0,4],[0,0,5],[0,0,7],[0,0,8],[0,0,9]],\"explanation\":\"R1C1 can only be 6: every other digit already appears in its row, column, or box\"}],\"technique_counts\":{\"naked_single\":1},\"guessing_required\":false,\"singles_solvable_cells\":1}");
+        assert_eq!(serde_json::from_str::<RatingReport>(&json).unwrap(), report);
+    }
+}
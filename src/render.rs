@@ -0,0 +1,250 @@
+use std::collections::HashSet;
+use std::env;
+use std::io::{ stdout, IsTerminal };
+use crate::sudoku_board::SudokuBoard;
+
+pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_GIVEN: &str = "\x1b[1;34m"; // Bold blue
+pub const ANSI_ENTERED: &str = "\x1b[32m"; // Green
+pub const ANSI_HIGHLIGHT: &str = "\x1b[1;31;7m"; // Bold red, inverse
+pub const ANSI_DIM: &str = "\x1b[2m";
+
+pub struct RenderOptions<'a> {
+    pub givens: Option<&'a SudokuBoard>, // The starting puzzle, when absent every filled space is treated as a given
+    pub highlights: HashSet<(usize, usize)>,
+    pub color_givens: bool,
+    pub color: bool
+}
+
+impl<'a> Default for RenderOptions<'a> {
+    fn default() -> RenderOptions<'a> {
+        return RenderOptions {
+            givens: None,
+            highlights: HashSet::new(),
+            color_givens: true,
+            color: true
+        }
+    }
+}
+
+impl<'a> RenderOptions<'a> {
+    pub fn plain() -> RenderOptions<'a> {
+        return RenderOptions { color: false, ..RenderOptions::default() };
+    }
+
+    // Colors should only be written when stdout is a terminal and the user hasn't opted out through `NO_COLOR`
+    pub fn color_supported() -> bool {
+        return env::var_os("NO_COLOR").is_none() && stdout().is_terminal();
+    }
+}
+
+impl SudokuBoard {
+    pub fn render_ansi(&self, options: &RenderOptions) -> String {
+        // Mirrors the layout of `Display` so the no-color mode is exactly the plain grid
+        let mut rendered = String::from("\n  ┌                   ┐\n");
+        for row in 0..=8 {
+            rendered.push_str("  │");
+            for column in 0..=8 {
+                rendered.push(' ');
+                rendered.push_str(&self.render_ansi_space(row, column, options));
+            }
+            rendered.push_str(" │\n");
+        }
+        rendered.push_str("  └                   ┘\n\n");
+        return rendered;
+    }
+
+    fn render_ansi_space(&self, row: usize, column: usize, options: &RenderOptions) -> String {
+        let value = self[(row, column)];
+        if !options.color {
+            return value.to_string();
+        }
+
+        let color = if options.highlights.contains(&(row, column)) {
+            Some(ANSI_HIGHLIGHT)
+        }
+        else if value == 0 {
+            None
+        }
+        else if options.givens.map_or(true, |givens| givens[(row, column)] != 0) {
+            if options.color_givens { Some(ANSI_GIVEN) } else { None }
+        }
+        else {
+            Some(ANSI_ENTERED)
+        };
+
+        return match color {
+            Some(color) => format!("{}{}{}", color, value, ANSI_RESET),
+            None => value.to_string()
+        };
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffOptions {
+    pub color: bool // Without color the markers alone tell the cells apart, for CI logs
+}
+
+impl Default for DiffOptions {
+    fn default() -> DiffOptions {
+        return DiffOptions { color: true };
+    }
+}
+
+impl DiffOptions {
+    pub fn plain() -> DiffOptions {
+        return DiffOptions { color: false };
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DiffSummary {
+    pub added: usize, // Empty in `before`, filled in `after`
+    pub changed: usize // Filled in `before` and different in `after`, including spaces that were emptied
+}
+
+pub fn diff_summary(before: &SudokuBoard, after: &SudokuBoard) -> DiffSummary {
+    let mut summary = DiffSummary::default();
+    for index in 0..81 {
+        let space = (index / 9, index % 9);
+        match (before[space], after[space]) {
+            (old, new) if old == new => {},
+            (0, _) => summary.added += 1,
+            _ => summary.changed += 1
+        }
+    }
+    return summary;
+}
+
+// Three characters per space: ` 5 ` unchanged, `+5 ` added, `3→5` changed and `3→.` emptied, with `.` for blanks.
+// In color unchanged spaces are dimmed, additions green and changes highlighted. A summary line follows the grid.
+pub fn render_diff(before: &SudokuBoard, after: &SudokuBoard, options: &DiffOptions) -> String {
+    let show = |value: u8| if value == 0 { '.' } else { char::from(b'0' + value) };
+    let mut rendered = String::new();
+    for row in 0..=8 {
+        if row == 3 || row == 6 {
+            rendered.push_str("------------+-------------+------------\n");
+        }
+        for column in 0..=8 {
+            if column == 3 || column == 6 {
+                rendered.push_str(" |");
+            }
+            if column != 0 {
+                rendered.push(' ');
+            }
+
+            let (old, new) = (before[(row, column)], after[(row, column)]);
+            let (cell, color) = match (old, new) {
+                _ if old == new => (format!(" {} ", show(new)), ANSI_DIM),
+                (0, _) => (format!("+{} ", show(new)), ANSI_ENTERED),
+                _ => (format!("{}→{}", show(old), show(new)), ANSI_HIGHLIGHT)
+            };
+            if options.color {
+                rendered.push_str(&format!("{}{}{}", color, cell, ANSI_RESET));
+            }
+            else {
+                rendered.push_str(&cell);
+            }
+        }
+        rendered.push('\n');
+    }
+
+    let summary = diff_summary(before, after);
+    let plural = |count: usize| if count == 1 { "cell" } else { "cells" };
+    rendered.push_str(&format!("{} {} added, {} {} changed\n", summary.added, plural(summary.added), summary.changed, plural(summary.changed)));
+    return rendered;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::easy;
+
+    #[test]
+    fn render_ansi_plain_matches_display() {
+        let board = easy();
+
+        let rendered = board.render_ansi(&RenderOptions::plain());
+
+        assert_eq!(rendered, format!("{}", board));
+        assert!(!rendered.contains('\x1b'));
+    }
+
+    #[test]
+    fn render_ansi_colors_givens_and_entered_spaces() {
+        let givens = easy();
+        let mut board = easy();
+        board.set_value(0, 0, 6);
+
+        let rendered = board.render_ansi(&RenderOptions { givens: Some(&givens), ..RenderOptions::default() });
+
+        assert!(rendered.starts_with(&format!("\n  ┌                   ┐\n  │ {}6{} {}7{}", ANSI_ENTERED, ANSI_RESET, ANSI_GIVEN, ANSI_RESET)));
+        assert!(rendered.contains(&format!(" 0 {}2{}", ANSI_GIVEN, ANSI_RESET))); // Empty spaces are never colored
+    }
+
+    #[test]
+    fn render_ansi_color_givens_disabled() {
+        let board = easy();
+
+        let rendered = board.render_ansi(&RenderOptions { color_givens: false, ..RenderOptions::default() });
+
+        assert!(!rendered.contains(ANSI_GIVEN));
+        assert!(!rendered.contains('\x1b'));
+    }
+
+    #[test]
+    fn render_ansi_highlights_take_precedence() {
+        let board = easy();
+        let mut highlights = HashSet::new();
+        highlights.insert((0, 1));
+        highlights.insert((8, 8));
+
+        let rendered = board.render_ansi(&RenderOptions { highlights, ..RenderOptions::default() });
+
+        assert!(rendered.contains(&format!("│ 0 {}7{} {}3{}", ANSI_HIGHLIGHT, ANSI_RESET, ANSI_GIVEN, ANSI_RESET)));
+        assert!(rendered.contains(&format!("{}0{} │", ANSI_HIGHLIGHT, ANSI_RESET)));
+        assert_eq!(rendered.matches(ANSI_HIGHLIGHT).count(), 2);
+    }
+
+    #[test]
+    fn render_diff_plain_works() {
+        let before = easy();
+        let mut after = easy();
+        after.set_value(0, 0, 6);
+        after.set_value(0, 1, 5);
+        after.clear_value(8, 0);
+
+        let rendered = render_diff(&before, &after, &DiffOptions::plain());
+
+        assert_eq!(rendered, concat!(
+            "+6  7→5  3  |  8   9   4  |  5   1   2 \n",
+            " 9   1   2  |  7   3   5  |  4   8   6 \n",
+            " 8   4   5  |  .   .   2  |  9   7   3 \n",
+            "------------+-------------+------------\n",
+            " 7   9   8  |  2   6   1  |  3   5   4 \n",
+            " 5   2   6  |  4   7   3  |  8   9   1 \n",
+            " 1   3   4  |  5   8   9  |  2   6   7 \n",
+            "------------+-------------+------------\n",
+            " 4   6   9  |  .   2   8  |  7   3   5 \n",
+            " 2   8   7  |  3   5   6  |  1   4   9 \n",
+            "3→.  5   1  |  9   4   7  |  6   2   . \n",
+            "1 cell added, 2 cells changed\n"
+        ));
+        assert_eq!(diff_summary(&before, &after), DiffSummary { added: 1, changed: 2 });
+        assert_eq!(diff_summary(&before, &before), DiffSummary::default());
+    }
+
+    #[test]
+    fn render_diff_color_works() {
+        let before = easy();
+        let mut after = easy();
+        after.set_value(0, 0, 6);
+        after.set_value(0, 1, 5);
+
+        let rendered = render_diff(&before, &after, &DiffOptions::default());
+
+        assert!(rendered.starts_with(&format!("{}+6 {} {}7→5{} {} 3 {}", ANSI_ENTERED, ANSI_RESET, ANSI_HIGHLIGHT, ANSI_RESET, ANSI_DIM, ANSI_RESET)));
+        assert_eq!(rendered.matches(ANSI_DIM).count(), 79);
+        assert!(rendered.ends_with("1 cell added, 1 cell changed\n"));
+    }
+}
@@ -0,0 +1,185 @@
+use std::error::Error;
+use std::fmt::{ self, Display, Formatter };
+use crate::candidate_board::{ CandidateBoard, CandidateSet };
+use crate::sudoku_board::SudokuBoard;
+
+// Simple Sudoku's .ss format: 9 grid lines, blanks written `.`, `X` or `0`, optionally boxed in with `|`, `-`, `+` and
+// `*`. A saved game follows the grid with its pencil marks, 9 lines of 9 cells separated by whitespace or `|`, where a
+// blank's cell lists its candidates (`.` when it has none) and a filled space's cell repeats its value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SsParseError {
+    pub line: usize, // Counts from 1
+    pub message: String
+}
+
+impl Display for SsParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        return write!(f, "line {}: {}", self.line, self.message);
+    }
+}
+
+impl Error for SsParseError {}
+
+fn is_separator(line: &str) -> bool {
+    return line.chars().all(|character| "*-+|= \t".contains(character));
+}
+
+impl SudokuBoard {
+    pub fn from_ss(text: &str) -> Result<(SudokuBoard, Option<CandidateBoard>), SsParseError> {
+        let lines: Vec<(usize, &str)> = text.lines()
+            .enumerate()
+            .map(|(line_index, line)| (line_index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !is_separator(line))
+            .collect();
+        let last_line = text.lines().count().max(1);
+        if lines.len() < 9 {
+            return Err(SsParseError { line: last_line, message: format!("expected 9 grid lines, found {}", lines.len()) });
+        }
+
+        let mut puzzle = [0; 81];
+        for (row_index, (line_number, line)) in lines.iter().take(9).enumerate() {
+            let cells: Vec<char> = line.chars().filter(|character| *character != '|' && !character.is_whitespace()).collect();
+            if cells.len() != 9 {
+                return Err(SsParseError { line: *line_number, message: format!("expected 9 cells, found {}", cells.len()) });
+            }
+            for (column_index, character) in cells.into_iter().enumerate() {
+                puzzle[9 * row_index + column_index] = match character {
+                    '.' | 'X' | 'x' => 0,
+                    _ => match character.to_digit(10) {
+                        Some(value) => value as u8,
+                        None => return Err(SsParseError { line: *line_number, message: format!("unexpected character '{}'", character) })
+                    }
+                };
+            }
+        }
+        let board = SudokuBoard::new(&puzzle);
+
+        let mark_lines = &lines[9..];
+        if mark_lines.is_empty() {
+            return Ok((board, None));
+        }
+        if mark_lines.len() != 9 {
+            return Err(SsParseError { line: mark_lines[0].0, message: format!("expected 9 pencil mark lines, found {}", mark_lines.len()) });
+        }
+
+        let mut notes = CandidateBoard::empty();
+        for (row_index, (line_number, line)) in mark_lines.iter().enumerate() {
+            let cells: Vec<&str> = line.split(|character: char| character == '|' || character.is_whitespace()).filter(|cell| !cell.is_empty()).collect();
+            if cells.len() != 9 {
+                return Err(SsParseError { line: *line_number, message: format!("expected 9 pencil mark cells, found {}", cells.len()) });
+            }
+            for (column_index, cell) in cells.into_iter().enumerate() {
+                let mut candidates = CandidateSet::new();
+                for character in cell.chars().filter(|character| *character != '.') {
+                    match character.to_digit(10) {
+                        Some(value) if value != 0 => candidates.insert(value as u8),
+                        _ => return Err(SsParseError { line: *line_number, message: format!("unexpected pencil mark '{}'", character) })
+                    }
+                }
+                if board[(row_index, column_index)] == 0 {
+                    notes.set(row_index, column_index, candidates);
+                }
+            }
+        }
+
+        return Ok((board, Some(notes)));
+    }
+
+    // Blanks are written as `.`, the pencil mark grid is only written when there are notes
+    pub fn to_ss(&self, notes: Option<&CandidateBoard>) -> String {
+        let mut ss = String::from("*-----------*\n");
+        for row_index in 0..=8 {
+            if row_index == 3 || row_index == 6 {
+                ss.push_str("|---+---+---|\n");
+            }
+            for column_index in 0..=8 {
+                if column_index % 3 == 0 {
+                    ss.push('|');
+                }
+                ss.push(match self[(row_index, column_index)] {
+                    0 => '.',
+                    value => char::from(b'0' + value)
+                });
+            }
+            ss.push_str("|\n");
+        }
+        ss.push_str("*-----------*\n");
+
+        let notes = match notes {
+            Some(notes) => notes,
+            None => return ss
+        };
+
+        let cell = |row_index: usize, column_index: usize| match self[(row_index, column_index)] {
+            0 if notes.get(row_index, column_index).is_empty() => String::from("."),
+            0 => notes.get(row_index, column_index).to_string(),
+            value => value.to_string()
+        };
+        let widths: Vec<usize> = (0..=8).map(|column_index| (0..=8).map(|row_index| cell(row_index, column_index).len()).max().unwrap().max(1)).collect();
+        let stack_width = |stack: usize| widths[3 * stack..3 * stack + 3].iter().sum::<usize>() + 4;
+        let border = |corner: char, joint: char| {
+            let stacks: Vec<String> = (0..3).map(|stack| "-".repeat(stack_width(stack))).collect();
+            format!("{}{}{}\n", corner, stacks.join(&joint.to_string()), corner)
+        };
+
+        ss.push('\n');
+        ss.push_str(&border('*', '-'));
+        for row_index in 0..=8 {
+            if row_index == 3 || row_index == 6 {
+                ss.push_str(&border('|', '+'));
+            }
+            for (column_index, width) in widths.iter().enumerate() {
+                ss.push_str(if column_index % 3 == 0 { "| " } else { " " });
+                ss.push_str(&format!("{:<width$}", cell(row_index, column_index), width = width));
+                if column_index % 3 == 2 {
+                    ss.push(' ');
+                }
+            }
+            ss.push_str("|\n");
+        }
+        ss.push_str(&border('*', '-'));
+        return ss;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::classic;
+
+    #[test]
+    fn from_ss_dialects() {
+        let boxed = "*-----------*\n|53.|.7.|...|\n|6..|195|...|\n|.98|...|.6.|\n|---+---+---|\n|8..|.6.|..3|\n|4..|8.3|..1|\n|7..|.2.|..6|\n|---+---+---|\n|.6.|...|28.|\n|...|419|..5|\n|...|.8.|.79|\n*-----------*\n";
+        let plain = "53XX7XXXX\n6XX195XXX\nX98XXXX6X\n8XXX6XXX3\n4XX8X3XX1\n7XXX2XXX6\nX6XXXX28X\nXXX419XX5\nXXXX8XX79\n";
+
+        assert_eq!(SudokuBoard::from_ss(boxed), Ok((classic(), None)));
+        assert_eq!(SudokuBoard::from_ss(plain), Ok((classic(), None)));
+        assert_eq!(classic().to_ss(None), boxed);
+    }
+
+    #[test]
+    fn pencil_marks_round_trip() {
+        let mut notes = CandidateBoard::new(&classic());
+        notes.remove(0, 2, 1);
+        notes.remove(0, 3, 6);
+        notes.set(8, 0, CandidateSet::new());
+
+        let ss = classic().to_ss(Some(&notes));
+        let (board, parsed_notes) = SudokuBoard::from_ss(&ss).unwrap();
+
+        assert_eq!(board, classic());
+        assert_eq!(parsed_notes, Some(notes));
+        assert!(ss.lines().any(|line| line == "| 5   3    24     | 2    7  2468 | 1489  1249 248  |"));
+    }
+
+    #[test]
+    fn from_ss_errors() {
+        let short = "53..7....\n6..195...\n";
+        let bad = "53..7....\n6..195...\n.98....6.\n8...6...3\n4..8.3..1\n7...2...6\n.6....28.\n...419..5\n....8..7?\n";
+        let marks = format!("{}{}", classic().to_ss(None), "| 12 3 |\n");
+
+        assert_eq!(SudokuBoard::from_ss(short).unwrap_err(), SsParseError { line: 2, message: String::from("expected 9 grid lines, found 2") });
+        assert_eq!(SudokuBoard::from_ss(bad).unwrap_err().to_string(), "line 9: unexpected character '?'");
+        assert_eq!(SudokuBoard::from_ss(&marks).unwrap_err().line, 14);
+    }
+}
@@ -0,0 +1,33 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Engine {
+    #[default]
+    Iterative, // Index based loop with an explicit attempted values table
+    Recursive // Depth first `search` with one stack frame per unsolved space
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SolverConfig {
+    pub engine: Engine
+}
+
+impl SolverConfig {
+    pub fn new() -> SolverConfig {
+        return SolverConfig::default();
+    }
+
+    pub fn engine(mut self, engine: Engine) -> SolverConfig {
+        self.engine = engine;
+        return self;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_works() {
+        assert_eq!(SolverConfig::new().engine, Engine::Iterative);
+        assert_eq!(SolverConfig::new().engine(Engine::Recursive).engine, Engine::Recursive);
+    }
+}
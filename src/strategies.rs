@@ -0,0 +1,147 @@
+use proptest::prelude::*;
+use proptest::sample::{ subsequence, SizeRange };
+use crate::random::{ RandomSource, SplitMix64 };
+use crate::sudoku_board::SudokuBoard;
+pub use crate::generator::{ solved_grid, solved_grid_with_rng };
+
+// Board strategies for property tests, behind the `proptest` feature. The proptest strategies shrink towards fewer
+// holes, the seeded functions below always give the same board for a seed and suit fixed test tables.
+
+// Solved grids from random seeds
+pub fn solved_grids() -> impl Strategy<Value = SudokuBoard> {
+    return any::<u64>().prop_map(solved_grid);
+}
+
+// Solved grids with a number of holes in `holes` emptied. The solution isn't necessarily unique.
+pub fn masked_puzzles(holes: impl Into<SizeRange>) -> impl Strategy<Value = SudokuBoard> {
+    return (solved_grids(), subsequence((0..81).collect::<Vec<usize>>(), holes)).prop_map(|(mut board, holes)| {
+        for index in holes {
+            board.clear_value(index / 9, index % 9);
+        }
+        board
+    });
+}
+
+// Masked puzzles with one value repeated in a row, so `all_spaces_valid` is always false
+pub fn invalid_boards() -> impl Strategy<Value = SudokuBoard> {
+    return (masked_puzzles(40), 0..9usize, 0..9usize, 0..8usize, 1..=9u8).prop_map(|(board, row_index, from, to, value)| {
+        repeat_in_row(board, row_index, from, to, value)
+    });
+}
+
+// Any board `SudokuBoard::new` accepts that a solver could be handed: a solved grid with 0 to 81 holes
+impl Arbitrary for SudokuBoard {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<SudokuBoard>;
+
+    fn arbitrary_with(_: ()) -> BoxedStrategy<SudokuBoard> {
+        return masked_puzzles(0..=81).boxed();
+    }
+}
+
+// `solved_grid(seed)` with `holes` random spaces emptied. The solution isn't necessarily unique.
+pub fn masked_puzzle(seed: u64, holes: usize) -> SudokuBoard {
+    let mut board = solved_grid(seed);
+    let mut spaces: Vec<usize> = (0..81).collect();
+    SplitMix64::new(seed ^ 0x5EED).shuffle(&mut spaces);
+    for index in spaces.into_iter().take(holes) {
+        board.clear_value(index / 9, index % 9);
+    }
+    return board;
+}
+
+// A masked puzzle with one given copied onto another space of its row, so `all_spaces_valid` is always false
+pub fn invalid_board(seed: u64) -> SudokuBoard {
+    let board = masked_puzzle(seed, 40);
+    let mut random = SplitMix64::new(seed ^ 0xBAD);
+    let row_index = random.below(9) as usize;
+    let (from, to) = (random.below(9) as usize, random.below(8) as usize);
+    let value = match board[(row_index, from)] {
+        0 => 1 + random.below(9) as u8,
+        value => value
+    };
+    return repeat_in_row(board, row_index, from, to, value);
+}
+
+// Copies the value at `from` onto another space of the row, `to` counts the spaces other than `from`. An empty `from`
+// gets `value` first, a filled one keeps its own.
+fn repeat_in_row(mut board: SudokuBoard, row_index: usize, from: usize, to: usize, value: u8) -> SudokuBoard {
+    let to = if to >= from { to + 1 } else { to };
+    let value = match board[(row_index, from)] {
+        0 => value,
+        existing_value => existing_value
+    };
+    board.set_value(row_index, from, value);
+    board.set_value(row_index, to, value);
+    return board;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sudoku_solver::SudokuSolver;
+
+    #[test]
+    fn solved_grid_is_solved() {
+        for seed in 0..50 {
+            assert!(solved_grid(seed).is_solved(), "seed {}", seed);
+        }
+        assert_eq!(solved_grid(7), solved_grid(7));
+        assert_ne!(solved_grid(7), solved_grid(8));
+    }
+
+    #[test]
+    fn solve_output_is_solved() {
+        for seed in 0..50 {
+            let puzzle = masked_puzzle(seed, 20 + seed as usize % 30);
+            let solution = SudokuSolver::new(&puzzle).solve();
+
+            assert!(solution.is_solved(), "seed {}", seed);
+            assert!(puzzle.get_unsolved_spaces().len() >= 20);
+            assert!((0..81).all(|index| puzzle[(index / 9, index % 9)] == 0 || puzzle[(index / 9, index % 9)] == solution[(index / 9, index % 9)]));
+        }
+    }
+
+    #[test]
+    fn parse_round_trip() {
+        for seed in 0..50 {
+            let board = masked_puzzle(seed, seed as usize);
+
+            assert_eq!(SudokuBoard::parse_grid(&board.to_string()), Ok(SudokuBoard::copy(&board)));
+        }
+    }
+
+    #[test]
+    fn invalid_board_is_invalid() {
+        for seed in 0..50 {
+            let board = invalid_board(seed);
+
+            assert!(!board.all_spaces_valid(), "seed {}", seed);
+            assert_eq!(crate::solve_puzzle(&board.to_array()), None);
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn arbitrary_boards_parse_round_trip(board in any::<SudokuBoard>()) {
+            prop_assert_eq!(SudokuBoard::parse_grid(&board.to_string()), Ok(SudokuBoard::copy(&board)));
+        }
+
+        #[test]
+        fn masked_puzzles_solve(puzzle in masked_puzzles(20..50)) {
+            let solution = SudokuSolver::new(&puzzle).solve();
+
+            prop_assert!(solution.is_solved());
+            prop_assert!((20..50).contains(&puzzle.unsolved_count()));
+            prop_assert!((0..81).all(|index| puzzle[(index / 9, index % 9)] == 0 || puzzle[(index / 9, index % 9)] == solution[(index / 9, index % 9)]));
+        }
+
+        #[test]
+        fn invalid_boards_are_invalid(board in invalid_boards()) {
+            prop_assert!(!board.all_spaces_valid());
+            prop_assert_eq!(crate::solve_puzzle(&board.to_array()), None);
+        }
+    }
+}
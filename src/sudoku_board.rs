@@ -1,12 +1,268 @@
-use std::fmt::{ Display, Formatter, Result };
+use std::error::Error;
+use std::fmt::{ Debug, Display, Formatter, Result };
 use nalgebra::DMatrix;
-use std::collections::HashSet;
-use std::iter::FromIterator;
 use std::ops::{ Index, IndexMut };
+use std::sync::Mutex;
+use crate::candidate_board::CandidateSet;
+use crate::digit::Digit;
+
+// Nonet of every space in row-major order, nonets are themselves numbered row-major
+pub const NONET_OF: [usize; 81] = nonet_of_table();
+
+// Top left space of every nonet
+pub const NONET_ORIGIN: [(usize, usize); 9] = [
+    (0, 0), (0, 3), (0, 6),
+    (3, 0), (3, 3), (3, 6),
+    (6, 0), (6, 3), (6, 6)
+];
+
+// Re-exported for code that imported it from here before `peers` existed
+pub use crate::peers::PEERS;
+
+const fn nonet_of_table() -> [usize; 81] {
+    let mut table = [0; 81];
+    let mut index = 0;
+    while index < 81 {
+        table[index] = 3 * (index / 27) + (index % 9) / 3;
+        index += 1;
+    }
+    return table;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum House {
+    Row(usize),
+    Column(usize),
+    Nonet(usize)
+}
+
+impl House {
+    pub fn spaces(&self) -> Vec<(usize, usize)> {
+        return SudokuBoard::house_coordinates(*self).collect();
+    }
+
+    // The row, column or nonet number
+    pub fn index(&self) -> usize {
+        return match *self {
+            House::Row(house_index) | House::Column(house_index) | House::Nonet(house_index) => house_index
+        };
+    }
+
+    // `R1` to `R9`, `C1` to `C9` or `B1` to `B9`, the name in JSON and reports
+    pub fn label(&self) -> String {
+        return match self {
+            House::Row(row_index) => format!("R{}", row_index + 1),
+            House::Column(column_index) => format!("C{}", column_index + 1),
+            House::Nonet(nonet_index) => format!("B{}", nonet_index + 1)
+        };
+    }
+
+    // The inverse of `label`
+    pub fn from_label(label: &str) -> Option<House> {
+        let index = label.get(1..)?.parse::<usize>().ok().filter(|number| (1..=9).contains(number))? - 1;
+        return match label.get(..1)? {
+            "R" => Some(House::Row(index)),
+            "C" => Some(House::Column(index)),
+            "B" => Some(House::Nonet(index)),
+            _ => None
+        };
+    }
+
+    // The space at `position`, counting like the coordinate iterators
+    fn space(&self, position: usize) -> (usize, usize) {
+        return match *self {
+            House::Row(row_index) => (row_index, position),
+            House::Column(column_index) => (position, column_index),
+            House::Nonet(nonet_index) => (NONET_ORIGIN[nonet_index].0 + position / 3, NONET_ORIGIN[nonet_index].1 + position % 3)
+        };
+    }
+}
+
+// Written as its label
+#[cfg(feature = "serde")]
+impl serde::Serialize for House {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        return serializer.serialize_str(&self.label());
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for House {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<House, D::Error> {
+        let label = String::deserialize(deserializer)?;
+        return House::from_label(&label).ok_or_else(|| serde::de::Error::custom(format!("invalid house label '{}'", label)));
+    }
+}
+
+// A borrowed row, column or nonet that reads straight from the board, positions in the same order as the coordinate
+// iterators. Copying 9 values out is never needed, and the borrow keeps the board from changing under the view, which
+// the doctest below holds it to.
+#[doc = r#"
+```compile_fail,E0502
+use solv_a_line::sudoku_board::SudokuBoard;
+
+let mut board = SudokuBoard::new(&[0; 81]);
+let row = board.row_view(0);
+board.set_value(0, 0, 5);
+row.iter().count();
+```
+"#]
+#[derive(Debug, Clone, Copy)]
+pub struct HouseView<'a> {
+    board: &'a SudokuBoard,
+    house: House
+}
+
+impl<'a> HouseView<'a> {
+    pub fn house(&self) -> House {
+        return self.house;
+    }
+
+    pub fn get(&self, position: usize) -> u8 {
+        if position > 8 {
+            panic!("An invalid position was passed into 'get', it was {}", position);
+        }
+
+        return self.board.configuration[self.house.space(position)];
+    }
+
+    pub fn contains(&self, value: u8) -> bool {
+        return self.iter().any(|other| other == value);
+    }
+
+    pub fn iter(&self) -> HouseViewIter<'a> {
+        return HouseViewIter { view: *self, position: 0 };
+    }
+}
+
+impl<'a> IntoIterator for HouseView<'a> {
+    type Item = u8;
+    type IntoIter = HouseViewIter<'a>;
+
+    fn into_iter(self) -> HouseViewIter<'a> {
+        return self.iter();
+    }
+}
+
+impl<'a> IntoIterator for &HouseView<'a> {
+    type Item = u8;
+    type IntoIter = HouseViewIter<'a>;
+
+    fn into_iter(self) -> HouseViewIter<'a> {
+        return self.iter();
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HouseViewIter<'a> {
+    view: HouseView<'a>,
+    position: usize
+}
+
+impl<'a> Iterator for HouseViewIter<'a> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.position == 9 {
+            return None;
+        }
+        self.position += 1;
+        return Some(self.view.get(self.position - 1));
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        return (9 - self.position, Some(9 - self.position));
+    }
+}
+
+impl<'a> ExactSizeIterator for HouseViewIter<'a> {}
+
+// A value used more than once in a single house, along with every space holding it
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Conflict {
+    pub house: House,
+    pub value: u8,
+    pub spaces: Vec<(usize, usize)>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationReport {
+    pub conflicts: Vec<Conflict> // Row conflicts first, then columns, then nonets
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        return self.conflicts.is_empty();
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoardError {
+    ValueOutOfRange { row: usize, column: usize, value: u8 },
+    HouseOutOfRange(House),
+    LockedGiven { row: usize, column: usize }
+}
+
+impl Display for BoardError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            BoardError::ValueOutOfRange { row, column, value } => write!(f, "All values must be [0..9] inclusive, ({}, {}) was {}", row, column, value),
+            BoardError::HouseOutOfRange(house) => write!(f, "House indices must be [0..8] inclusive, it was {:?}", house),
+            BoardError::LockedGiven { row, column } => write!(f, "({}, {}) is a locked given", row, column)
+        }
+    }
+}
+
+impl Error for BoardError {}
+
+// The first space, in row-major order, where a board doesn't keep a value of the board it should extend
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InconsistentAt {
+    pub row: usize,
+    pub column: usize,
+    pub expected: u8, // The base's value
+    pub found: u8 // 0 when the space was emptied
+}
+
+impl Display for InconsistentAt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        return write!(f, "({}, {}) should be {}, it was {}", self.row, self.column, self.expected, self.found);
+    }
+}
+
+impl Error for InconsistentAt {}
+
+const ALL_SPACES: u128 = (1 << 81) - 1;
+
+// The row-major space of the lowest set bit
+fn space_of(bitboard: u128) -> Option<(usize, usize)> {
+    if bitboard == 0 {
+        return None;
+    }
+    let index = bitboard.trailing_zeros() as usize;
+    return Some((index / 9, index % 9));
+}
+
+// One space a mutation changed, reported to the board's observer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellChanged {
+    pub row: usize,
+    pub column: usize,
+    pub previous_value: u8, // 0 for a space that was empty
+    pub value: u8 // 0 for an erase
+}
+
+// Behind a mutex only so boards stay `Sync`, it's always reached through `&mut self` and never locked
+type Observer = Mutex<Box<dyn FnMut(CellChanged) + Send>>;
 
-#[derive(Debug)]
 pub struct SudokuBoard {
-    configuration: DMatrix<u8>
+    configuration: DMatrix<u8>,
+    empties: u128, // Bit `9 * row + column` is set for every empty space, kept in step with `configuration` by every mutation
+    pending_write: Option<usize>, // The space last handed out by IndexMut, folded into `empties` by the next mutation
+    givens: u128, // Bit `9 * row + column` is set for every space filled when the board was constructed
+    givens_locked: bool,
+    observer: Option<Observer>
 }
 
 impl Display for SudokuBoard {
@@ -15,6 +271,36 @@ impl Display for SudokuBoard {
     }
 }
 
+// `{:?}` keeps to one line so failed assertions stay readable, `{:#?}` lays the grid out with `.` for empty spaces
+impl Debug for SudokuBoard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let summary = format!("givens: {}, valid: {}, solved: {}", self.givens.count_ones(), self.all_spaces_valid(), self.is_solved());
+        if !f.alternate() {
+            return write!(f, "SudokuBoard(\"{}\", {})", self.to_line(), summary);
+        }
+
+        write!(f, "SudokuBoard({})", summary)?;
+        for row_index in 0..=8 {
+            if row_index % 3 == 0 && row_index != 0 {
+                write!(f, "\n------+-------+------")?;
+            }
+            writeln!(f)?;
+            for column_index in 0..=8 {
+                let separator = match column_index {
+                    0 => "",
+                    3 | 6 => " | ",
+                    _ => " "
+                };
+                match self.configuration[(row_index, column_index)] {
+                    0 => write!(f, "{}.", separator)?,
+                    value => write!(f, "{}{}", separator, value)?
+                }
+            }
+        }
+        return Ok(());
+    }
+}
+
 impl PartialEq for SudokuBoard {
     fn eq(&self, other: &SudokuBoard) -> bool {
         self.configuration == other.configuration
@@ -29,72 +315,483 @@ impl Index<(usize, usize)> for SudokuBoard {
     }
 }
 
+// Index writes are unobserved: the observer never hears about them, and the value is only range checked in debug builds,
+// when the write is folded into the bookkeeping. They exist for the solvers' hot loops, everything else should call
+// `set_value`. The empty space bookkeeping catches up with the write on the board's next mutation, and every query sees
+// the written value before that.
 impl IndexMut<(usize, usize)> for SudokuBoard {
     fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
+        self.settle();
+        self.pending_write = Some(9 * index.0 + index.1);
         &mut self.configuration[(index.0, index.1)]
     }
 }
 
 impl SudokuBoard {
     pub fn new(sudoku_puzzle: &[u8; 81]) -> SudokuBoard {
-        if sudoku_puzzle.iter().any(|value| *value > 9) { // Values will not be negative because `u8` is used
-            panic!("All values must be [0..9] inclusive");
+        return match SudokuBoard::try_new(sudoku_puzzle) {
+            Ok(sudoku_board) => sudoku_board,
+            Err(_) => panic!("All values must be [0..9] inclusive")
+        };
+    }
+
+    pub fn try_new(sudoku_puzzle: &[u8; 81]) -> std::result::Result<SudokuBoard, BoardError> {
+        if let Some(index) = sudoku_puzzle.iter().position(|value| *value > 9) { // Values will not be negative because `u8` is used
+            return Err(BoardError::ValueOutOfRange { row: index / 9, column: index % 9, value: sudoku_puzzle[index] });
         }
 
-        return SudokuBoard {
-            configuration: DMatrix::from_row_slice(9, 9, sudoku_puzzle)
+        return Ok(SudokuBoard {
+            configuration: DMatrix::from_row_slice(9, 9, sudoku_puzzle),
+            empties: sudoku_puzzle.iter().enumerate().filter(|(_, value)| **value == 0).fold(0, |empties, (index, _)| empties | 1 << index),
+            givens: sudoku_puzzle.iter().enumerate().filter(|(_, value)| **value != 0).fold(0, |givens, (index, _)| givens | 1 << index),
+            givens_locked: false,
+            pending_write: None,
+            observer: None
+        });
+    }
+
+    // Skips the range check of `new` for values already known to be [0..9] inclusive. Out of range values don't cause
+    // anything unsafe, just meaningless results or panics later on. Debug builds still check and panic.
+    pub fn new_unchecked(sudoku_puzzle: &[u8; 81]) -> SudokuBoard {
+        debug_assert!(sudoku_puzzle.iter().all(|value| *value <= 9), "All values must be [0..9] inclusive");
+
+        let mut empties = 0;
+        for (index, value) in sudoku_puzzle.iter().enumerate() {
+            if *value == 0 {
+                empties |= 1 << index;
+            }
         }
+        return SudokuBoard {
+            configuration: DMatrix::from_row_slice(9, 9, sudoku_puzzle),
+            empties,
+            givens: ALL_SPACES & !empties,
+            givens_locked: false,
+            pending_write: None,
+            observer: None
+        };
+    }
+
+    // Like `new_unchecked`, with the spaces in `givens` as the givens instead of every placed value
+    pub(crate) fn new_unchecked_with_givens(sudoku_puzzle: &[u8; 81], givens: u128) -> SudokuBoard {
+        let mut sudoku_board = SudokuBoard::new_unchecked(sudoku_puzzle);
+        sudoku_board.givens = givens;
+        return sudoku_board;
     }
 
+    // The observer isn't copied
     pub fn copy(other: &SudokuBoard) -> SudokuBoard {
         return SudokuBoard {
-            configuration: other.configuration.clone_owned()
+            configuration: other.configuration.clone_owned(),
+            empties: other.empties(),
+            givens: other.givens,
+            givens_locked: other.givens_locked,
+            pending_write: None,
+            observer: None
+        }
+    }
+
+    // Overwrites this board with `other` in place, reusing its storage. This board keeps its own observer, which hears
+    // about every space that changed.
+    pub fn copy_from(&mut self, other: &SudokuBoard) {
+        self.settle();
+        let previous_values = self.observer.as_ref().map(|_| self.to_array());
+        self.configuration.copy_from(&other.configuration);
+        self.empties = other.empties();
+        self.givens = other.givens;
+        self.givens_locked = other.givens_locked;
+        if let Some(previous_values) = previous_values {
+            self.notify_changes(&previous_values);
+        }
+    }
+
+    // Called with every space a mutation changes, in the order they change. Bulk setters report each space, rejected
+    // mutations and writes of the value already there report nothing. Writes through `board[(row, column)]` are never
+    // reported, use `set_value` for changes the observer should see. Replaces any previous observer.
+    pub fn set_observer(&mut self, observer: impl FnMut(CellChanged) + Send + 'static) {
+        self.observer = Some(Mutex::new(Box::new(observer)));
+    }
+
+    pub fn clear_observer(&mut self) {
+        self.observer = None;
+    }
+
+    fn notify(&mut self, change: CellChanged) {
+        if let Some(observer) = self.observer.as_mut() {
+            (observer.get_mut().unwrap_or_else(|poisoned| poisoned.into_inner()))(change);
+        }
+    }
+
+    // Reports every space that differs from `previous_values`, in row-major order
+    fn notify_changes(&mut self, previous_values: &[u8; 81]) {
+        for (index, &previous_value) in previous_values.iter().enumerate() {
+            let value = self.configuration[(index / 9, index % 9)];
+            if value != previous_value {
+                self.notify(CellChanged { row: index / 9, column: index % 9, previous_value, value });
+            }
         }
     }
 
+    // `empties` with the space of a pending IndexMut write brought up to date
+    fn empties(&self) -> u128 {
+        if let Some(index) = self.pending_write {
+            debug_assert!(self.configuration[(index / 9, index % 9)] <= 9, "All values must be [0..9] inclusive");
+        }
+        return match self.pending_write {
+            Some(index) if self.configuration[(index / 9, index % 9)] == 0 => self.empties | 1 << index,
+            Some(index) => self.empties & !(1 << index),
+            None => self.empties
+        };
+    }
+
+    // Folds a pending IndexMut write into the bookkeeping, without reporting it
+    fn settle(&mut self) {
+        self.empties = self.empties();
+        self.pending_write = None;
+    }
+
+    pub fn set_value(&mut self, row_index: usize, column_index: usize, value: u8) {
+        if value > 9 {
+            panic!("All values must be [0..9] inclusive");
+        }
+
+        self.settle();
+        let previous_value = std::mem::replace(&mut self.configuration[(row_index, column_index)], value);
+        let bit = 1 << (9 * row_index + column_index);
+        if value == 0 {
+            self.empties |= bit;
+        } else {
+            self.empties &= !bit;
+        }
+        debug_assert_eq!(self.empties & bit != 0, value == 0); // The full recount is left to `empties_bitboard_matches_recount`
+        if previous_value != value {
+            self.notify(CellChanged { row: row_index, column: column_index, previous_value, value });
+        }
+    }
+
+    pub fn clear_value(&mut self, row_index: usize, column_index: usize) {
+        self.set_value(row_index, column_index, 0);
+    }
+
+    // The same space as `board[(row, column)]`, with `None` for empty instead of 0
+    pub fn digit(&self, row_index: usize, column_index: usize) -> Option<Digit> {
+        return Digit::new(self.configuration[(row_index, column_index)]);
+    }
+
+    // `None` empties the space, like `clear_value`
+    pub fn set_digit(&mut self, row_index: usize, column_index: usize, digit: Option<Digit>) {
+        self.set_value(row_index, column_index, digit.map_or(0, Digit::get));
+    }
+
+    // Row-major like `to_array`
+    pub fn to_digits(&self) -> [Option<Digit>; 81] {
+        let mut digits = [None; 81];
+        for (index, digit) in digits.iter_mut().enumerate() {
+            *digit = self.digit(index / 9, index % 9);
+        }
+        return digits;
+    }
+
+    // Bit `9 * row + column` is set for every given, laid out like `empties_bitboard`
+    pub fn givens_bitboard(&self) -> u128 {
+        return self.givens;
+    }
+
+    pub fn is_given(&self, row_index: usize, column_index: usize) -> bool {
+        return self.givens & 1 << (9 * row_index + column_index) != 0;
+    }
+
+    // Once locked, the bulk setters refuse to change givens. `set_value` stays unchecked for the solvers' sake.
+    pub fn lock_givens(&mut self) {
+        self.givens_locked = true;
+    }
+
+    pub fn unlock_givens(&mut self) {
+        self.givens_locked = false;
+    }
+
+    pub fn givens_locked(&self) -> bool {
+        return self.givens_locked;
+    }
+
+    pub fn clear(&mut self) {
+        self.settle();
+        let previous_values = self.observer.as_ref().map(|_| self.to_array());
+        self.configuration.fill(0);
+        self.empties = ALL_SPACES;
+        self.givens = 0;
+        self.givens_locked = false;
+        if let Some(previous_values) = previous_values {
+            self.notify_changes(&previous_values);
+        }
+    }
+
+    // Erases every space that wasn't a given, givens and their lock are kept
+    pub fn reset_to_givens(&mut self) {
+        let givens = self.givens;
+        for index in (0..81).filter(|index| givens & 1 << index == 0) {
+            self.set_value(index / 9, index % 9, 0);
+        }
+    }
+
+    pub fn set_row(&mut self, row_index: usize, values: [u8; 9]) -> std::result::Result<(), BoardError> {
+        return self.set_house(House::Row(row_index), values);
+    }
+
+    pub fn set_column(&mut self, column_index: usize, values: [u8; 9]) -> std::result::Result<(), BoardError> {
+        return self.set_house(House::Column(column_index), values);
+    }
+
+    // `values` are row-major within the nonet
+    pub fn set_nonet(&mut self, nonet_index: usize, values: [u8; 9]) -> std::result::Result<(), BoardError> {
+        return self.set_house(House::Nonet(nonet_index), values);
+    }
+
+    fn set_house(&mut self, house: House, values: [u8; 9]) -> std::result::Result<(), BoardError> {
+        // Everything is checked before the first write so a rejected call leaves the board untouched
+        if house.index() > 8 {
+            return Err(BoardError::HouseOutOfRange(house));
+        }
+
+        let spaces = house.spaces();
+        for (&(row_index, column_index), &value) in spaces.iter().zip(values.iter()) {
+            if value > 9 {
+                return Err(BoardError::ValueOutOfRange { row: row_index, column: column_index, value });
+            }
+            if self.givens_locked && self.is_given(row_index, column_index) && self.configuration[(row_index, column_index)] != value {
+                return Err(BoardError::LockedGiven { row: row_index, column: column_index });
+            }
+        }
+
+        for (&(row_index, column_index), &value) in spaces.iter().zip(values.iter()) {
+            self.set_value(row_index, column_index, value);
+        }
+        return Ok(());
+    }
+
+    // Bit `9 * row + column` is set for every empty space, bits 81 and up are always clear
+    pub fn empties_bitboard(&self) -> u128 {
+        return self.empties();
+    }
+
+    // Bit `9 * row + column` is set for every space holding `value`, laid out like `empties_bitboard`
+    pub fn positions_bitboard(&self, value: u8) -> u128 {
+        if !(1..=9).contains(&value) {
+            panic!("An invalid value was passed into 'positions_bitboard', it was {}", value);
+        }
+
+        let mut positions = 0;
+        let mut filled = ALL_SPACES & !self.empties();
+        while filled != 0 {
+            let index = filled.trailing_zeros() as usize;
+            filled &= filled - 1;
+            if self.configuration[(index / 9, index % 9)] == value {
+                positions |= 1 << index;
+            }
+        }
+        return positions;
+    }
+
+    // Every space holding `value`, row-major
+    pub fn positions_of(&self, value: u8) -> Vec<(usize, usize)> {
+        if !(1..=9).contains(&value) {
+            panic!("An invalid value was passed into 'positions_of', it was {}", value);
+        }
+
+        let mut positions = self.positions_bitboard(value);
+        let mut spaces = Vec::with_capacity(positions.count_ones() as usize);
+        while let Some(space) = space_of(positions) {
+            spaces.push(space);
+            positions &= positions - 1;
+        }
+        return spaces;
+    }
+
+    pub fn unsolved_count(&self) -> usize {
+        return self.empties().count_ones() as usize;
+    }
+
+    pub fn all_spaces_solved(&self) -> bool {
+        return self.empties() == 0;
+    }
+
+    pub fn to_array(&self) -> [u8; 81] {
+        let mut values = [0; 81];
+        for (index, value) in values.iter_mut().enumerate() {
+            *value = self.configuration[(index / 9, index % 9)];
+        }
+        return values;
+    }
+
+    // The 81 values row-major with `0` for empty spaces, also used as the key that tells puzzles apart
+    pub fn to_line(&self) -> String {
+        return self.to_array().iter().map(|value| char::from(b'0' + value)).collect();
+    }
+
+    // How many times each value is placed, indexed by value so `digit_counts()[0]` is the number of empty spaces
+    pub fn digit_counts(&self) -> [u8; 10] {
+        let mut counts = [0; 10];
+        for value in self.configuration.iter() {
+            counts[*value as usize] += 1;
+        }
+        return counts;
+    }
+
+    pub fn digit_complete(&self, digit: u8) -> bool {
+        if !(1..=9).contains(&digit) {
+            panic!("Digits must be [1..9] inclusive, it was {}", digit);
+        }
+        return self.digit_counts()[digit as usize] >= 9;
+    }
+
+    // The incomplete digit placed most often, ties going to the smaller digit. `None` once every digit is complete.
+    pub fn most_placed_digit(&self) -> Option<u8> {
+        let counts = self.digit_counts();
+        return (1..=9).filter(|digit| counts[*digit as usize] < 9).min_by_key(|digit| (9 - counts[*digit as usize], *digit));
+    }
+
+    // The digit placed least often, ties going to the smaller digit. `None` once every digit is complete.
+    pub fn least_placed_digit(&self) -> Option<u8> {
+        let counts = self.digit_counts();
+        return (1..=9).filter(|digit| counts[*digit as usize] < 9).min_by_key(|digit| (counts[*digit as usize], *digit));
+    }
+
     pub fn get_unsolved_spaces(&self) -> Vec<(usize, usize)> {
-        let mut unsolved_spaces = Vec::new();
-        for row in 0..=8 {
-            for column in 0..=8 {
-                if self.configuration[(row, column)] == 0 {
-                    unsolved_spaces.push((row, column));
-                }
+        return self.unsolved_spaces().collect();
+    }
+
+    // Row-major, popping the lowest set bit of the empties bitboard each step
+    pub fn unsolved_spaces(&self) -> impl Iterator<Item = (usize, usize)> {
+        let mut empties = self.empties();
+        return std::iter::from_fn(move || {
+            if empties == 0 {
+                return None;
             }
+            let index = empties.trailing_zeros() as usize;
+            empties &= empties - 1;
+            return Some((index / 9, index % 9));
+        });
+    }
+
+    pub fn first_unsolved(&self) -> Option<(usize, usize)> {
+        return space_of(self.empties());
+    }
+
+    // Scans row-major starting after `space` and wraps around, so `space` itself is returned last if it's unsolved
+    pub fn next_unsolved_after(&self, space: (usize, usize)) -> Option<(usize, usize)> {
+        let start = 9 * space.0 + space.1;
+        let after = self.empties() & (ALL_SPACES << (start + 1));
+        return space_of(after).or_else(|| self.first_unsolved());
+    }
+
+    // Reverse of `next_unsolved_after`, for shift-tab style navigation
+    pub fn previous_unsolved_before(&self, space: (usize, usize)) -> Option<(usize, usize)> {
+        let start = 9 * space.0 + space.1;
+        let before = self.empties() & ((1 << start) - 1);
+        let candidates = if before == 0 { self.empties() } else { before };
+        if candidates == 0 {
+            return None;
         }
-        return unsolved_spaces;
+        let index = 127 - candidates.leading_zeros() as usize;
+        return Some((index / 9, index % 9));
     }
 
     pub fn all_spaces_valid(&self) -> bool {
         // All values in a row/column/nonet must be unique, otherwise this breaks the rules of Sudoku
+        #[cfg(feature = "simd")]
+        return crate::simd::houses_valid(&self.to_array());
+        #[cfg(not(feature = "simd"))]
+        return self.scan_houses(None);
+    }
+
+    // Whether this board keeps every value `base` has, anything may fill the spaces `base` leaves empty
+    pub fn is_consistent_extension_of(&self, base: &SudokuBoard) -> std::result::Result<(), InconsistentAt> {
+        let mismatches = (1..=9).fold(0, |mismatches, value| mismatches | base.positions_bitboard(value) & !self.positions_bitboard(value));
+        return match space_of(mismatches) {
+            Some((row, column)) => Err(InconsistentAt { row, column, expected: base[(row, column)], found: self[(row, column)] }),
+            None => Ok(())
+        };
+    }
+
+    pub fn is_solved(&self) -> bool {
+        // A full board without duplicates has every value [1..9] exactly once in each house
+        return self.all_spaces_solved() && self.all_spaces_valid();
+    }
+
+    pub fn validate(&self) -> ValidationReport {
+        let mut conflicts = Vec::new();
+        self.scan_houses(Some(&mut conflicts));
+        return ValidationReport { conflicts };
+    }
+
+    fn scan_houses(&self, conflicts: Option<&mut Vec<Conflict>>) -> bool {
+        // Single pass keeping a bitmask of the values seen so far in every house. Without a report to fill, the first
+        // duplicate is enough to answer.
+        let mut seen = [0u16; 27]; // Indexed in the order of `houses`
+        let mut duplicated = [0u16; 27];
+        let mut is_valid = true;
 
         for row_index in 0..=8 {
-            let row = self.get_row(row_index);
-            let row_without_unsolved_spaces: Vec<u8> = row.iter().filter(|&&value| value != 0).map(|value| *value).collect();
-            let row_without_unsolved_spaces_set: HashSet<u8> = HashSet::from_iter(row_without_unsolved_spaces.iter().map(|value| *value));
-            if row_without_unsolved_spaces_set.len() != row_without_unsolved_spaces.len() {
-                return false;
+            for column_index in 0..=8 {
+                let bit = match self.digit(row_index, column_index) {
+                    Some(digit) => 1 << digit.get(),
+                    None => continue
+                };
+                for house_number in [row_index, 9 + column_index, 18 + NONET_OF[9 * row_index + column_index]].iter() {
+                    if seen[*house_number] & bit != 0 {
+                        if conflicts.is_none() {
+                            return false;
+                        }
+                        duplicated[*house_number] |= bit;
+                        is_valid = false;
+                    }
+                    seen[*house_number] |= bit;
+                }
             }
         }
 
-        for column_index in 0..=8 {
-            let column = self.get_column(column_index);
-            let column_without_unsolved_spaces: Vec<u8> = column.iter().filter(|&&value| value != 0).map(|value| *value).collect();
-            let column_without_unsolved_spaces_set: HashSet<u8> = HashSet::from_iter(column_without_unsolved_spaces.iter().map(|value| *value));
-            if column_without_unsolved_spaces_set.len() != column_without_unsolved_spaces.len() {
-                return false;
+        if let Some(conflicts) = conflicts {
+            for (house, duplicated_values) in SudokuBoard::houses().zip(duplicated.iter()) {
+                for value in (1..=9).filter(|value| duplicated_values & (1 << value) != 0) {
+                    let spaces = SudokuBoard::house_coordinates(house).filter(|space| self.configuration[*space] == value).collect();
+                    conflicts.push(Conflict { house, value, spaces });
+                }
             }
         }
 
-        for nonet_index in 0..=8 {
-            let nonet = self.get_nonet(nonet_index);
-            let nonet_without_unsolved_spaces: Vec<u8> = nonet.iter().filter(|&&value| value != 0).map(|value| *value).collect();
-            let nonet_without_unsolved_spaces_set: HashSet<u8> = HashSet::from_iter(nonet_without_unsolved_spaces.iter().map(|value| *value));
-            if nonet_without_unsolved_spaces_set.len() != nonet_without_unsolved_spaces.len() {
-                return false;
-            }
+        return is_valid;
+    }
+
+    // Bit `n` is set when the value `n` appears in `house`, bit 0 when the house has an empty space
+    fn house_mask(&self, house: House) -> u16 {
+        return SudokuBoard::house_coordinates(house).fold(0, |mask, space| mask | 1 << self.configuration[space]);
+    }
+
+    pub fn missing_in(&self, house: House) -> CandidateSet {
+        if house.index() > 8 {
+            panic!("An invalid house was passed into 'missing_in', it was {:?}", house);
         }
+        return CandidateSet::from_bits(!self.house_mask(house));
+    }
 
-        return true;
+    pub fn missing_in_row(&self, row_index: usize) -> CandidateSet {
+        if row_index > 8 {
+            panic!("An invalid row_index was passed into 'missing_in_row', it was {}", row_index);
+        }
+        return self.missing_in(House::Row(row_index));
+    }
+
+    pub fn missing_in_column(&self, column_index: usize) -> CandidateSet {
+        if column_index > 8 {
+            panic!("An invalid column_index was passed into 'missing_in_column', it was {}", column_index);
+        }
+        return self.missing_in(House::Column(column_index));
+    }
+
+    pub fn missing_in_nonet(&self, nonet_index: usize) -> CandidateSet {
+        if nonet_index > 8 {
+            panic!("An invalid nonet_index was passed into 'missing_in_nonet', it was {}", nonet_index);
+        }
+        return self.missing_in(House::Nonet(nonet_index));
     }
 
     pub fn get_row(&self, row_index: usize) -> Vec<u8> {
@@ -105,29 +802,159 @@ impl SudokuBoard {
         return self.configuration.column(column_index).iter().map(|value| *value).collect();
     }
 
+    // Borrowing counterparts of `get_row`, `get_column` and `get_nonet`
+    pub fn row_view(&self, row_index: usize) -> HouseView<'_> {
+        if row_index > 8 {
+            panic!("An invalid row_index was passed into 'row_view', it was {}", row_index);
+        }
+        return HouseView { board: self, house: House::Row(row_index) };
+    }
+
+    pub fn column_view(&self, column_index: usize) -> HouseView<'_> {
+        if column_index > 8 {
+            panic!("An invalid column_index was passed into 'column_view', it was {}", column_index);
+        }
+        return HouseView { board: self, house: House::Column(column_index) };
+    }
+
+    pub fn nonet_view(&self, nonet_index: usize) -> HouseView<'_> {
+        if nonet_index > 8 {
+            panic!("An invalid nonet_index was passed into 'nonet_view', it was {}", nonet_index);
+        }
+        return HouseView { board: self, house: House::Nonet(nonet_index) };
+    }
+
+    // The 9 values of any house, in the order of `house_coordinates`
+    pub fn get_house(&self, house: House) -> [u8; 9] {
+        let mut values = [0; 9];
+        for (value, space) in values.iter_mut().zip(SudokuBoard::house_coordinates(house)) {
+            *value = self.configuration[space];
+        }
+        return values;
+    }
+
+    pub fn house_view(&self, house: House) -> HouseView<'_> {
+        if house.index() > 8 {
+            panic!("An invalid house was passed into 'house_view', it was {:?}", house);
+        }
+        return HouseView { board: self, house };
+    }
+
+    // The same order as `row_coordinates`, `column_coordinates` or `nonet_coordinates`
+    pub fn house_coordinates(house: House) -> impl Iterator<Item = (usize, usize)> {
+        if house.index() > 8 {
+            panic!("An invalid house was passed into 'house_coordinates', it was {:?}", house);
+        }
+        return (0..=8).map(move |position| house.space(position));
+    }
+
+    // All 27: rows, then columns, then nonets
+    pub fn houses() -> impl Iterator<Item = House> {
+        return (0..=8).map(House::Row)
+            .chain((0..=8).map(House::Column))
+            .chain((0..=8).map(House::Nonet));
+    }
+
+    pub fn nonet_index_of(row_index: usize, column_index: usize) -> usize {
+        return NONET_OF[9 * row_index + column_index];
+    }
+
+    // Left to right
+    pub fn row_coordinates(row_index: usize) -> impl Iterator<Item = (usize, usize)> {
+        if row_index > 8 {
+            panic!("An invalid row_index was passed into 'row_coordinates', it was {}", row_index);
+        }
+        return (0..=8).map(move |column_index| (row_index, column_index));
+    }
+
+    // Top to bottom
+    pub fn column_coordinates(column_index: usize) -> impl Iterator<Item = (usize, usize)> {
+        if column_index > 8 {
+            panic!("An invalid column_index was passed into 'column_coordinates', it was {}", column_index);
+        }
+        return (0..=8).map(move |row_index| (row_index, column_index));
+    }
+
+    // Row-major within the nonet, the top left space first and the bottom right space last
+    pub fn nonet_coordinates(nonet_index: usize) -> impl Iterator<Item = (usize, usize)> {
+        if nonet_index > 8 {
+            panic!("An invalid nonet_index was passed into 'nonet_coordinates', it was {}", nonet_index);
+        }
+        let (starting_row, starting_column) = NONET_ORIGIN[nonet_index];
+        return (0..=8).map(move |offset| (starting_row + offset / 3, starting_column + offset % 3));
+    }
+
+    // The three rows of a band one after the other, each left to right
+    pub fn band_coordinates(band_index: usize) -> impl Iterator<Item = (usize, usize)> {
+        if band_index > 2 {
+            panic!("An invalid band_index was passed into 'band_coordinates', it was {}", band_index);
+        }
+        return (0..27).map(move |offset| (3 * band_index + offset / 9, offset % 9));
+    }
+
+    // The three columns of a stack one after the other, each top to bottom
+    pub fn stack_coordinates(stack_index: usize) -> impl Iterator<Item = (usize, usize)> {
+        if stack_index > 2 {
+            panic!("An invalid stack_index was passed into 'stack_coordinates', it was {}", stack_index);
+        }
+        return (0..27).map(move |offset| (offset % 9, 3 * stack_index + offset / 9));
+    }
+
+    // In the `band_coordinates` order, the same as the band's rows concatenated
+    pub fn get_band(&self, band_index: usize) -> [u8; 27] {
+        if band_index > 2 {
+            panic!("An invalid band_index was passed into 'get_band', it was {}", band_index);
+        }
+        let mut values = [0; 27];
+        for (value, space) in values.iter_mut().zip(SudokuBoard::band_coordinates(band_index)) {
+            *value = self.configuration[space];
+        }
+        return values;
+    }
+
+    // In the `stack_coordinates` order, the same as the stack's columns concatenated
+    pub fn get_stack(&self, stack_index: usize) -> [u8; 27] {
+        if stack_index > 2 {
+            panic!("An invalid stack_index was passed into 'get_stack', it was {}", stack_index);
+        }
+        let mut values = [0; 27];
+        for (value, space) in values.iter_mut().zip(SudokuBoard::stack_coordinates(stack_index)) {
+            *value = self.configuration[space];
+        }
+        return values;
+    }
+
+    // Row-major within the nonet like `nonet_coordinates`, so index 4 is always the center space
     pub fn get_nonet(&self, nonet_index: usize) -> Vec<u8> {
-        let starting_row;
-        let starting_column;
-        match nonet_index {
-            0 => { starting_row = 0; starting_column = 0; },
-            1 => { starting_row = 0; starting_column = 3; },
-            2 => { starting_row = 0; starting_column = 6; },
-            3 => { starting_row = 3; starting_column = 0; },
-            4 => { starting_row = 3; starting_column = 3; },
-            5 => { starting_row = 3; starting_column = 6; },
-            6 => { starting_row = 6; starting_column = 0; },
-            7 => { starting_row = 6; starting_column = 3; },
-            8 => { starting_row = 6; starting_column = 6; },
-            _ => { panic!("An invalid nonet_index was passed into 'get_nonet', it was {}", nonet_index)}
-        }
-
-        return self.configuration.slice((starting_row, starting_column), (3, 3)).iter().map(|value| *value).collect();
+        if nonet_index > 8 {
+            panic!("An invalid nonet_index was passed into 'get_nonet', it was {}", nonet_index);
+        }
+
+        return SudokuBoard::nonet_coordinates(nonet_index).map(|space| self.configuration[space]).collect();
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use std::collections::HashSet;
+    use std::iter::FromIterator;
+    use crate::random::{ RandomSource, SplitMix64 };
+    use crate::strategies::masked_puzzle;
+
+    // The original Vec + HashSet implementation of `all_spaces_valid`, kept to check the bitmask version against
+    fn all_spaces_valid_reference(board: &SudokuBoard) -> bool {
+        for index in 0..=8 {
+            for house in [board.get_row(index), board.get_column(index), board.get_nonet(index)].iter() {
+                let house_without_unsolved_spaces: Vec<u8> = house.iter().filter(|&&value| value != 0).copied().collect();
+                let house_without_unsolved_spaces_set: HashSet<u8> = HashSet::from_iter(house_without_unsolved_spaces.iter().copied());
+                if house_without_unsolved_spaces_set.len() != house_without_unsolved_spaces.len() {
+                    return false;
+                }
+            }
+        }
+        return true;
+    }
 
     #[test]
     fn constructor_works_valid_board() {
@@ -162,62 +989,510 @@ mod test {
             2,0,7, 0,0,0, 6,0,0,
             0,0,0, 0,0,6, 1,3,9
         ];
-        SudokuBoard::new(&invalid_board_value);
+        SudokuBoard::new(&invalid_board_value);
+    }
+
+    #[test]
+    fn new_unchecked_matches_new() {
+        let configuration = [
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,2, 0,0,5, 0,4,0,
+            1,0,8, 0,4,0, 0,0,0,
+            0,0,0, 0,0,0, 4,0,3,
+            0,0,6, 0,5,0, 0,0,1,
+            0,0,0, 0,2,0, 0,0,6,
+            3,0,1, 0,0,0, 0,8,0,
+            2,0,7, 0,0,0, 6,0,0,
+            0,0,0, 0,0,6, 1,3,9
+        ];
+
+        let (board, unchecked_board) = (SudokuBoard::new(&configuration), SudokuBoard::new_unchecked(&configuration));
+
+        assert_eq!(unchecked_board, board);
+        assert_eq!(unchecked_board.empties_bitboard(), board.empties_bitboard());
+        assert!((0..81).all(|index| unchecked_board.is_given(index / 9, index % 9) == board.is_given(index / 9, index % 9)));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn new_unchecked_debug_checks() {
+        let mut configuration = [0; 81];
+        configuration[40] = 10;
+        SudokuBoard::new_unchecked(&configuration);
+    }
+
+    #[test]
+    fn empty_count_tracks_mutations() {
+        let mut board = SudokuBoard::new(&[
+            0,7,3, 8,9,4, 5,1,2,
+            9,1,2, 7,3,5, 4,8,6,
+            8,4,5, 6,1,2, 9,7,3,
+            7,9,8, 2,6,1, 3,5,4,
+            5,2,6, 4,7,3, 8,9,1,
+            1,3,4, 5,8,9, 2,6,7,
+            4,6,9, 0,2,8, 7,3,5,
+            2,8,7, 3,5,6, 1,4,9,
+            3,5,1, 9,4,7, 6,2,0
+        ]);
+        assert_eq!(board.unsolved_count(), 3);
+
+        board.set_value(0, 0, 6); // 0 -> n
+        assert_eq!(board.unsolved_count(), board.get_unsolved_spaces().len());
+        assert_eq!(board.unsolved_count(), 2);
+
+        board.set_value(0, 1, 0); // n -> 0
+        assert_eq!(board.unsolved_count(), board.get_unsolved_spaces().len());
+        assert_eq!(board.unsolved_count(), 3);
+
+        board.set_value(0, 2, 5); // n -> m
+        assert_eq!(board.unsolved_count(), board.get_unsolved_spaces().len());
+        assert_eq!(board.unsolved_count(), 3);
+
+        board.clear_value(8, 8); // 0 -> 0
+        assert_eq!(board.unsolved_count(), board.get_unsolved_spaces().len());
+        assert_eq!(board.unsolved_count(), 3);
+
+        board.set_value(0, 1, 7);
+        board.set_value(6, 3, 1);
+        board.set_value(8, 8, 8);
+        assert!(board.all_spaces_solved());
+        assert_eq!(SudokuBoard::copy(&board).unsolved_count(), 0);
+    }
+
+    #[test]
+    fn empties_bitboard_matches_recount() {
+        for seed in 0..32 {
+            let mut board = masked_puzzle(seed, 30);
+            let mut random = SplitMix64::new(seed);
+            for _ in 0..200 {
+                let index = random.below(81) as usize;
+                let value = if random.below(2) == 0 { 0 } else { random.below(10) as u8 };
+                if random.below(2) == 0 {
+                    board.set_value(index / 9, index % 9, value);
+                } else {
+                    board[(index / 9, index % 9)] = value;
+                }
+
+                let recount: Vec<(usize, usize)> = (0..81).map(|index| (index / 9, index % 9)).filter(|space| board[*space] == 0).collect();
+                assert_eq!(board.unsolved_count(), recount.len());
+                assert_eq!(board.all_spaces_solved(), recount.is_empty());
+                assert_eq!(board.first_unsolved(), recount.first().copied());
+                assert_eq!(board.get_unsolved_spaces(), recount);
+                assert_eq!(board.empties_bitboard(), recount.iter().fold(0, |empties, (row, column)| empties | 1 << (9 * row + column)));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_value_invalid_value() {
+        SudokuBoard::new(&[0; 81]).set_value(0, 0, 10);
+    }
+
+    #[test]
+    fn debug_works() {
+        let mut board = SudokuBoard::new(&[
+            0,7,3, 8,9,4, 5,1,2,
+            9,1,2, 7,3,5, 4,8,6,
+            8,4,5, 0,0,2, 9,7,3,
+            7,9,8, 2,6,1, 3,5,4,
+            5,2,6, 4,7,3, 8,9,1,
+            1,3,4, 5,8,9, 2,6,7,
+            4,6,9, 0,2,8, 7,3,5,
+            2,8,7, 3,5,6, 1,4,9,
+            3,5,1, 9,4,7, 6,2,0
+        ]);
+        board.set_value(0, 0, 7);
+
+        assert_eq!(format!("{:?}", board), "SudokuBoard(\"773894512912735486845002973798261354526473891134589267469028735287356149351947620\", givens: 76, valid: false, solved: false)");
+        assert_eq!(format!("{:#?}", board), [
+            "SudokuBoard(givens: 76, valid: false, solved: false)",
+            "7 7 3 | 8 9 4 | 5 1 2",
+            "9 1 2 | 7 3 5 | 4 8 6",
+            "8 4 5 | . . 2 | 9 7 3",
+            "------+-------+------",
+            "7 9 8 | 2 6 1 | 3 5 4",
+            "5 2 6 | 4 7 3 | 8 9 1",
+            "1 3 4 | 5 8 9 | 2 6 7",
+            "------+-------+------",
+            "4 6 9 | . 2 8 | 7 3 5",
+            "2 8 7 | 3 5 6 | 1 4 9",
+            "3 5 1 | 9 4 7 | 6 2 ."
+        ].join("\n"));
+    }
+
+    #[test]
+    fn try_new_works() {
+        let mut values = [0; 81];
+        values[12] = 3;
+        assert_eq!(SudokuBoard::try_new(&values).unwrap().to_array(), values);
+        assert_eq!(SudokuBoard::try_new(&values).unwrap().to_line(), format!("{}3{}", "0".repeat(12), "0".repeat(68)));
+
+        values[40] = 17;
+        assert_eq!(SudokuBoard::try_new(&values), Err(BoardError::ValueOutOfRange { row: 4, column: 4, value: 17 }));
+    }
+
+    #[test]
+    fn set_row_works() {
+        let mut board = SudokuBoard::new(&[0; 81]);
+
+        board.set_row(4, [1, 2, 3, 4, 5, 6, 7, 8, 9]).unwrap();
+        board.set_column(0, [9, 0, 0, 0, 1, 0, 0, 0, 0]).unwrap();
+        board.set_nonet(8, [0, 0, 0, 0, 0, 0, 0, 0, 5]).unwrap();
+
+        assert_eq!(board.get_row(4), vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(board.get_column(0), vec![9, 0, 0, 0, 1, 0, 0, 0, 0]);
+        assert_eq!(board[(8, 8)], 5);
+        assert_eq!(board.unsolved_count(), 81 - 11);
+        assert_eq!(board.set_row(9, [0; 9]), Err(BoardError::HouseOutOfRange(House::Row(9))));
+    }
+
+    #[test]
+    fn set_row_invalid_value_is_atomic() {
+        let mut board = SudokuBoard::new(&[0; 81]);
+        board.set_value(2, 0, 4);
+
+        let result = board.set_row(2, [1, 2, 3, 4, 10, 6, 7, 8, 9]);
+
+        assert_eq!(result, Err(BoardError::ValueOutOfRange { row: 2, column: 4, value: 10 }));
+        assert_eq!(board.get_row(2), vec![4, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(board.unsolved_count(), 80);
+    }
+
+    #[test]
+    fn set_nonet_locked_givens() {
+        let mut values = [0; 81];
+        values[10] = 5; // (1, 1)
+        let mut board = SudokuBoard::new(&values);
+        assert!(board.is_given(1, 1));
+        assert!(!board.is_given(0, 0));
+
+        // Unlocked givens can be overwritten like any other space
+        board.set_nonet(0, [1, 2, 3, 4, 6, 0, 0, 0, 0]).unwrap();
+        assert_eq!(board[(1, 1)], 6);
+
+        board.set_nonet(0, [0, 0, 0, 0, 5, 0, 0, 0, 0]).unwrap();
+        board.lock_givens();
+        assert_eq!(board.set_nonet(0, [1, 2, 3, 4, 6, 0, 0, 0, 0]), Err(BoardError::LockedGiven { row: 1, column: 1 }));
+        assert_eq!(board.get_nonet(0), vec![0, 0, 0, 0, 5, 0, 0, 0, 0]);
+
+        // Rewriting a given with its own value isn't a change
+        board.set_nonet(0, [1, 2, 3, 4, 5, 6, 7, 8, 9]).unwrap();
+        assert_eq!(board.unsolved_count(), 72);
+
+        board.unlock_givens();
+        board.set_column(1, [0; 9]).unwrap();
+        assert_eq!(board[(1, 1)], 0);
+    }
+
+    #[test]
+    fn reset_to_givens_works() {
+        let puzzle = [
+            0,7,3, 8,9,4, 5,1,2,
+            9,1,2, 7,3,5, 4,8,6,
+            8,4,5, 0,0,2, 9,7,3,
+            7,9,8, 2,6,1, 3,5,4,
+            5,2,6, 4,7,3, 8,9,1,
+            1,3,4, 5,8,9, 2,6,7,
+            4,6,9, 0,2,8, 7,3,5,
+            2,8,7, 3,5,6, 1,4,9,
+            3,5,1, 9,4,7, 6,2,0
+        ];
+        let mut board = SudokuBoard::new(&puzzle);
+        board.lock_givens();
+        board.set_value(0, 0, 6);
+        board.set_value(2, 3, 1);
+        board.set_value(8, 8, 8);
+
+        board.reset_to_givens();
+
+        assert_eq!(board, SudokuBoard::new(&puzzle));
+        assert_eq!(board.unsolved_count(), 5);
+        assert!(board.givens_locked());
+        assert!(board.is_given(0, 1));
+    }
+
+    #[test]
+    fn clear_works() {
+        let mut values = [0; 81];
+        values[0] = 1;
+        values[80] = 9;
+        let mut board = SudokuBoard::new(&values);
+        board.lock_givens();
+
+        board.clear();
+
+        assert_eq!(board, SudokuBoard::new(&[0; 81]));
+        assert_eq!(board.unsolved_count(), 81);
+        assert_eq!(board.get_unsolved_spaces().len(), 81);
+        assert!(!board.givens_locked());
+        assert!(!board.is_given(0, 0));
+    }
+
+    #[test]
+    fn digit_counts_works() {
+        let mut board = SudokuBoard::new(&[
+            0,7,3, 8,9,4, 5,1,2,
+            9,1,2, 7,3,5, 4,8,6,
+            8,4,5, 0,0,2, 9,7,3,
+            7,9,8, 2,6,1, 3,5,4,
+            5,2,6, 4,7,3, 8,9,1,
+            1,3,4, 5,8,9, 2,6,7,
+            4,6,9, 0,2,8, 7,3,5,
+            2,8,7, 3,5,6, 1,4,9,
+            3,5,1, 9,4,7, 6,2,0
+        ]);
+
+        assert_eq!(board.digit_counts(), [5, 7, 9, 9, 9, 9, 7, 9, 8, 9]);
+        assert!(board.digit_complete(2));
+        assert!(!board.digit_complete(6));
+        assert_eq!(board.most_placed_digit(), Some(8));
+        assert_eq!(board.least_placed_digit(), Some(1));
+
+        board.set_value(0, 0, 6);
+        board.set_value(2, 3, 6);
+        assert!(board.digit_complete(6));
+        assert_eq!(board.least_placed_digit(), Some(1));
+
+        board.set_value(2, 4, 1);
+        board.set_value(6, 3, 1);
+        board.set_value(8, 8, 8);
+        assert_eq!(board.digit_counts()[0], 0);
+        assert_eq!((board.most_placed_digit(), board.least_placed_digit()), (None, None));
+    }
+
+    #[test]
+    #[should_panic]
+    fn digit_complete_invalid_digit() {
+        SudokuBoard::new(&[0; 81]).digit_complete(0);
+    }
+
+    #[test]
+    fn get_unsolved_spaces_works() {
+        let board_with_zeroes = SudokuBoard::new(&[
+            0,7,3, 8,9,4, 5,1,2,
+            9,1,2, 7,3,5, 4,8,6,
+            8,4,5, 6,1,2, 9,7,3,
+            7,9,8, 2,6,1, 3,5,4,
+            5,2,6, 4,7,3, 8,9,1,
+            1,3,4, 5,8,9, 2,6,7,
+            4,6,9, 0,2,8, 7,3,5,
+            2,8,7, 3,5,6, 1,4,9,
+            3,5,1, 9,4,7, 6,2,0
+        ]);
+
+        let unsolved_spaces = board_with_zeroes.get_unsolved_spaces();
+
+        assert_eq!(unsolved_spaces, vec![
+            (0, 0),
+            (6, 3),
+            (8, 8)
+        ]);
+    }
+
+    #[test]
+    fn unsolved_navigation_works() {
+        let board = SudokuBoard::new(&[
+            6,7,3, 8,9,4, 5,1,2,
+            9,1,2, 7,3,5, 4,8,6,
+            8,4,5, 6,1,2, 9,7,3,
+            7,9,8, 2,6,1, 3,5,4,
+            5,2,6, 4,7,3, 8,9,1,
+            1,3,4, 5,8,9, 2,6,7,
+            4,6,9, 1,2,8, 7,3,5,
+            2,8,7, 3,5,6, 1,4,9,
+            0,5,1, 9,0,7, 6,2,8
+        ]);
+
+        assert_eq!(board.first_unsolved(), Some((8, 0)));
+        assert_eq!(board.next_unsolved_after((0, 0)), Some((8, 0)));
+        assert_eq!(board.next_unsolved_after((8, 0)), Some((8, 4)));
+        assert_eq!(board.next_unsolved_after((8, 8)), Some((8, 0))); // Wraps around from the final space
+        assert_eq!(board.next_unsolved_after((8, 4)), Some((8, 0)));
+        assert_eq!(board.previous_unsolved_before((8, 4)), Some((8, 0)));
+        assert_eq!(board.previous_unsolved_before((8, 0)), Some((8, 4))); // Wraps around from the first unsolved space
+        assert_eq!(board.previous_unsolved_before((0, 0)), Some((8, 4)));
+    }
+
+    #[test]
+    fn unsolved_navigation_single_space() {
+        let mut spaces = [
+            6,7,3, 8,9,4, 5,1,2,
+            9,1,2, 7,3,5, 4,8,6,
+            8,4,5, 6,1,2, 9,7,3,
+            7,9,8, 2,6,1, 3,5,4,
+            5,2,6, 4,7,3, 8,9,1,
+            1,3,4, 5,8,9, 2,6,7,
+            4,6,9, 1,2,8, 7,3,5,
+            2,8,7, 3,5,6, 1,4,9,
+            3,5,1, 9,4,7, 6,2,8
+        ];
+        let solved_board = SudokuBoard::new(&spaces);
+        spaces[40] = 0;
+        let single_space_board = SudokuBoard::new(&spaces);
+
+        assert_eq!(solved_board.first_unsolved(), None);
+        assert_eq!(solved_board.next_unsolved_after((4, 4)), None);
+        assert_eq!(solved_board.previous_unsolved_before((4, 4)), None);
+        assert_eq!(single_space_board.next_unsolved_after((4, 4)), Some((4, 4)));
+        assert_eq!(single_space_board.previous_unsolved_before((4, 4)), Some((4, 4)));
+    }
+
+    #[test]
+    fn all_spaces_valid_works() {
+        let invalid_board_spaces = [
+            6,7,3, 8,9,4, 5,1,2,
+            9,1,2, 7,3,5, 4,8,6,
+            8,4,5, 6,1,2, 9,7,3,
+            7,9,8, 2,6,1, 3,5,4,
+            5,2,6, 4,7,3, 9,9,1,
+            1,3,4, 5,8,9, 2,6,7,
+            4,6,9, 1,2,8, 7,3,5,
+            2,8,7, 3,5,6, 1,4,9,
+            3,5,1, 9,4,7, 6,2,8
+        ];
+        let valid_board_spaces = [
+            6,7,3, 8,9,4, 5,1,2,
+            9,1,2, 7,3,5, 4,8,6,
+            8,4,5, 6,1,2, 9,7,3,
+            7,9,8, 2,6,1, 3,5,4,
+            5,2,6, 4,7,3, 8,9,1,
+            1,3,4, 5,8,9, 2,6,7,
+            4,6,9, 1,2,8, 7,3,5,
+            2,8,7, 3,5,6, 1,4,9,
+            3,5,1, 9,4,7, 6,2,8
+        ];
+
+        let invalid_board = SudokuBoard::new(&invalid_board_spaces);
+        let valid_board = SudokuBoard::new(&valid_board_spaces);
+
+        assert_eq!(invalid_board.all_spaces_valid(), false);
+        assert_eq!(valid_board.all_spaces_valid(), true);
     }
 
     #[test]
-    fn get_unsolved_spaces_works() {
-        let board_with_zeroes = SudokuBoard::new(&[
-            0,7,3, 8,9,4, 5,1,2,
+    fn all_spaces_valid_matches_reference() {
+        let solved_spaces = [
+            6,7,3, 8,9,4, 5,1,2,
             9,1,2, 7,3,5, 4,8,6,
             8,4,5, 6,1,2, 9,7,3,
             7,9,8, 2,6,1, 3,5,4,
             5,2,6, 4,7,3, 8,9,1,
             1,3,4, 5,8,9, 2,6,7,
-            4,6,9, 0,2,8, 7,3,5,
+            4,6,9, 1,2,8, 7,3,5,
             2,8,7, 3,5,6, 1,4,9,
-            3,5,1, 9,4,7, 6,2,0
-        ]);
+            3,5,1, 9,4,7, 6,2,8
+        ];
+        let mut boards = vec![SudokuBoard::new(&solved_spaces), SudokuBoard::new(&[0; 81])];
 
-        let unsolved_spaces = board_with_zeroes.get_unsolved_spaces();
+        let mut with_zeroes = solved_spaces;
+        with_zeroes[0] = 0;
+        with_zeroes[40] = 0;
+        with_zeroes[80] = 0;
+        boards.push(SudokuBoard::new(&with_zeroes));
 
-        assert_eq!(unsolved_spaces, vec![
-            (0, 0),
-            (6, 3),
-            (8, 8)
-        ]);
+        let mut row_duplicate = [0; 81];
+        row_duplicate[0] = 5;
+        row_duplicate[8] = 5;
+        boards.push(SudokuBoard::new(&row_duplicate));
+
+        let mut column_duplicate = [0; 81];
+        column_duplicate[4] = 7;
+        column_duplicate[76] = 7;
+        boards.push(SudokuBoard::new(&column_duplicate));
+
+        let mut nonet_duplicate = [0; 81];
+        nonet_duplicate[30] = 2;
+        nonet_duplicate[50] = 2;
+        boards.push(SudokuBoard::new(&nonet_duplicate));
+
+        let mut swapped = solved_spaces;
+        swapped.swap(0, 1); // Only breaks the columns
+        boards.push(SudokuBoard::new(&swapped));
+
+        for board in boards.iter() {
+            assert_eq!(board.all_spaces_valid(), all_spaces_valid_reference(board));
+            assert_eq!(board.validate().is_valid(), all_spaces_valid_reference(board));
+        }
+        assert_eq!(boards.iter().filter(|board| board.all_spaces_valid()).count(), 3);
     }
 
     #[test]
-    fn all_spaces_valid_works() {
-        let invalid_board_spaces = [
+    fn is_solved_works() {
+        let solved_spaces = [
             6,7,3, 8,9,4, 5,1,2,
             9,1,2, 7,3,5, 4,8,6,
             8,4,5, 6,1,2, 9,7,3,
             7,9,8, 2,6,1, 3,5,4,
-            5,2,6, 4,7,3, 9,9,1,
+            5,2,6, 4,7,3, 8,9,1,
             1,3,4, 5,8,9, 2,6,7,
             4,6,9, 1,2,8, 7,3,5,
             2,8,7, 3,5,6, 1,4,9,
             3,5,1, 9,4,7, 6,2,8
         ];
-        let valid_board_spaces = [
+        let mut duplicated_spaces = solved_spaces;
+        duplicated_spaces[42] = 9;
+        let mut incomplete_spaces = solved_spaces;
+        incomplete_spaces[80] = 0;
+
+        assert!(SudokuBoard::new(&solved_spaces).is_solved());
+        assert!(!SudokuBoard::new(&duplicated_spaces).is_solved());
+        assert!(!SudokuBoard::new(&incomplete_spaces).is_solved());
+    }
+
+    #[test]
+    fn validate_works() {
+        let invalid_board = SudokuBoard::new(&[
             6,7,3, 8,9,4, 5,1,2,
             9,1,2, 7,3,5, 4,8,6,
             8,4,5, 6,1,2, 9,7,3,
             7,9,8, 2,6,1, 3,5,4,
-            5,2,6, 4,7,3, 8,9,1,
+            5,2,6, 4,7,3, 9,9,1,
             1,3,4, 5,8,9, 2,6,7,
             4,6,9, 1,2,8, 7,3,5,
             2,8,7, 3,5,6, 1,4,9,
             3,5,1, 9,4,7, 6,2,8
-        ];
+        ]);
 
-        let invalid_board = SudokuBoard::new(&invalid_board_spaces);
-        let valid_board = SudokuBoard::new(&valid_board_spaces);
+        let report = invalid_board.validate();
 
-        assert_eq!(invalid_board.all_spaces_valid(), false);
-        assert_eq!(valid_board.all_spaces_valid(), true);
+        assert!(!report.is_valid());
+        assert_eq!(report.conflicts, vec![
+            Conflict { house: House::Row(4), value: 9, spaces: vec![(4, 6), (4, 7)] },
+            Conflict { house: House::Column(6), value: 9, spaces: vec![(2, 6), (4, 6)] },
+            Conflict { house: House::Nonet(5), value: 9, spaces: vec![(4, 6), (4, 7)] }
+        ]);
+    }
+
+    #[test]
+    fn missing_in_house_works() {
+        let board = SudokuBoard::new(&[
+            0,7,3, 8,9,4, 5,1,2,
+            9,1,2, 7,3,5, 4,8,6,
+            8,4,5, 0,0,2, 9,7,3,
+            0,0,0, 0,0,0, 0,0,0,
+            5,2,6, 4,7,3, 8,9,1,
+            1,3,4, 5,8,9, 2,6,7,
+            4,6,9, 0,2,8, 7,3,5,
+            2,8,7, 3,5,6, 1,4,9,
+            3,5,1, 9,4,7, 6,2,0
+        ]);
+
+        assert_eq!(board.missing_in_row(0).iter().collect::<Vec<u8>>(), vec![6]);
+        assert_eq!(board.missing_in_row(2).iter().collect::<Vec<u8>>(), vec![1, 6]);
+        assert!(board.missing_in_row(1).is_empty());
+        assert_eq!(board.missing_in_row(3), CandidateSet::ALL);
+        assert_eq!(board.missing_in_column(3).iter().collect::<Vec<u8>>(), vec![1, 2, 6]);
+        assert!(board.missing_in_column(1).iter().eq(std::iter::once(9)));
+        assert_eq!(board.missing_in_nonet(1).iter().collect::<Vec<u8>>(), vec![1, 6]);
+        assert!(board.missing_in_nonet(2).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn missing_in_nonet_invalid_index() {
+        SudokuBoard::new(&[0; 81]).missing_in_nonet(9);
     }
 
     #[test]
@@ -284,6 +1559,139 @@ mod test {
         ]);
     }
 
+    #[test]
+    fn nonet_tables_match_formula() {
+        for row_index in 0..=8 {
+            for column_index in 0..=8 {
+                let nonet_index = 3 * ((9 * row_index + column_index) / 27) + ((9 * row_index + column_index) / 3 % 3);
+                assert_eq!(SudokuBoard::nonet_index_of(row_index, column_index), nonet_index);
+
+                let (starting_row, starting_column) = NONET_ORIGIN[nonet_index];
+                assert_eq!((starting_row, starting_column), (row_index / 3 * 3, column_index / 3 * 3));
+            }
+        }
+    }
+
+    #[test]
+    fn peers_table_works() {
+        for (index, peers) in PEERS.iter().enumerate() {
+            let (row_index, column_index) = (index / 9, index % 9);
+            let mut expected: Vec<usize> = House::Row(row_index).spaces().into_iter()
+                .chain(House::Column(column_index).spaces())
+                .chain(House::Nonet(SudokuBoard::nonet_index_of(row_index, column_index)).spaces())
+                .map(|(row, column)| 9 * row + column)
+                .filter(|other| *other != index)
+                .collect();
+            expected.sort_unstable();
+            expected.dedup();
+            assert_eq!(peers.to_vec(), expected);
+        }
+    }
+
+    #[test]
+    fn house_coordinates_work() {
+        let mut covered = [0; 81];
+        for nonet_index in 0..=8 {
+            let coordinates: Vec<(usize, usize)> = SudokuBoard::nonet_coordinates(nonet_index).collect();
+            assert_eq!(coordinates, House::Nonet(nonet_index).spaces());
+            assert!(coordinates.iter().all(|&(row_index, column_index)| SudokuBoard::nonet_index_of(row_index, column_index) == nonet_index));
+            for (row_index, column_index) in coordinates {
+                covered[9 * row_index + column_index] += 1;
+            }
+        }
+        assert!(covered.iter().all(|count| *count == 1));
+
+        assert_eq!(SudokuBoard::nonet_coordinates(4).collect::<Vec<(usize, usize)>>(), vec![(3, 3), (3, 4), (3, 5), (4, 3), (4, 4), (4, 5), (5, 3), (5, 4), (5, 5)]);
+        assert_eq!(SudokuBoard::row_coordinates(2).nth(4), Some((2, 4)));
+        assert_eq!(SudokuBoard::column_coordinates(2).nth(4), Some((4, 2)));
+
+        // `get_nonet` follows the same order
+        let board = SudokuBoard::new(&[
+            6,7,3, 8,9,4, 5,1,2,
+            9,1,2, 7,3,5, 4,8,6,
+            8,4,5, 6,1,2, 9,7,3,
+            7,9,8, 2,6,1, 3,5,4,
+            5,2,6, 4,7,3, 8,9,1,
+            1,3,4, 5,8,9, 2,6,7,
+            4,6,9, 1,2,8, 7,3,5,
+            2,8,7, 3,5,6, 1,4,9,
+            3,5,1, 9,4,7, 6,2,8
+        ]);
+        for nonet_index in 0..=8 {
+            let values: Vec<u8> = SudokuBoard::nonet_coordinates(nonet_index).map(|space| board[space]).collect();
+            assert_eq!(board.get_nonet(nonet_index), values);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn nonet_coordinates_invalid_index() {
+        SudokuBoard::nonet_coordinates(9).count();
+    }
+
+    #[test]
+    fn house_views_work() {
+        let board = SudokuBoard::new(&[
+            6,7,3, 8,9,4, 5,1,2,
+            9,1,2, 7,3,5, 4,8,6,
+            8,4,5, 6,1,2, 9,7,3,
+            7,9,8, 2,6,1, 3,5,4,
+            5,2,6, 4,7,3, 8,9,1,
+            1,3,4, 5,8,9, 2,6,7,
+            4,6,9, 1,2,8, 7,3,5,
+            2,8,7, 3,5,6, 1,4,9,
+            3,5,1, 9,4,7, 6,2,0
+        ]);
+
+        for index in 0..=8 {
+            assert_eq!(board.row_view(index).iter().collect::<Vec<u8>>(), board.get_row(index));
+            assert_eq!(board.column_view(index).iter().collect::<Vec<u8>>(), board.get_column(index));
+            assert_eq!(board.nonet_view(index).iter().collect::<Vec<u8>>(), board.get_nonet(index));
+        }
+
+        let nonet = board.nonet_view(4);
+        assert_eq!(nonet.house(), House::Nonet(4));
+        assert_eq!(nonet.get(4), 7);
+        assert_eq!(nonet.iter().len(), 9);
+        assert!(!board.row_view(8).contains(8));
+        let mut sum = 0;
+        for value in board.column_view(8) {
+            sum += value as u32;
+        }
+        assert_eq!(sum, 37);
+    }
+
+    #[test]
+    #[should_panic]
+    fn row_view_invalid_index() {
+        SudokuBoard::new(&[0; 81]).row_view(9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn house_view_invalid_position() {
+        SudokuBoard::new(&[0; 81]).nonet_view(0).get(9);
+    }
+
+    #[test]
+    fn house_labels_round_trip() {
+        assert_eq!((House::Row(0).label(), House::Column(4).label(), House::Nonet(8).label()), (String::from("R1"), String::from("C5"), String::from("B9")));
+        for index in 0..9 {
+            for house in [House::Row(index), House::Column(index), House::Nonet(index)].iter() {
+                assert_eq!(House::from_label(&house.label()), Some(*house));
+            }
+        }
+        for label in ["", "R", "R0", "R10", "X1", "r1", "C-1"].iter() {
+            assert_eq!(House::from_label(label), None, "{}", label);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_nonet_invalid_index() {
+        SudokuBoard::new(&[0; 81]).get_nonet(9);
+    }
+
     #[test]
     fn get_nonet_works() {
         let valid_board = SudokuBoard::new(&[
@@ -304,15 +1712,269 @@ mod test {
         }
 
         assert_eq!(all_nonets, vec![
-            vec![ 6,9,8, 7,1,4, 3,2,5 ],
-            vec![ 8,7,6, 9,3,1, 4,5,2 ],
-            vec![ 5,4,9, 1,8,7, 2,6,3 ],
-            vec![ 7,5,1, 9,2,3, 8,6,4 ],
-            vec![ 2,4,5, 6,7,8, 1,3,9 ],
-            vec![ 3,8,2, 5,9,6, 4,1,7 ],
-            vec![ 4,2,3, 6,8,5, 9,7,1 ],
-            vec![ 1,3,9, 2,5,4, 8,6,7 ],
-            vec![ 7,1,6, 3,4,2, 5,9,8 ]
+            vec![ 6,7,3, 9,1,2, 8,4,5 ],
+            vec![ 8,9,4, 7,3,5, 6,1,2 ],
+            vec![ 5,1,2, 4,8,6, 9,7,3 ],
+            vec![ 7,9,8, 5,2,6, 1,3,4 ],
+            vec![ 2,6,1, 4,7,3, 5,8,9 ],
+            vec![ 3,5,4, 8,9,1, 2,6,7 ],
+            vec![ 4,6,9, 2,8,7, 3,5,1 ],
+            vec![ 1,2,8, 3,5,6, 9,4,7 ],
+            vec![ 7,3,5, 1,4,9, 6,2,8 ]
+        ]);
+    }
+
+    #[test]
+    fn digit_accessors_work() {
+        let mut board = SudokuBoard::new(&[
+            0,7,3, 8,9,4, 5,1,2,
+            9,1,2, 7,3,5, 4,8,6,
+            8,4,5, 0,0,2, 9,7,3,
+            7,9,8, 2,6,1, 3,5,4,
+            5,2,6, 4,7,3, 8,9,1,
+            1,3,4, 5,8,9, 2,6,7,
+            4,6,9, 0,2,8, 7,3,5,
+            2,8,7, 3,5,6, 1,4,9,
+            3,5,1, 9,4,7, 6,2,0
+        ]);
+
+        assert_eq!(board.digit(0, 0), None);
+        assert_eq!(board.digit(0, 1), Digit::new(7));
+        assert_eq!(board.to_digits().iter().map(|digit| digit.map_or(0, Digit::get)).collect::<Vec<u8>>(), board.to_array().to_vec());
+
+        board.set_digit(0, 0, Digit::new(6));
+        assert_eq!(board[(0, 0)], 6);
+        assert_eq!(board.unsolved_count(), 4);
+        board.set_digit(0, 1, None);
+        assert_eq!(board[(0, 1)], 0);
+        assert_eq!(board.digit(0, 1), None);
+        assert_eq!(board.unsolved_count(), 5);
+        assert!(board.is_given(0, 1));
+    }
+
+    #[test]
+    fn u8_and_digit_setters_agree() {
+        let mut by_value = SudokuBoard::new(&[0; 81]);
+        let mut by_digit = SudokuBoard::new(&[0; 81]);
+
+        for (index, value) in [5u8, 0, 9, 1, 0, 5].iter().enumerate() {
+            by_value.set_value(0, index, *value);
+            by_digit.set_digit(0, index, Digit::new(*value));
+        }
+        by_value.clear_value(0, 2);
+        by_digit.set_digit(0, 2, None);
+
+        assert_eq!(by_value.to_array(), by_digit.to_array());
+        assert_eq!(by_value.empties_bitboard(), by_digit.empties_bitboard());
+        assert_eq!(by_value.validate(), by_digit.validate());
+        assert!(!by_digit.all_spaces_valid());
+    }
+
+    #[test]
+    fn bands_and_stacks_work() {
+        let board = SudokuBoard::new(&[
+            6,7,3, 8,9,4, 5,1,2,
+            9,1,2, 7,3,5, 4,8,6,
+            8,4,5, 6,1,2, 9,7,3,
+            7,9,8, 2,6,1, 3,5,4,
+            5,2,6, 4,7,3, 8,9,1,
+            1,3,4, 5,8,9, 2,6,7,
+            4,6,9, 1,2,8, 7,3,5,
+            2,8,7, 3,5,6, 1,4,9,
+            3,5,1, 9,4,7, 6,2,8
+        ]);
+
+        for chute_index in 0..=2 {
+            let band: HashSet<(usize, usize)> = SudokuBoard::band_coordinates(chute_index).collect();
+            let stack: HashSet<(usize, usize)> = SudokuBoard::stack_coordinates(chute_index).collect();
+            assert_eq!(band, (0..81).map(|index| (index / 9, index % 9)).filter(|space| space.0 / 3 == chute_index).collect());
+            assert_eq!(stack, (0..81).map(|index| (index / 9, index % 9)).filter(|space| space.1 / 3 == chute_index).collect());
+
+            let rows: Vec<u8> = (3 * chute_index..3 * chute_index + 3).flat_map(|row_index| board.get_row(row_index)).collect();
+            let columns: Vec<u8> = (3 * chute_index..3 * chute_index + 3).flat_map(|column_index| board.get_column(column_index)).collect();
+            assert_eq!(board.get_band(chute_index).to_vec(), rows);
+            assert_eq!(board.get_stack(chute_index).to_vec(), columns);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_band_invalid_index() {
+        SudokuBoard::new(&[0; 81]).get_band(3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn stack_coordinates_invalid_index() {
+        SudokuBoard::stack_coordinates(3).count();
+    }
+
+    #[test]
+    fn positions_of_works() {
+        let board = SudokuBoard::new(&[
+            0,7,3, 8,9,4, 5,1,2,
+            9,1,2, 7,3,5, 4,8,6,
+            8,4,5, 0,0,2, 9,7,3,
+            7,9,8, 2,6,1, 3,5,4,
+            5,2,6, 4,7,3, 8,9,1,
+            1,3,4, 5,8,9, 2,6,7,
+            4,6,9, 0,2,8, 7,3,5,
+            2,8,7, 3,5,6, 1,4,9,
+            3,5,1, 9,4,7, 6,2,0
+        ]);
+
+        assert_eq!(board.positions_of(9), vec![(0, 4), (1, 0), (2, 6), (3, 1), (4, 7), (5, 5), (6, 2), (7, 8), (8, 3)]);
+        assert_eq!(SudokuBoard::new(&[0; 81]).positions_of(5), Vec::new());
+        assert_eq!(SudokuBoard::new(&[0; 81]).positions_bitboard(5), 0);
+        assert_eq!(board.positions_of(6).len(), 7);
+
+        for value in 1..=9 {
+            let bitboard = board.positions_of(value).iter().fold(0u128, |bitboard, (row, column)| bitboard | 1 << (9 * row + column));
+            assert_eq!(board.positions_bitboard(value), bitboard);
+            assert!(board.positions_of(value).iter().all(|space| board[*space] == value));
+        }
+        assert_eq!((1..=9).map(|value| board.positions_bitboard(value)).fold(board.empties_bitboard(), |all, positions| all | positions), ALL_SPACES);
+    }
+
+    #[test]
+    #[should_panic]
+    fn positions_of_invalid_value() {
+        SudokuBoard::new(&[0; 81]).positions_of(0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn conflict_serde_round_trip() {
+        let conflict = Conflict { house: House::Nonet(4), value: 7, spaces: vec![(3, 3), (5, 4)] };
+        let json = serde_json::to_string(&conflict).unwrap();
+
+        assert_eq!(json, "{\"house\":\"B5\",\"value\":7,\"spaces\":[[3,3],[5,4]]}");
+        assert_eq!(serde_json::from_str::<Conflict>(&json).unwrap(), conflict);
+        assert!(serde_json::from_str::<Conflict>(&json.replace("B5", "X5")).is_err());
+    }
+
+    #[test]
+    fn observer_sees_each_change() {
+        let events = std::sync::Arc::new(Mutex::new(Vec::new()));
+        let recorder = std::sync::Arc::clone(&events);
+        let mut board = SudokuBoard::new(&[0; 81]);
+        board.set_observer(move |change| recorder.lock().unwrap().push(change));
+
+        board.set_value(0, 0, 5);
+        board.set_value(0, 0, 5);
+        board.set_row(1, [1, 2, 0, 0, 0, 0, 0, 0, 3]).unwrap();
+        assert!(board.set_row(1, [4, 0, 0, 10, 0, 0, 0, 0, 0]).is_err());
+        assert!(board.set_row(9, [0; 9]).is_err());
+        board.clear_value(1, 1);
+        board.set_value(0, 0, 5);
+
+        let change = |row, column, previous_value, value| CellChanged { row, column, previous_value, value };
+        assert_eq!(*events.lock().unwrap(), vec![
+            change(0, 0, 0, 5),
+            change(1, 0, 0, 1),
+            change(1, 1, 0, 2),
+            change(1, 8, 0, 3),
+            change(1, 1, 2, 0)
+        ]);
+
+        events.lock().unwrap().clear();
+        board.clear();
+        assert_eq!(*events.lock().unwrap(), vec![change(0, 0, 5, 0), change(1, 0, 1, 0), change(1, 8, 3, 0)]);
+    }
+
+    #[test]
+    fn index_writes_are_unobserved() {
+        let events = std::sync::Arc::new(Mutex::new(Vec::new()));
+        let recorder = std::sync::Arc::clone(&events);
+        let mut board = SudokuBoard::new(&[0; 81]);
+        board.set_observer(move |change| recorder.lock().unwrap().push(change));
+
+        board[(2, 2)] = 7;
+        assert_eq!(board.unsolved_count(), 80);
+        board.set_value(0, 0, 5);
+        board[(2, 2)] = 0;
+        board.set_value(0, 1, 6);
+
+        assert_eq!(board.unsolved_count(), 79);
+        assert_eq!(*events.lock().unwrap(), vec![
+            CellChanged { row: 0, column: 0, previous_value: 0, value: 5 },
+            CellChanged { row: 0, column: 1, previous_value: 0, value: 6 }
+        ]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn index_write_debug_checks() {
+        let mut board = SudokuBoard::new(&[0; 81]);
+        board[(2, 2)] = 12;
+        board.unsolved_count();
+    }
+
+    #[test]
+    fn is_consistent_extension_of_works() {
+        let base = SudokuBoard::new(&[
+            0,7,3, 8,9,4, 5,1,2,
+            9,1,2, 7,3,5, 4,8,6,
+            8,4,5, 0,0,2, 9,7,3,
+            7,9,8, 2,6,1, 3,5,4,
+            5,2,6, 4,7,3, 8,9,1,
+            1,3,4, 5,8,9, 2,6,7,
+            4,6,9, 0,2,8, 7,3,5,
+            2,8,7, 3,5,6, 1,4,9,
+            3,5,1, 9,4,7, 6,2,0
+        ]);
+        let mut extension = SudokuBoard::copy(&base);
+        extension.set_value(0, 0, 6);
+        extension.set_value(2, 4, 9); // Breaks the rules, which isn't this check's business
+
+        assert_eq!(extension.is_consistent_extension_of(&base), Ok(()));
+        assert_eq!(base.is_consistent_extension_of(&base), Ok(()));
+        assert_eq!(base.is_consistent_extension_of(&SudokuBoard::new(&[0; 81])), Ok(()));
+        assert_eq!(base.is_consistent_extension_of(&extension), Err(InconsistentAt { row: 0, column: 0, expected: 6, found: 0 }));
+
+        extension.set_value(4, 4, 1);
+        extension.clear_value(8, 0);
+        assert_eq!(extension.is_consistent_extension_of(&base), Err(InconsistentAt { row: 4, column: 4, expected: 7, found: 1 }));
+        assert_eq!(SudokuBoard::new(&[0; 81]).is_consistent_extension_of(&base).unwrap_err().to_string(), "(0, 1) should be 7, it was 0");
+    }
+
+    #[test]
+    fn houses_match_accessors() {
+        let board = SudokuBoard::new(&[
+            0,7,3, 8,9,4, 5,1,2,
+            9,1,2, 7,3,5, 4,8,6,
+            8,4,5, 0,0,2, 9,7,3,
+            7,9,8, 2,6,1, 3,5,4,
+            5,2,6, 4,7,3, 8,9,1,
+            1,3,4, 5,8,9, 2,6,7,
+            4,6,9, 0,2,8, 7,3,5,
+            2,8,7, 3,5,6, 1,4,9,
+            3,5,1, 9,4,7, 6,2,0
         ]);
+
+        for index in 0..=8 {
+            assert_eq!(board.get_house(House::Row(index)).to_vec(), board.get_row(index));
+            assert_eq!(board.get_house(House::Column(index)).to_vec(), board.get_column(index));
+            assert_eq!(board.get_house(House::Nonet(index)).to_vec(), board.get_nonet(index));
+            assert!(SudokuBoard::house_coordinates(House::Row(index)).eq(SudokuBoard::row_coordinates(index)));
+            assert!(SudokuBoard::house_coordinates(House::Column(index)).eq(SudokuBoard::column_coordinates(index)));
+            assert!(SudokuBoard::house_coordinates(House::Nonet(index)).eq(SudokuBoard::nonet_coordinates(index)));
+            assert_eq!(board.missing_in(House::Nonet(index)), board.missing_in_nonet(index));
+            assert!(board.house_view(House::Column(index)).iter().eq(board.column_view(index).iter()));
+        }
+
+        let mut coverage = [0; 81];
+        for house in SudokuBoard::houses() {
+            SudokuBoard::house_coordinates(house).for_each(|(row_index, column_index)| coverage[9 * row_index + column_index] += 1);
+        }
+        assert_eq!(SudokuBoard::houses().count(), 27);
+        assert!(coverage.iter().all(|count| *count == 3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn house_coordinates_invalid_house() {
+        SudokuBoard::house_coordinates(House::Nonet(9)).count();
     }
-}
\ No newline at end of file
+}
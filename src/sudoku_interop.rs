@@ -0,0 +1,95 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt::{ self, Display, Formatter };
+use sudoku::Sudoku;
+use crate::sudoku_board::SudokuBoard;
+
+// `sudoku::Sudoku` only holds clues, so every filled space crosses over as a given and back again
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConversionError {
+    EnteredValue { row: usize, column: usize },
+    ValueOutOfRange { row: usize, column: usize, value: u8 }
+}
+
+impl Display for ConversionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ConversionError::EnteredValue { row, column } => write!(f, "({}, {}) was entered rather than given, a Sudoku only holds clues", row, column),
+            ConversionError::ValueOutOfRange { row, column, value } => write!(f, "All values must be [0..9] inclusive, ({}, {}) was {}", row, column, value)
+        }
+    }
+}
+
+impl Error for ConversionError {}
+
+impl From<Sudoku> for SudokuBoard {
+    fn from(sudoku: Sudoku) -> SudokuBoard {
+        return SudokuBoard::new(&sudoku.to_bytes()); // `Sudoku` keeps its values in [0..9], so this can't panic
+    }
+}
+
+impl TryFrom<&SudokuBoard> for Sudoku {
+    type Error = ConversionError;
+
+    fn try_from(board: &SudokuBoard) -> Result<Sudoku, ConversionError> {
+        let values = board.to_array();
+        for (index, value) in values.iter().enumerate() {
+            let (row, column) = (index / 9, index % 9);
+            if *value > 9 {
+                return Err(ConversionError::ValueOutOfRange { row, column, value: *value });
+            }
+            if *value != 0 && !board.is_given(row, column) {
+                return Err(ConversionError::EnteredValue { row, column });
+            }
+        }
+
+        return Ok(Sudoku::from_bytes(values).expect("Values were checked to be [0..9] inclusive"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sudoku_solver::SudokuSolver;
+    use crate::test_fixtures::{ classic, easy, hard };
+
+    #[test]
+    fn board_round_trip_keeps_givens() {
+        for board in [easy(), hard(), classic()].iter() {
+            let sudoku = Sudoku::try_from(board).unwrap();
+            let round_tripped = SudokuBoard::from(sudoku);
+
+            assert_eq!(round_tripped, *board);
+            for index in 0..81 {
+                assert_eq!(round_tripped.is_given(index / 9, index % 9), board.is_given(index / 9, index % 9));
+            }
+        }
+    }
+
+    #[test]
+    fn sudoku_round_trip_works() {
+        for board in [easy(), hard(), classic()].iter() {
+            let sudoku = Sudoku::from_bytes(board.to_array()).unwrap();
+
+            assert_eq!(Sudoku::try_from(&SudokuBoard::from(sudoku)).unwrap().to_bytes(), sudoku.to_bytes());
+        }
+    }
+
+    #[test]
+    fn entered_values_are_refused() {
+        let mut board = hard();
+        board.set_value(0, 0, 4);
+
+        assert_eq!(Sudoku::try_from(&board).err(), Some(ConversionError::EnteredValue { row: 0, column: 0 }));
+    }
+
+    #[test]
+    fn both_solvers_agree() {
+        for board in [hard(), classic()].iter() {
+            let solution = Sudoku::try_from(board).unwrap().solve_unique().unwrap();
+
+            assert_eq!(SudokuBoard::from(solution), SudokuSolver::new(board).solve());
+        }
+    }
+}
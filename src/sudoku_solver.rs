@@ -1,33 +1,360 @@
-use std::cell::RefCell;
-use std::collections::{ HashMap, HashSet };
-use std::iter::{ FromIterator, Iterator };
+use std::error::Error;
+use std::fmt::{ self, Display, Formatter };
+use std::ops::Deref;
+use std::sync::{ Arc, Mutex, OnceLock };
+use std::sync::atomic::{ AtomicBool, AtomicU32, AtomicU64, Ordering };
+use crate::solver_config::{ Engine, SolverConfig };
+use crate::peers::PEERS;
 use crate::sudoku_board::SudokuBoard;
+use crate::techniques::UnsolvableReason;
+use crate::trace::SolveStep;
+use crate::uniqueness::{ is_unique, Uniqueness };
+
+// Fixed capacity list of spaces, a board never has more than 81 of them so this never needs to allocate
+#[derive(Debug, Clone, Copy)]
+pub struct SpaceList {
+    spaces: [(usize, usize); 81],
+    length: usize
+}
+
+impl SpaceList {
+    pub fn new() -> SpaceList {
+        return SpaceList {
+            spaces: [(0, 0); 81],
+            length: 0
+        }
+    }
+
+    pub fn push(&mut self, space: (usize, usize)) {
+        if self.length == 81 {
+            panic!("A SpaceList can't hold more than 81 spaces");
+        }
+
+        self.spaces[self.length] = space;
+        self.length += 1;
+    }
+}
+
+impl Default for SpaceList {
+    fn default() -> SpaceList {
+        return SpaceList::new();
+    }
+}
+
+impl Deref for SpaceList {
+    type Target = [(usize, usize)];
+
+    fn deref(&self) -> &Self::Target {
+        &self.spaces[..self.length]
+    }
+}
+
+impl PartialEq for SpaceList {
+    fn eq(&self, other: &SpaceList) -> bool {
+        **self == **other
+    }
+}
+
+impl PartialEq<Vec<(usize, usize)>> for SpaceList {
+    fn eq(&self, other: &Vec<(usize, usize)>) -> bool {
+        **self == **other
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveError {
+    InvalidBoard, // The starting board already breaks the rules
+    NoSolution(Option<UnsolvableReason>), // The contradiction singles run into, if they find one before stalling
+    Cancelled,
+    MultipleSolutions, // Only raised where the answer depends on there being exactly one solution
+    GivensChanged // A board to start the search from doesn't keep every given of the solver's board
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::InvalidBoard => write!(f, "An invalid starting board configuration was passed."),
+            SolveError::NoSolution(None) => write!(f, "This board is unsolvable"),
+            SolveError::NoSolution(Some(reason)) => write!(f, "This board is unsolvable: {}", reason),
+            SolveError::Cancelled => write!(f, "The solve was cancelled"),
+            SolveError::MultipleSolutions => write!(f, "This board has more than one solution"),
+            SolveError::GivensChanged => write!(f, "The board to start from doesn't keep the original givens")
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+// Engines only touch the shared atomics once per this many steps, keeping the hot loop free of synchronization
+const CONTROL_INTERVAL: u64 = 256;
+
+// Shared between a running search and whoever is watching it. Clones refer to the same search.
+#[derive(Debug, Clone, Default)]
+pub struct SolveControl {
+    cancelled: Arc<AtomicBool>,
+    iterations: Arc<AtomicU64>,
+    first_solution: Arc<OnceLock<u64>>
+}
+
+impl SolveControl {
+    pub fn new() -> SolveControl {
+        return SolveControl::default();
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        return self.cancelled.load(Ordering::Relaxed);
+    }
+
+    // Values placed by the search so far, lagging behind by less than `CONTROL_INTERVAL` while it runs
+    pub fn iterations(&self) -> u64 {
+        return self.iterations.load(Ordering::Relaxed);
+    }
+
+    // `iterations` when `solve_unique` found its first solution, so `iterations() - first_solution_iterations()` is
+    // what proving there's no second one cost. `None` until then and for every other kind of solve.
+    pub fn first_solution_iterations(&self) -> Option<u64> {
+        return self.first_solution.get().copied();
+    }
+
+    fn tick(&self, pending: &mut u64) -> Result<(), SolveError> {
+        *pending += 1;
+        if *pending == CONTROL_INTERVAL {
+            self.flush(pending);
+            if self.is_cancelled() {
+                return Err(SolveError::Cancelled);
+            }
+        }
+        return Ok(());
+    }
+
+    fn flush(&self, pending: &mut u64) {
+        self.iterations.fetch_add(*pending, Ordering::Relaxed);
+        *pending = 0;
+    }
+}
+
+// The state of the board after `resolve_after_change`, and whether the solution from before the change could be kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Resolution {
+    pub uniqueness: Uniqueness,
+    pub reused_solution: bool
+}
+
+// Where the values of a warm started solve came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolveStats {
+    pub propagated_cells: usize, // Filled in the board the search started from but not in the solver's board
+    pub searched_cells: usize, // Left for the search to fill
+    pub iterations: u64 // Values the search placed, see `SolveControl::iterations`
+}
+
+// The working state of a search: the board being filled in, the values tried at every depth, and a control to tick.
+// One can be allocated once and handed to any number of `solve_with_scratch` calls on any puzzles, every call resets
+// what it uses first.
+pub struct SearchScratch {
+    board: SudokuBoard,
+    attempted_values: [u16; 81],
+    control: SolveControl
+}
+
+impl Default for SearchScratch {
+    fn default() -> SearchScratch {
+        return SearchScratch { board: SudokuBoard::new(&[0; 81]), attempted_values: [0; 81], control: SolveControl::new() };
+    }
+}
+
+impl SearchScratch {
+    pub fn new() -> SearchScratch {
+        return SearchScratch::default();
+    }
+}
 
 pub struct SudokuSolver {
     pub board: SudokuBoard,
-    pub unsolved_spaces: Vec<(usize, usize)>,
+    pub unsolved_spaces: SpaceList,
     pub percent_solved: f32,
-    solved_board: RefCell<Option<SudokuBoard>>
+    config: SolverConfig,
+    solved_board: OnceLock<Arc<SudokuBoard>>,
+    search_lock: Mutex<()>, // Held while searching so concurrent callers wait for the one search instead of repeating it
+    search_count: AtomicU32,
+    last_solve_cached: AtomicBool,
+    known_uniqueness: Option<Uniqueness> // Left by `resolve_after_change` for the next one, cleared with the cache
 }
 
 impl SudokuSolver {
     pub fn new(sudoku_board: &SudokuBoard) -> SudokuSolver {
+        return SudokuSolver::with_config(sudoku_board, SolverConfig::default());
+    }
+
+    pub fn with_config(sudoku_board: &SudokuBoard, config: SolverConfig) -> SudokuSolver {
         if !sudoku_board.all_spaces_valid() {
             panic!("An invalid starting board configuration was passed.");
         }
+        return SudokuSolver::build(sudoku_board, config);
+    }
 
-        let unsolved_spaces = sudoku_board.get_unsolved_spaces();
-        let unsolved_length: f32 = unsolved_spaces.len() as f32;
+    // For boards already validated elsewhere, skipping the duplicate scan `new` does. Nothing unsafe happens with an
+    // invalid board, but every answer about it is meaningless. Debug builds still check and panic.
+    pub fn new_unchecked(sudoku_board: &SudokuBoard) -> SudokuSolver {
+        debug_assert!(sudoku_board.all_spaces_valid(), "An invalid starting board configuration was passed.");
+        return SudokuSolver::build(sudoku_board, SolverConfig::default());
+    }
 
-        return SudokuSolver {
+    fn build(sudoku_board: &SudokuBoard, config: SolverConfig) -> SudokuSolver {
+        let mut sudoku_solver = SudokuSolver {
             board: SudokuBoard::copy(sudoku_board),
-            unsolved_spaces,
-            percent_solved: (1.0 - (unsolved_length / (9.0 * 9.0))) * 100.0,
-            solved_board: RefCell::new(None)
+            unsolved_spaces: SpaceList::new(),
+            percent_solved: 0.0,
+            config,
+            solved_board: OnceLock::new(),
+            search_lock: Mutex::new(()),
+            search_count: AtomicU32::new(0),
+            last_solve_cached: AtomicBool::new(false),
+            known_uniqueness: None
+        };
+        sudoku_solver.refresh();
+        return sudoku_solver;
+    }
+
+    // Recomputes everything derived from `board` and drops the cached solution, needed after any change to `board`
+    fn refresh(&mut self) {
+        let mut unsolved_spaces = SpaceList::new();
+        for row in 0..=8 {
+            for column in 0..=8 {
+                if self.board.digit(row, column).is_none() {
+                    unsolved_spaces.push((row, column));
+                }
+            }
         }
+        let unsolved_length: f32 = unsolved_spaces.len() as f32;
+
+        self.unsolved_spaces = unsolved_spaces;
+        self.percent_solved = (1.0 - (unsolved_length / (9.0 * 9.0))) * 100.0;
+        self.solved_board.take();
+        self.known_uniqueness = None;
+    }
+
+    pub fn reset_to_givens(&mut self) {
+        self.board.reset_to_givens();
+        self.refresh();
+    }
+
+    pub fn clear(&mut self) {
+        self.board.clear();
+        self.refresh();
+    }
+
+    pub fn is_cached(&self) -> bool {
+        return self.solved_board.get().is_some();
+    }
+
+    // How many times the full search actually ran, failed and cancelled searches included
+    pub fn solve_count(&self) -> u32 {
+        return self.search_count.load(Ordering::Relaxed);
+    }
+
+    // Whether the most recent solve was answered from the cache, `false` before the first one
+    pub fn was_last_solve_cached(&self) -> bool {
+        return self.last_solve_cached.load(Ordering::Relaxed);
+    }
+
+    pub fn config(&self) -> SolverConfig {
+        return self.config;
     }
 
     pub fn solve(&self) -> SudokuBoard {
+        return match self.try_solve() {
+            Ok(solved_board) => solved_board,
+            Err(error) => panic!("{}", error)
+        };
+    }
+
+    pub fn try_solve(&self) -> Result<SudokuBoard, SolveError> {
+        return self.solve_with_control(&SolveControl::new());
+    }
+
+    // Like `try_solve`, with `control` reporting progress and able to stop the search from another thread
+    pub fn solve_with_control(&self, control: &SolveControl) -> Result<SudokuBoard, SolveError> {
+        return self.solve_shared_with_control(control).map(|solved_board| SudokuBoard::copy(&solved_board));
+    }
+
+    // The cached solution itself, cheap to hand out to many readers
+    pub fn solve_shared(&self) -> Result<Arc<SudokuBoard>, SolveError> {
+        return self.solve_shared_with_control(&SolveControl::new());
+    }
+
+    // Exclusive access means no other caller can be mid-search, so this skips the search lock entirely
+    pub fn solve_mut(&mut self) -> Result<&SudokuBoard, SolveError> {
+        *self.last_solve_cached.get_mut() = self.solved_board.get().is_some();
+        if self.solved_board.get().is_none() {
+            *self.search_count.get_mut() += 1;
+            let solved_board = SudokuSolver::run_engine(self.config.engine, SudokuBoard::copy(&self.board), &self.unsolved_spaces, &SolveControl::new())?;
+            let _ = self.solved_board.set(Arc::new(solved_board));
+        }
+        return Ok(self.solved_board.get().unwrap());
+    }
+
+    // Consumes the solver, so the search fills in the solver's own board instead of a copy of it
+    pub fn solve_owned(self) -> Result<SudokuBoard, SolveError> {
+        let SudokuSolver { board, unsolved_spaces, config, solved_board, .. } = self;
+        if let Some(solved_board) = solved_board.into_inner() {
+            return Ok(Arc::try_unwrap(solved_board).unwrap_or_else(|shared| SudokuBoard::copy(&shared)));
+        }
+        return SudokuSolver::run_engine(config.engine, board, &unsolved_spaces, &SolveControl::new());
+    }
+
+    fn solve_shared_with_control(&self, control: &SolveControl) -> Result<Arc<SudokuBoard>, SolveError> {
+        // Optimization 1: Keep solved board stored in private variable for cached access
+        if let Some(solved_board) = self.solved_board.get() {
+            self.last_solve_cached.store(true, Ordering::Relaxed);
+            return Ok(Arc::clone(solved_board));
+        }
+
+        // Failed or cancelled searches aren't cached, so a later call can try again
+        let _search_guard = self.search_lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(solved_board) = self.solved_board.get() {
+            self.last_solve_cached.store(true, Ordering::Relaxed);
+            return Ok(Arc::clone(solved_board));
+        }
+
+        self.search_count.fetch_add(1, Ordering::Relaxed);
+        self.last_solve_cached.store(false, Ordering::Relaxed);
+        let solved_board = Arc::new(SudokuSolver::run_engine(self.config.engine, SudokuBoard::copy(&self.board), &self.unsolved_spaces, control)?);
+
+        let _ = self.solved_board.set(Arc::clone(&solved_board));
+        return Ok(solved_board);
+    }
+
+    // For searches run elsewhere, such as `solve_async`'s worker. A solution already cached is kept.
+    #[cfg(feature = "async")]
+    pub(crate) fn cache_solution(&self, solved_board: &SudokuBoard) {
+        let _ = self.solved_board.set(Arc::new(SudokuBoard::copy(solved_board)));
+    }
+
+    // Solves `board` in place, callers pass a copy unless they own the solver's board outright
+    fn run_engine(engine: Engine, mut board: SudokuBoard, unsolved_spaces: &[(usize, usize)], control: &SolveControl) -> Result<SudokuBoard, SolveError> {
+        SudokuSolver::run_engine_in(engine, &mut board, &mut [0; 81], unsolved_spaces, control)?;
+        return Ok(board);
+    }
+
+    // `attempted_values` must be all 0 going in
+    fn run_engine_in(engine: Engine, board: &mut SudokuBoard, attempted_values: &mut [u16; 81], unsolved_spaces: &[(usize, usize)], control: &SolveControl) -> Result<(), SolveError> {
+        let mut pending = 0;
+        let result = match engine {
+            Engine::Iterative => SudokuSolver::solve_iterative(board, attempted_values, unsolved_spaces, control, &mut pending, &mut |_| {}),
+            Engine::Recursive => SudokuSolver::solve_recursive(board, unsolved_spaces, control, &mut pending)
+        };
+        control.flush(&mut pending);
+        return with_diagnosis(result, board);
+    }
+
+    // `record` hears about every placement and retraction, for `solve_traced`
+    fn solve_iterative(solved_board: &mut SudokuBoard, attempted_values: &mut [u16; 81], unsolved_spaces: &[(usize, usize)], control: &SolveControl, pending: &mut u64, record: &mut impl FnMut(SolveStep)) -> Result<(), SolveError> {
         // Back-tracking Algo
         // 1. Check if board is solved. If it is, end.
         // 2. Get Row at current space.
@@ -38,60 +365,295 @@ impl SudokuSolver {
         // 6. If there is/are valid value(s), plug in the first valid and move onto step 1 for the next space to solve.
         // 7. If not, move back to the previous space that was solved and plug in the next valid value.
 
-        // Optimization 1: Keep solved board stored in private variable for cached access
-        if self.solved_board.borrow().is_some() {
-            return SudokuBoard::copy(self.solved_board.borrow().as_ref().unwrap());
-        }
+        // `attempted_values` is a bitmask of the values tried so far, indexed the same as `unsolved_spaces`
+        return SudokuSolver::backtrack_from(0, solved_board, attempted_values, unsolved_spaces, control, pending, record);
+    }
+
+    // The iterative engine on `board` with every step handed to `record`, `iteration` counting loop turns from 0
+    pub(crate) fn run_iterative_recording(board: &mut SudokuBoard, unsolved_spaces: &[(usize, usize)], control: &SolveControl, record: &mut impl FnMut(SolveStep)) -> Result<(), SolveError> {
+        let mut pending = 0;
+        let result = SudokuSolver::solve_iterative(board, &mut [0; 81], unsolved_spaces, control, &mut pending, record);
+        control.flush(&mut pending);
+        return with_diagnosis(result, board);
+    }
 
-        let all_value_candidates = vec![1, 2, 3, 4, 5, 6, 7, 8, 9];
-        let mut solved_board = SudokuBoard::copy(&self.board);
-        let mut attempted_values: HashMap<(usize, usize), Vec<u8>> = HashMap::new();
-        let mut unsolved_spaces_index = 0;
-
-        while unsolved_spaces_index < self.unsolved_spaces.len() {
-            let row_index = self.unsolved_spaces[unsolved_spaces_index].0;
-            let column_index = self.unsolved_spaces[unsolved_spaces_index].1;
-            let nonet_index = 3 * ((9 * row_index + column_index) / 27) + ((9 * row_index + column_index) / 3 % 3);
-            solved_board[(row_index, column_index)] = 0; // Set back to 0 in the case this was a back-tracked space
-
-            let row = solved_board.get_row(row_index);
-            let column = solved_board.get_column(column_index);
-            let nonet = solved_board.get_nonet(nonet_index);
-
-            let invalid_value_candidates: HashSet<u8> = HashSet::from_iter( // Store in a set, all the previously used values, values in row, column, and nonet.
-                attempted_values.entry((row_index, column_index)).or_default().iter()
-                .chain(row.iter().filter(|&&value| value != 0))
-                .chain(column.iter().filter(|&&value| value != 0))
-                .chain(nonet.iter().filter(|&&value| value != 0))
-                .map(|value| *value)
-            );
-
-            let mut valid_value_candidates = all_value_candidates.iter().filter(|value| !invalid_value_candidates.contains(value));
-            let first_value = valid_value_candidates.next();
-            if first_value.is_some() { // Found a valid value to use
-                solved_board[(row_index, column_index)] = *first_value.unwrap();
-                attempted_values.entry((row_index, column_index)).or_default().push(*first_value.unwrap());
+    // The loop of `solve_iterative` starting at `unsolved_spaces_index`. Starting at the last unsolved space of a solved
+    // board with the `attempted_values` that found it carries on the same search towards the next solution.
+    fn backtrack_from(mut unsolved_spaces_index: usize, solved_board: &mut SudokuBoard, attempted_values: &mut [u16; 81], unsolved_spaces: &[(usize, usize)], control: &SolveControl, pending: &mut u64, record: &mut impl FnMut(SolveStep)) -> Result<(), SolveError> {
+        // Every space from `unsolved_spaces_index` onwards is still empty (or about to be cleared), so the board is only
+        // fully solved once the index has walked past the last unsolved space
+        let mut iteration = 0;
+        while unsolved_spaces_index < unsolved_spaces.len() {
+            control.tick(pending)?;
+            let (row_index, column_index) = unsolved_spaces[unsolved_spaces_index];
+            let previous_value = solved_board[(row_index, column_index)];
+            if previous_value != 0 { // A back-tracked space
+                solved_board.clear_value(row_index, column_index);
+                record(SolveStep::Retract { row: row_index, column: column_index, value: previous_value, iteration });
+            }
+
+            let invalid_value_candidates = attempted_values[unsolved_spaces_index] | used_values(solved_board, row_index, column_index);
+            if let Some(first_value) = (1..=9).find(|value| invalid_value_candidates & (1 << value) == 0) { // Found a valid value to use
+                solved_board.set_value(row_index, column_index, first_value);
+                record(SolveStep::Place { row: row_index, column: column_index, value: first_value, iteration });
+                attempted_values[unsolved_spaces_index] |= 1 << first_value;
                 unsolved_spaces_index += 1;
             }
             else { // Need to backtrack
                 if unsolved_spaces_index == 0 {
-                    panic!("This board is unsolvable");
+                    return Err(SolveError::NoSolution(None));
                 }
-                
-                attempted_values.remove(&(row_index, column_index));
+
+                attempted_values[unsolved_spaces_index] = 0;
                 unsolved_spaces_index -= 1;
             }
+            iteration += 1;
         };
 
-        self.solved_board.replace(Some(solved_board));
-        return SudokuBoard::copy(self.solved_board.borrow().as_ref().unwrap());
+        return Ok(());
     }
+
+    // Like `try_solve`, but the search runs in `scratch` and the solution is borrowed from it, so a scratch reused
+    // across many puzzles saves allocating for each. A cached solution is used, but one found here isn't cached.
+    pub fn solve_with_scratch<'a>(&self, scratch: &'a mut SearchScratch) -> Result<&'a SudokuBoard, SolveError> {
+        if let Some(solved_board) = self.solved_board.get() {
+            self.last_solve_cached.store(true, Ordering::Relaxed);
+            scratch.board.copy_from(solved_board);
+            return Ok(&scratch.board);
+        }
+
+        self.search_count.fetch_add(1, Ordering::Relaxed);
+        self.last_solve_cached.store(false, Ordering::Relaxed);
+        scratch.board.copy_from(&self.board);
+        scratch.attempted_values = [0; 81];
+        SudokuSolver::run_engine_in(self.config.engine, &mut scratch.board, &mut scratch.attempted_values, &self.unsolved_spaces, &scratch.control)?;
+        return Ok(&scratch.board);
+    }
+
+    // Like `try_solve`, but a second solution is an error. Rather than counting solutions in a separate search, the
+    // search that found the first one carries on until it finds another or runs out, so for a unique puzzle the extra
+    // cost is only the part of the tree left after the solution. Always runs the iterative engine, the recursive one
+    // can't pick up where it stopped.
+    pub fn solve_unique(&self) -> Result<SudokuBoard, SolveError> {
+        return self.solve_unique_with_control(&SolveControl::new());
+    }
+
+    // Like `solve_unique`, with `control` also recording where the first solution was found
+    pub fn solve_unique_with_control(&self, control: &SolveControl) -> Result<SudokuBoard, SolveError> {
+        self.search_count.fetch_add(1, Ordering::Relaxed);
+        self.last_solve_cached.store(false, Ordering::Relaxed);
+
+        let mut board = SudokuBoard::copy(&self.board);
+        let mut attempted_values = [0; 81];
+        let mut pending = 0;
+        let first_search = SudokuSolver::solve_iterative(&mut board, &mut attempted_values, &self.unsolved_spaces, control, &mut pending, &mut |_| {});
+        control.flush(&mut pending);
+        with_diagnosis(first_search, &board)?;
+        let _ = control.first_solution.set(control.iterations());
+        let solved_board = SudokuBoard::copy(&board);
+
+        if !self.unsolved_spaces.is_empty() {
+            let second_search = SudokuSolver::backtrack_from(self.unsolved_spaces.len() - 1, &mut board, &mut attempted_values, &self.unsolved_spaces, control, &mut pending, &mut |_| {});
+            control.flush(&mut pending);
+            match second_search {
+                Ok(()) => return Err(SolveError::MultipleSolutions),
+                Err(SolveError::NoSolution(_)) => {},
+                Err(error) => return Err(error)
+            }
+        }
+
+        let _ = self.solved_board.set(Arc::new(SudokuBoard::copy(&solved_board)));
+        return Ok(solved_board);
+    }
+
+    // Sets the space from `old` to `new` for an editor toggling clues, keeping the cached solution whenever it still fits:
+    // removing a clue never invalidates it, and neither does adding one that matches it. Only then does the search run
+    // again. Uniqueness is always rechecked, except for a clue added to a board already known to be unique.
+    pub fn resolve_after_change(&mut self, row_index: usize, column_index: usize, old: u8, new: u8) -> Resolution {
+        if self.board[(row_index, column_index)] != old {
+            panic!("An invalid old value was passed into 'resolve_after_change', it was {}", old);
+        }
+
+        let previous_solution = self.solved_board.take();
+        let previous_uniqueness = self.known_uniqueness;
+        self.board.set_value(row_index, column_index, new);
+        self.refresh();
+        if !self.board.all_spaces_valid() {
+            self.known_uniqueness = Some(Uniqueness::NoSolution);
+            return Resolution { uniqueness: Uniqueness::NoSolution, reused_solution: false };
+        }
+
+        let kept_solution = previous_solution.filter(|solution| new == 0 || solution[(row_index, column_index)] == new);
+        let reused_solution = kept_solution.is_some();
+        let uniqueness = match kept_solution {
+            Some(solution) => {
+                let _ = self.solved_board.set(solution);
+                if new != 0 && previous_uniqueness == Some(Uniqueness::Unique) { Uniqueness::Unique } else { is_unique(&self.board) }
+            },
+            None => match self.solve_shared() {
+                Ok(_) => is_unique(&self.board),
+                Err(_) => Uniqueness::NoSolution
+            }
+        };
+
+        self.known_uniqueness = Some(uniqueness);
+        return Resolution { uniqueness, reused_solution };
+    }
+
+    // Like `try_solve`, with the search starting from `partial` instead of the solver's board, usually the board
+    // `simplify` made of it so the singles it found aren't searched for again. `partial` has to keep every given and
+    // break no rule, but the values it adds are trusted: a wrong one makes the solve fail with `NoSolution`.
+    // Nothing is cached, the solution depends on `partial`.
+    pub fn solve_from(&self, partial: &SudokuBoard) -> Result<SudokuBoard, SolveError> {
+        return self.solve_from_with_stats(partial).map(|(solved_board, _)| solved_board);
+    }
+
+    pub fn solve_from_with_stats(&self, partial: &SudokuBoard) -> Result<(SudokuBoard, SolveStats), SolveError> {
+        if partial.is_consistent_extension_of(&self.board).is_err() {
+            return Err(SolveError::GivensChanged);
+        }
+        if !partial.all_spaces_valid() {
+            return Err(SolveError::InvalidBoard);
+        }
+
+        let warm_solver = SudokuSolver::build(partial, self.config);
+        let control = SolveControl::new();
+        self.search_count.fetch_add(1, Ordering::Relaxed);
+        self.last_solve_cached.store(false, Ordering::Relaxed);
+        let solved_board = SudokuSolver::run_engine(self.config.engine, SudokuBoard::copy(partial), &warm_solver.unsolved_spaces, &control)?;
+
+        let stats = SolveStats {
+            propagated_cells: self.unsolved_spaces.len() - warm_solver.unsolved_spaces.len(),
+            searched_cells: warm_solver.unsolved_spaces.len(),
+            iterations: control.iterations()
+        };
+        return Ok((solved_board, stats));
+    }
+
+    // Counts solutions up to `limit`, so `count_solutions(2)` is enough to tell unique puzzles from ambiguous ones
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        return self.count_solutions_with_scratch(limit, &mut SearchScratch::new());
+    }
+
+    pub fn count_solutions_with_scratch(&self, limit: usize, scratch: &mut SearchScratch) -> usize {
+        scratch.board.copy_from(&self.board);
+        let mut count = 0;
+        count_search(&mut scratch.board, limit, &mut count);
+        return count;
+    }
+
+    // Givens whose removal on its own still leaves exactly one solution. Removing several of these at once can still
+    // break uniqueness, each one is only checked against the full set of the other givens.
+    pub fn redundant_givens(&self) -> Vec<(usize, usize)> {
+        let mut redundant_givens = Vec::new();
+        for row_index in 0..=8 {
+            for column_index in 0..=8 {
+                if self.board.digit(row_index, column_index).is_none() {
+                    continue;
+                }
+
+                let mut reduced_board = SudokuBoard::copy(&self.board);
+                reduced_board.clear_value(row_index, column_index);
+                if SudokuSolver::new(&reduced_board).count_solutions(2) == 1 {
+                    redundant_givens.push((row_index, column_index));
+                }
+            }
+        }
+        return redundant_givens;
+    }
+
+    fn solve_recursive(solved_board: &mut SudokuBoard, unsolved_spaces: &[(usize, usize)], control: &SolveControl, pending: &mut u64) -> Result<(), SolveError> {
+        if !search(solved_board, unsolved_spaces, 0, control, pending)? {
+            return Err(SolveError::NoSolution(None));
+        }
+        return Ok(());
+    }
+}
+
+// Fills in why a search came up empty. Both engines clear every space they filled before giving up, so `board` is the
+// board the search started from again.
+pub(crate) fn with_diagnosis(result: Result<(), SolveError>, board: &SudokuBoard) -> Result<(), SolveError> {
+    return match result {
+        Err(SolveError::NoSolution(None)) => Err(SolveError::NoSolution(board.diagnose_unsolvable())),
+        result => result
+    };
+}
+
+// Depth first back-tracking. Every frame solves one of the unsolved spaces, so the recursion is at most 81 frames deep
+// and can't overflow the stack.
+fn search(board: &mut SudokuBoard, unsolved_spaces: &[(usize, usize)], depth: usize, control: &SolveControl, pending: &mut u64) -> Result<bool, SolveError> {
+    if depth == unsolved_spaces.len() {
+        return Ok(true);
+    }
+
+    let (row_index, column_index) = unsolved_spaces[depth];
+    let invalid_value_candidates = used_values(board, row_index, column_index);
+    for value in (1..=9).filter(|value| invalid_value_candidates & (1 << value) == 0) {
+        control.tick(pending)?;
+        board.set_value(row_index, column_index, value);
+        if search(board, unsolved_spaces, depth + 1, control, pending)? {
+            return Ok(true);
+        }
+    }
+
+    board.clear_value(row_index, column_index);
+    return Ok(false);
+}
+
+// Exhaustive search that always branches on the space with the fewest remaining values, which keeps the tree small
+// enough to prove there's no second solution
+fn count_search(board: &mut SudokuBoard, limit: usize, count: &mut usize) {
+    if *count >= limit {
+        return;
+    }
+
+    let mut best_space = None;
+    let mut best_used_values = 0u16;
+    for row_index in 0..=8 {
+        for column_index in 0..=8 {
+            if board.digit(row_index, column_index).is_some() {
+                continue;
+            }
+
+            let space_used_values = used_values(board, row_index, column_index);
+            if best_space.is_none() || space_used_values.count_ones() > best_used_values.count_ones() {
+                best_space = Some((row_index, column_index));
+                best_used_values = space_used_values;
+            }
+        }
+    }
+
+    let (row_index, column_index) = match best_space {
+        Some(space) => space,
+        None => {
+            *count += 1;
+            return;
+        }
+    };
+
+    for value in (1..=9).filter(|value| best_used_values & (1 << value) == 0) {
+        board.set_value(row_index, column_index, value);
+        count_search(board, limit, count);
+        if *count >= limit {
+            break;
+        }
+    }
+    board.clear_value(row_index, column_index);
+}
+
+// Bit `n` is set for every value `n` held by a peer of the space. Bit 0 marks unsolved spaces and is ignored.
+pub(crate) fn used_values(board: &SudokuBoard, row_index: usize, column_index: usize) -> u16 {
+    let mut used_values = 0;
+    for peer in PEERS[9 * row_index + column_index].iter() {
+        used_values |= 1 << board[(peer / 9, peer % 9)];
+    }
+    return used_values;
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::time::Instant;
+    use crate::candidate_board::CandidateBoard;
 
     #[test]
     fn constructor_works_valid_board() {
@@ -118,6 +680,40 @@ mod tests {
         assert_eq!(solver.solved_board.into_inner().is_none(), true);
     }
 
+    #[test]
+    fn new_unchecked_matches_new() {
+        let hard_board = SudokuBoard::new(&[
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,2, 0,0,5, 0,4,0,
+            1,0,8, 0,4,0, 0,0,0,
+            0,0,0, 0,0,0, 4,0,3,
+            0,0,6, 0,5,0, 0,0,1,
+            0,0,0, 0,2,0, 0,0,6,
+            3,0,1, 0,0,0, 0,8,0,
+            2,0,7, 0,0,0, 6,0,0,
+            0,0,0, 0,0,6, 1,3,9
+        ]);
+
+        for board in [hard_board, crate::strategies::masked_puzzle(7, 50), SudokuBoard::new(&[0; 81])].iter() {
+            let (solver, unchecked_solver) = (SudokuSolver::new(board), SudokuSolver::new_unchecked(board));
+
+            assert_eq!(unchecked_solver.board, solver.board);
+            assert_eq!(unchecked_solver.unsolved_spaces, solver.unsolved_spaces);
+            assert_eq!(unchecked_solver.percent_solved, solver.percent_solved);
+            assert_eq!(unchecked_solver.solve(), solver.solve());
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn new_unchecked_debug_checks() {
+        let mut values = [0; 81];
+        values[0] = 1;
+        values[1] = 1;
+        SudokuSolver::new_unchecked(&SudokuBoard::new(&values));
+    }
+
     #[test]
     #[should_panic]
     fn constructor_works_invalid_board() {
@@ -226,6 +822,455 @@ mod tests {
         ]));
     }
 
+    // The solution has to keep every clue of `board`, and the uniqueness must match a from-scratch check
+    fn assert_resolution_matches_scratch(solver: &SudokuSolver, resolution: Resolution) {
+        assert_eq!(resolution.uniqueness, is_unique(&solver.board));
+        match resolution.uniqueness {
+            Uniqueness::NoSolution => assert!(matches!(SudokuSolver::new(&solver.board).try_solve(), Err(SolveError::NoSolution(_)))),
+            _ => {
+                let solution = solver.solve();
+                assert!(solution.all_spaces_solved() && solution.all_spaces_valid());
+                assert!((0..81).all(|index| solver.board[(index / 9, index % 9)] == 0 || solver.board[(index / 9, index % 9)] == solution[(index / 9, index % 9)]));
+            }
+        }
+    }
+
+    #[test]
+    fn resolve_after_change_works() {
+        let unique_board = SudokuBoard::new(&[
+            7,8,0, 4,0,0, 1,2,0,
+            6,0,0, 0,7,5, 0,0,9,
+            0,0,0, 6,0,1, 0,7,8,
+            0,0,7, 0,4,0, 2,6,0,
+            0,0,1, 0,5,0, 9,3,0,
+            9,0,4, 0,6,0, 0,0,5,
+            0,7,0, 3,0,0, 0,1,2,
+            1,2,0, 0,0,7, 4,0,0,
+            0,4,9, 2,0,6, 0,0,7
+        ]);
+        let mut solver = SudokuSolver::new(&unique_board);
+        let solution = solver.solve();
+
+        // Clue added that matches the solution: kept without searching
+        let resolution = solver.resolve_after_change(0, 2, 0, solution[(0, 2)]);
+        assert_eq!(resolution, Resolution { uniqueness: Uniqueness::Unique, reused_solution: true });
+        assert_eq!(solver.solve(), solution);
+        assert_eq!(solver.solve_count(), 1);
+        assert_resolution_matches_scratch(&solver, resolution);
+
+        // Clues removed until a second solution appears, the solution is kept throughout
+        for &(row_index, column_index) in [(0, 2), (0, 0), (0, 1), (1, 0)].iter() {
+            let resolution = solver.resolve_after_change(row_index, column_index, solver.board[(row_index, column_index)], 0);
+            assert!(resolution.reused_solution);
+            assert_eq!(solver.solve(), solution);
+            assert_resolution_matches_scratch(&solver, resolution);
+        }
+        assert_eq!(is_unique(&solver.board), Uniqueness::Multiple);
+        assert_eq!(solver.solve_count(), 1);
+
+        // Clue added that contradicts the solution, the search runs again
+        let other_value = CandidateBoard::new(&solver.board).get(0, 0).iter().find(|value| *value != solution[(0, 0)]).unwrap();
+        let resolution = solver.resolve_after_change(0, 0, 0, other_value);
+        assert!(!resolution.reused_solution);
+        assert_eq!(solver.solve_count(), 2);
+        assert_resolution_matches_scratch(&solver, resolution);
+
+        // A clue breaking the rules outright
+        let resolution = solver.resolve_after_change(0, 0, other_value, 1);
+        assert_eq!(resolution, Resolution { uniqueness: Uniqueness::NoSolution, reused_solution: false });
+    }
+
+    #[test]
+    #[should_panic]
+    fn resolve_after_change_wrong_old_value() {
+        SudokuSolver::new(&SudokuBoard::new(&[0; 81])).resolve_after_change(0, 0, 5, 1);
+    }
+
+    #[test]
+    fn warm_start_searches_less() {
+        let hard_board = SudokuBoard::new(&[
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,2, 0,0,5, 0,4,0,
+            1,0,8, 0,4,0, 0,0,0,
+            0,0,0, 0,0,0, 4,0,3,
+            0,0,6, 0,5,0, 0,0,1,
+            0,0,0, 0,2,0, 0,0,6,
+            3,0,1, 0,0,0, 0,8,0,
+            2,0,7, 0,0,0, 6,0,0,
+            0,0,0, 0,0,6, 1,3,9
+        ]);
+        let solver = SudokuSolver::new(&hard_board);
+        let (cold_solution, cold_stats) = solver.solve_from_with_stats(&hard_board).unwrap();
+        let (warm_solution, warm_stats) = solver.solve_from_with_stats(&hard_board.simplify()).unwrap();
+
+        assert_eq!(cold_solution, solver.solve());
+        assert_eq!(warm_solution, cold_solution);
+        assert_eq!(cold_stats.propagated_cells, 0);
+        assert!(warm_stats.propagated_cells > 0);
+        assert_eq!(warm_stats.propagated_cells + warm_stats.searched_cells, cold_stats.searched_cells);
+        assert!(warm_stats.iterations < cold_stats.iterations);
+    }
+
+    #[test]
+    fn solve_from_checks_partial() {
+        let board = SudokuBoard::new(&[
+            0,7,3, 8,9,4, 5,1,2,
+            9,1,2, 7,3,5, 4,8,6,
+            8,4,5, 0,0,2, 9,7,3,
+            7,9,8, 2,6,1, 3,5,4,
+            5,2,6, 4,7,3, 8,9,1,
+            1,3,4, 5,8,9, 2,6,7,
+            4,6,9, 0,2,8, 7,3,5,
+            2,8,7, 3,5,6, 1,4,9,
+            3,5,1, 9,4,7, 6,2,0
+        ]);
+        let solver = SudokuSolver::new(&board);
+
+        let mut changed_given = SudokuBoard::copy(&board);
+        changed_given.clear_value(0, 1);
+        let mut clashing = SudokuBoard::copy(&board);
+        clashing.set_value(0, 0, 7);
+        let mut wrong_guess = SudokuBoard::copy(&board);
+        wrong_guess.set_value(2, 3, 1);
+
+        assert_eq!(solver.solve_from(&changed_given), Err(SolveError::GivensChanged));
+        assert_eq!(solver.solve_from(&clashing), Err(SolveError::InvalidBoard));
+        assert!(matches!(solver.solve_from(&wrong_guess), Err(SolveError::NoSolution(_))));
+        assert_eq!(solver.solve_from(&board.simplify()), Ok(solver.solve()));
+    }
+
+    #[test]
+    fn solve_recursive_matches_iterative() {
+        let boards = [
+            SudokuBoard::new(&[
+                0,7,3, 8,9,4, 5,1,2,
+                9,1,2, 7,3,5, 4,8,6,
+                8,4,5, 0,0,2, 9,7,3,
+                7,9,8, 2,6,1, 3,5,4,
+                5,2,6, 4,7,3, 8,9,1,
+                1,3,4, 5,8,9, 2,6,7,
+                4,6,9, 0,2,8, 7,3,5,
+                2,8,7, 3,5,6, 1,4,9,
+                3,5,1, 9,4,7, 6,2,0
+            ]),
+            SudokuBoard::new(&[
+                7,8,0, 4,0,0, 1,2,0,
+                6,0,0, 0,7,5, 0,0,9,
+                0,0,0, 6,0,1, 0,7,8,
+                0,0,7, 0,4,0, 2,6,0,
+                0,0,1, 0,5,0, 9,3,0,
+                9,0,4, 0,6,0, 0,0,5,
+                0,7,0, 3,0,0, 0,1,2,
+                1,2,0, 0,0,7, 4,0,0,
+                0,4,9, 2,0,6, 0,0,7
+            ]),
+            SudokuBoard::new(&[
+                0,0,0, 0,0,0, 0,0,0,
+                0,0,2, 0,0,5, 0,4,0,
+                1,0,8, 0,4,0, 0,0,0,
+                0,0,0, 0,0,0, 4,0,3,
+                0,0,6, 0,5,0, 0,0,1,
+                0,0,0, 0,2,0, 0,0,6,
+                3,0,1, 0,0,0, 0,8,0,
+                2,0,7, 0,0,0, 6,0,0,
+                0,0,0, 0,0,6, 1,3,9
+            ])
+        ];
+
+        for board in boards.iter() {
+            let iterative_solver = SudokuSolver::new(board);
+            let recursive_solver = SudokuSolver::with_config(board, SolverConfig::new().engine(Engine::Recursive));
+
+            assert_eq!(recursive_solver.config().engine, Engine::Recursive);
+            assert_eq!(recursive_solver.solve(), iterative_solver.solve());
+        }
+    }
+
+    #[test]
+    fn scratch_reuse_matches_fresh_solves() {
+        let mut unsolvable = [0; 81];
+        unsolvable[..8].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        unsolvable[17] = 9;
+        let mut boards: Vec<SudokuBoard> = (0..20).map(|seed| crate::strategies::masked_puzzle(seed, 40 + seed as usize % 10)).collect();
+        boards.insert(5, SudokuBoard::new(&unsolvable));
+        boards.insert(15, SudokuBoard::new(&[0; 81]));
+
+        for config in [SolverConfig::new(), SolverConfig::new().engine(Engine::Recursive)].iter() {
+            let mut scratch = SearchScratch::new();
+            for board in boards.iter() {
+                let solver = SudokuSolver::with_config(board, *config);
+
+                assert_eq!(solver.solve_with_scratch(&mut scratch).map(SudokuBoard::copy), SudokuSolver::with_config(board, *config).try_solve());
+                assert_eq!(solver.count_solutions_with_scratch(2, &mut scratch), solver.count_solutions(2));
+                assert!(!solver.is_cached());
+            }
+        }
+    }
+
+    #[test]
+    fn solve_with_scratch_uses_cache() {
+        let solver = SudokuSolver::new(&crate::strategies::masked_puzzle(3, 50));
+        let solved_board = solver.solve();
+        let mut scratch = SearchScratch::new();
+
+        assert_eq!(solver.solve_with_scratch(&mut scratch), Ok(&solved_board));
+        assert!(solver.was_last_solve_cached());
+        assert_eq!(solver.solve_count(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn solve_recursive_unsolvable() {
+        let unsolvable_board = SudokuBoard::new(&[
+            1,2,3, 4,5,6, 7,8,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,9,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0
+        ]);
+
+        SudokuSolver::with_config(&unsolvable_board, SolverConfig::new().engine(Engine::Recursive)).solve();
+    }
+
+    #[test]
+    fn try_solve_unsolvable() {
+        let unsolvable_board = SudokuBoard::new(&[
+            1,2,3, 4,5,6, 7,8,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,9,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0
+        ]);
+
+        let no_solution = SolveError::NoSolution(Some(UnsolvableReason::CellHasNoCandidates { row: 0, column: 8 }));
+        assert_eq!(SudokuSolver::new(&unsolvable_board).try_solve(), Err(no_solution));
+        assert_eq!(SudokuSolver::with_config(&unsolvable_board, SolverConfig::new().engine(Engine::Recursive)).try_solve(), Err(no_solution));
+        assert_eq!(SudokuSolver::new(&unsolvable_board).solve_unique(), Err(no_solution));
+        assert_eq!(no_solution.to_string(), "This board is unsolvable: R1C9 has no candidates left");
+    }
+
+    #[test]
+    fn clear_invalidates_cache() {
+        let mut solver = SudokuSolver::new(&SudokuBoard::new(&[
+            0,7,3, 8,9,4, 5,1,2,
+            9,1,2, 7,3,5, 4,8,6,
+            8,4,5, 0,0,2, 9,7,3,
+            7,9,8, 2,6,1, 3,5,4,
+            5,2,6, 4,7,3, 8,9,1,
+            1,3,4, 5,8,9, 2,6,7,
+            4,6,9, 0,2,8, 7,3,5,
+            2,8,7, 3,5,6, 1,4,9,
+            3,5,1, 9,4,7, 6,2,0
+        ]));
+        let solution = solver.solve();
+
+        solver.reset_to_givens();
+        assert_eq!(solver.unsolved_spaces.len(), 5);
+        assert_eq!(solver.solve(), solution);
+
+        solver.clear();
+        assert_eq!(solver.unsolved_spaces.len(), 81);
+        assert_eq!(solver.percent_solved, 0.0);
+        assert!(solver.solved_board.get().is_none());
+        assert!(solver.solve().is_solved());
+        assert_ne!(solver.solve(), solution);
+    }
+
+    #[test]
+    fn solver_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<SudokuSolver>();
+        assert_send_sync::<Arc<SudokuBoard>>();
+    }
+
+    #[test]
+    fn concurrent_solves_search_once() {
+        let solver = SudokuSolver::new(&SudokuBoard::new(&[
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,2, 0,0,5, 0,4,0,
+            1,0,8, 0,4,0, 0,0,0,
+            0,0,0, 0,0,0, 4,0,3,
+            0,0,6, 0,5,0, 0,0,1,
+            0,0,0, 0,2,0, 0,0,6,
+            3,0,1, 0,0,0, 0,8,0,
+            2,0,7, 0,0,0, 6,0,0,
+            0,0,0, 0,0,6, 1,3,9
+        ]));
+
+        let solutions: Vec<Arc<SudokuBoard>> = std::thread::scope(|scope| {
+            let threads: Vec<_> = (0..4).map(|_| scope.spawn(|| solver.solve_shared().unwrap())).collect();
+            threads.into_iter().map(|thread| thread.join().unwrap()).collect()
+        });
+
+        assert_eq!(solver.search_count.load(Ordering::Relaxed), 1);
+        assert!(solutions.iter().all(|solution| Arc::ptr_eq(solution, &solutions[0])));
+        assert_eq!(*solutions[0], solver.solve());
+    }
+
+    #[test]
+    fn solve_mut_works() {
+        let mut solver = SudokuSolver::new(&SudokuBoard::new(&[
+            7,8,0, 4,0,0, 1,2,0,
+            6,0,0, 0,7,5, 0,0,9,
+            0,0,0, 6,0,1, 0,7,8,
+            0,0,7, 0,4,0, 2,6,0,
+            0,0,1, 0,5,0, 9,3,0,
+            9,0,4, 0,6,0, 0,0,5,
+            0,7,0, 3,0,0, 0,1,2,
+            1,2,0, 0,0,7, 4,0,0,
+            0,4,9, 2,0,6, 0,0,7
+        ]));
+
+        let first: *const SudokuBoard = solver.solve_mut().unwrap();
+        let second: *const SudokuBoard = solver.solve_mut().unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(*solver.search_count.get_mut(), 1);
+        assert!(solver.solve_mut().unwrap().is_solved());
+        let solved_board = solver.solve();
+        assert_eq!(*solver.solve_mut().unwrap(), solved_board);
+    }
+
+    #[test]
+    fn solve_owned_works() {
+        for engine in [Engine::Iterative, Engine::Recursive].iter() {
+            let config = SolverConfig::new().engine(*engine);
+            for puzzle in [
+                [0,7,3, 8,9,4, 5,1,2, 9,1,2, 7,3,5, 4,8,6, 8,4,5, 0,0,2, 9,7,3, 7,9,8, 2,6,1, 3,5,4, 5,2,6, 4,7,3, 8,9,1, 1,3,4, 5,8,9, 2,6,7, 4,6,9, 0,2,8, 7,3,5, 2,8,7, 3,5,6, 1,4,9, 3,5,1, 9,4,7, 6,2,0],
+                [7,8,0, 4,0,0, 1,2,0, 6,0,0, 0,7,5, 0,0,9, 0,0,0, 6,0,1, 0,7,8, 0,0,7, 0,4,0, 2,6,0, 0,0,1, 0,5,0, 9,3,0, 9,0,4, 0,6,0, 0,0,5, 0,7,0, 3,0,0, 0,1,2, 1,2,0, 0,0,7, 4,0,0, 0,4,9, 2,0,6, 0,0,7]
+            ].iter() {
+                let board = SudokuBoard::new(puzzle);
+                let expected = SudokuSolver::with_config(&board, config).solve();
+
+                assert_eq!(SudokuSolver::with_config(&board, config).solve_owned(), Ok(SudokuBoard::copy(&expected)));
+
+                // A cached solution is moved out as well
+                let solver = SudokuSolver::with_config(&board, config);
+                solver.solve();
+                assert_eq!(solver.solve_owned(), Ok(expected));
+            }
+        }
+    }
+
+    #[test]
+    fn count_solutions_works() {
+        let unique_board = SudokuBoard::new(&[
+            7,8,0, 4,0,0, 1,2,0,
+            6,0,0, 0,7,5, 0,0,9,
+            0,0,0, 6,0,1, 0,7,8,
+            0,0,7, 0,4,0, 2,6,0,
+            0,0,1, 0,5,0, 9,3,0,
+            9,0,4, 0,6,0, 0,0,5,
+            0,7,0, 3,0,0, 0,1,2,
+            1,2,0, 0,0,7, 4,0,0,
+            0,4,9, 2,0,6, 0,0,7
+        ]);
+        let unsolvable_board = SudokuBoard::new(&[
+            1,2,3, 4,5,6, 7,8,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,9,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0
+        ]);
+
+        assert_eq!(SudokuSolver::new(&unique_board).count_solutions(2), 1);
+        assert_eq!(SudokuSolver::new(&unsolvable_board).count_solutions(2), 0);
+        assert_eq!(SudokuSolver::new(&SudokuBoard::new(&[0; 81])).count_solutions(5), 5);
+    }
+
+    #[test]
+    fn solve_unique_works() {
+        let unique_board = SudokuBoard::new(&[
+            7,8,0, 4,0,0, 1,2,0,
+            6,0,0, 0,7,5, 0,0,9,
+            0,0,0, 6,0,1, 0,7,8,
+            0,0,7, 0,4,0, 2,6,0,
+            0,0,1, 0,5,0, 9,3,0,
+            9,0,4, 0,6,0, 0,0,5,
+            0,7,0, 3,0,0, 0,1,2,
+            1,2,0, 0,0,7, 4,0,0,
+            0,4,9, 2,0,6, 0,0,7
+        ]);
+        let unsolvable_board = SudokuBoard::new(&[
+            1,2,3, 4,5,6, 7,8,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,9,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0
+        ]);
+        let mut ambiguous_board = SudokuBoard::copy(&unique_board);
+        ambiguous_board.clear_value(0, 0);
+        ambiguous_board.clear_value(0, 1);
+        ambiguous_board.clear_value(1, 0);
+        assert_eq!(SudokuSolver::new(&ambiguous_board).count_solutions(2), 2);
+
+        let solver = SudokuSolver::new(&unique_board);
+        let control = SolveControl::new();
+        assert_eq!(solver.solve_unique_with_control(&control), Ok(solver.solve()));
+        let first_solution_iterations = control.first_solution_iterations().unwrap();
+        assert!(first_solution_iterations > 0 && first_solution_iterations < control.iterations());
+
+        let control = SolveControl::new();
+        assert_eq!(SudokuSolver::new(&ambiguous_board).solve_unique_with_control(&control), Err(SolveError::MultipleSolutions));
+        assert!(control.first_solution_iterations().is_some());
+
+        let control = SolveControl::new();
+        assert!(matches!(SudokuSolver::new(&unsolvable_board).solve_unique_with_control(&control), Err(SolveError::NoSolution(_))));
+        assert_eq!(control.first_solution_iterations(), None);
+
+        let solved_board = solver.solve();
+        assert_eq!(SudokuSolver::new(&solved_board).solve_unique(), Ok(SudokuBoard::copy(&solved_board)));
+        assert_eq!(SudokuSolver::new(&SudokuBoard::new(&[0; 81])).solve_unique(), Err(SolveError::MultipleSolutions));
+    }
+
+    #[test]
+    fn redundant_givens_works() {
+        // The medium puzzle with one extra given, (0, 2) = 5, taken from its solution
+        let board_with_extra_given = SudokuBoard::new(&[
+            7,8,5, 4,0,0, 1,2,0,
+            6,0,0, 0,7,5, 0,0,9,
+            0,0,0, 6,0,1, 0,7,8,
+            0,0,7, 0,4,0, 2,6,0,
+            0,0,1, 0,5,0, 9,3,0,
+            9,0,4, 0,6,0, 0,0,5,
+            0,7,0, 3,0,0, 0,1,2,
+            1,2,0, 0,0,7, 4,0,0,
+            0,4,9, 2,0,6, 0,0,7
+        ]);
+        // Every 17 clue puzzle is minimal
+        let minimal_board = SudokuBoard::new(&[
+            0,0,0, 0,0,0, 0,1,0,
+            4,0,0, 0,0,0, 0,0,0,
+            0,2,0, 0,0,0, 0,0,0,
+            0,0,0, 0,5,0, 4,0,7,
+            0,0,8, 0,0,0, 3,0,0,
+            0,0,1, 0,9,0, 0,0,0,
+            3,0,0, 4,0,0, 2,0,0,
+            0,5,0, 1,0,0, 0,0,0,
+            0,0,0, 8,0,6, 0,0,0
+        ]);
+
+        assert!(SudokuSolver::new(&board_with_extra_given).redundant_givens().contains(&(0, 2)));
+        assert_eq!(SudokuSolver::new(&minimal_board).count_solutions(2), 1);
+        assert_eq!(SudokuSolver::new(&minimal_board).redundant_givens(), vec![]);
+    }
+
     #[test]
     fn solve_caching_works() {
         let valid_board = SudokuBoard::new(&[
@@ -241,19 +1286,39 @@ mod tests {
         ]);
 
         let solver = SudokuSolver::new(&valid_board);
+        assert_eq!(solver.solve_count(), 0);
+        assert!(!solver.was_last_solve_cached());
 
-        let start_first = Instant::now();
         let solved_board_first = solver.solve();
-        let end_first = Instant::now();
-        let duration_first = end_first.duration_since(start_first).as_millis();
-
-        let start_second = Instant::now();
+        assert!(!solver.was_last_solve_cached());
         let solved_board_second = solver.solve();
-        let end_second = Instant::now();
-        let duration_second = end_second.duration_since(start_second).as_millis();
 
-        println!("Caching test took {}ms to solve in the first iteration and {}ms in the second iteration.", duration_first, duration_second);
         assert_eq!(solved_board_first, solved_board_second);
-        assert!(duration_second < duration_first);
+        assert_eq!(solver.solve_count(), 1);
+        assert!(solver.was_last_solve_cached());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn solve_count_after_refresh() {
+        let mut solver = SudokuSolver::new(&SudokuBoard::new(&[0; 81]));
+
+        solver.solve_mut().unwrap();
+        solver.solve_mut().unwrap();
+        assert!(solver.was_last_solve_cached());
+        solver.clear();
+        solver.solve();
+
+        assert_eq!(solver.solve_count(), 2);
+        assert!(!solver.was_last_solve_cached());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn solve_stats_serde_round_trip() {
+        let stats = SolveStats { propagated_cells: 12, searched_cells: 40, iterations: 1234 };
+        let json = serde_json::to_string(&stats).unwrap();
+
+        assert_eq!(json, "{\"propagated_cells\":12,\"searched_cells\":40,\"iterations\":1234}");
+        assert_eq!(serde_json::from_str::<SolveStats>(&json).unwrap(), stats);
+    }
+}
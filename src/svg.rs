@@ -0,0 +1,123 @@
+use std::fmt::Write;
+use crate::candidate_board::CandidateBoard;
+use crate::sudoku_board::{ SudokuBoard, NONET_ORIGIN };
+
+pub struct SvgOptions<'a> {
+    pub cell_size: u32,
+    pub givens: Option<&'a SudokuBoard>, // The starting puzzle, when absent every filled space is drawn as a given
+    pub candidates: Option<&'a CandidateBoard> // Pencil marks drawn in the empty spaces
+}
+
+impl<'a> Default for SvgOptions<'a> {
+    fn default() -> SvgOptions<'a> {
+        return SvgOptions {
+            cell_size: 50,
+            givens: None,
+            candidates: None
+        }
+    }
+}
+
+impl SudokuBoard {
+    pub fn to_svg(&self, options: &SvgOptions) -> String {
+        let cell_size = options.cell_size;
+        let board_size = 9 * cell_size;
+        let mut svg = String::new();
+
+        // Writing into a `String` can't fail, so the `fmt::Result`s below are safe to ignore
+        let _ = writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{0}" viewBox="0 0 {0} {0}">"#, board_size);
+        let _ = writeln!(svg, r#"<rect class="background" x="0" y="0" width="{0}" height="{0}" fill="white"/>"#, board_size);
+
+        for row in 0..=8 {
+            for column in 0..=8 {
+                let _ = writeln!(svg, r##"<rect class="cell" x="{}" y="{}" width="{2}" height="{2}" fill="none" stroke="#000000" stroke-width="1"/>"##,
+                    column as u32 * cell_size, row as u32 * cell_size, cell_size);
+            }
+        }
+
+        for (starting_row, starting_column) in NONET_ORIGIN.iter() {
+            let _ = writeln!(svg, r##"<rect class="nonet" x="{}" y="{}" width="{2}" height="{2}" fill="none" stroke="#000000" stroke-width="3"/>"##,
+                *starting_column as u32 * cell_size, *starting_row as u32 * cell_size, 3 * cell_size);
+        }
+
+        for row in 0..=8 {
+            for column in 0..=8 {
+                let value = self[(row, column)];
+                let x = column as u32 * cell_size + cell_size / 2;
+                let y = row as u32 * cell_size + cell_size / 2;
+
+                if value != 0 {
+                    let is_given = options.givens.map_or(true, |givens| givens[(row, column)] != 0);
+                    let _ = writeln!(svg, r#"<text class="{}" x="{}" y="{}" font-family="sans-serif" font-size="{}" font-weight="{}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                        if is_given { "given" } else { "entered" }, x, y, cell_size * 3 / 5, if is_given { "bold" } else { "normal" }, value);
+                }
+                else if let Some(candidate_board) = options.candidates {
+                    // Pencil marks sit in a 3x3 grid inside the space, laid out like a phone keypad
+                    for candidate in candidate_board.get(row, column).iter() {
+                        let mark_x = column as u32 * cell_size + (2 * ((candidate as u32 - 1) % 3) + 1) * cell_size / 6;
+                        let mark_y = row as u32 * cell_size + (2 * ((candidate as u32 - 1) / 3) + 1) * cell_size / 6;
+                        let _ = writeln!(svg, r##"<text class="candidate" x="{}" y="{}" font-family="sans-serif" font-size="{}" fill="#555555" text-anchor="middle" dominant-baseline="central">{}</text>"##,
+                            mark_x, mark_y, cell_size / 4, candidate);
+                    }
+                }
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        return svg;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::easy;
+
+    #[test]
+    fn to_svg_structure_works() {
+        let svg = easy().to_svg(&SvgOptions::default());
+
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="450" height="450""#));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches(r#"<rect class="cell""#).count(), 81);
+        assert_eq!(svg.matches(r#"<rect class="nonet""#).count(), 9);
+        assert_eq!(svg.matches(r#"<text class="given""#).count(), 76);
+        assert_eq!(svg.matches(r#"<text class="entered""#).count(), 0);
+        assert!(svg.contains(r#"<text class="given" x="75" y="25" font-family="sans-serif" font-size="30" font-weight="bold" text-anchor="middle" dominant-baseline="central">7</text>"#));
+        assert!(svg.contains(r#"<rect class="nonet" x="150" y="300" width="150" height="150""#));
+    }
+
+    #[test]
+    fn to_svg_entered_values_and_cell_size_works() {
+        let givens = easy();
+        let mut board = easy();
+        board.set_value(8, 8, 8);
+
+        let svg = board.to_svg(&SvgOptions { cell_size: 20, givens: Some(&givens), ..SvgOptions::default() });
+
+        assert!(svg.contains(r#"width="180" height="180""#));
+        assert_eq!(svg.matches(r#"<text class="given""#).count(), 76);
+        assert!(svg.contains(r#"<text class="entered" x="170" y="170" font-family="sans-serif" font-size="12" font-weight="normal" text-anchor="middle" dominant-baseline="central">8</text>"#));
+    }
+
+    #[test]
+    fn to_svg_candidates_works() {
+        let board = easy();
+        let candidate_board = CandidateBoard::new(&board);
+
+        let svg = board.to_svg(&SvgOptions { cell_size: 60, candidates: Some(&candidate_board), ..SvgOptions::default() });
+
+        assert_eq!(svg.matches(r#"<text class="candidate""#).count(), 6);
+        // 6 sits in the middle right of the top left space
+        assert!(svg.contains(r##"<text class="candidate" x="50" y="30" font-family="sans-serif" font-size="15" fill="#555555" text-anchor="middle" dominant-baseline="central">6</text>"##));
+    }
+
+    #[test]
+    fn to_svg_is_deterministic() {
+        let board = easy();
+        let candidate_board = CandidateBoard::new(&board);
+        let options = SvgOptions { candidates: Some(&candidate_board), ..SvgOptions::default() };
+
+        assert_eq!(board.to_svg(&options), board.to_svg(&options));
+    }
+}
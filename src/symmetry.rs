@@ -0,0 +1,111 @@
+use crate::sudoku_board::SudokuBoard;
+
+// Symmetries of a pattern of spaces, as used for the positions of givens
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Symmetry {
+    Rotational, // 180° around the center space
+    Horizontal, // Mirrored across the middle row
+    Vertical, // Mirrored across the middle column
+    Diagonal, // Mirrored across the top left to bottom right diagonal
+    AntiDiagonal // Mirrored across the top right to bottom left diagonal
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 5] = [Symmetry::Rotational, Symmetry::Horizontal, Symmetry::Vertical, Symmetry::Diagonal, Symmetry::AntiDiagonal];
+
+    // The space `space` maps onto, spaces on the axis (or the center, for rotations) map onto themselves
+    pub fn mirror(&self, space: (usize, usize)) -> (usize, usize) {
+        let (row_index, column_index) = space;
+        return match self {
+            Symmetry::Rotational => (8 - row_index, 8 - column_index),
+            Symmetry::Horizontal => (8 - row_index, column_index),
+            Symmetry::Vertical => (row_index, 8 - column_index),
+            Symmetry::Diagonal => (column_index, row_index),
+            Symmetry::AntiDiagonal => (8 - column_index, 8 - row_index)
+        };
+    }
+}
+
+impl SudokuBoard {
+    // Every symmetry the positions of the givens have, values are ignored. Spaces on an axis, and the center space for
+    // rotations, are their own mirror image so they never break a symmetry.
+    pub fn givens_symmetry(&self) -> Vec<Symmetry> {
+        return Symmetry::ALL.iter()
+            .copied()
+            .filter(|symmetry| (0..81).map(|index| (index / 9, index % 9)).all(|space| (self[space] == 0) == (self[symmetry.mirror(space)] == 0)))
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::classic;
+
+    #[test]
+    fn mirror_works() {
+        for symmetry in Symmetry::ALL.iter() {
+            assert_eq!(symmetry.mirror((4, 4)), (4, 4));
+            for index in 0..81 {
+                let space = (index / 9, index % 9);
+                assert_eq!(symmetry.mirror(symmetry.mirror(space)), space);
+            }
+        }
+        assert_eq!(Symmetry::Rotational.mirror((0, 1)), (8, 7));
+        assert_eq!(Symmetry::Horizontal.mirror((0, 1)), (8, 1));
+        assert_eq!(Symmetry::Vertical.mirror((0, 1)), (0, 7));
+        assert_eq!(Symmetry::Diagonal.mirror((0, 1)), (1, 0));
+        assert_eq!(Symmetry::AntiDiagonal.mirror((0, 1)), (7, 8));
+    }
+
+    #[test]
+    fn givens_symmetry_rotational_puzzle() {
+        let board = classic();
+
+        assert_eq!(board.givens_symmetry(), vec![Symmetry::Rotational]);
+    }
+
+    #[test]
+    fn givens_symmetry_asymmetric_puzzle() {
+        let board = SudokuBoard::new(&[
+            9,0,0, 0,1,0, 8,0,0,
+            0,0,7, 0,5,0, 0,0,6,
+            3,0,0, 0,0,0, 0,0,7,
+            6,4,0, 5,0,0, 0,7,0,
+            0,5,8, 0,0,0, 9,0,0,
+            0,0,0, 0,7,4, 0,0,5,
+            0,0,0, 0,0,0, 4,0,0,
+            0,3,0, 0,0,9, 5,0,0,
+            0,0,0, 2,8,6, 0,0,0
+        ]);
+
+        assert!(board.givens_symmetry().is_empty());
+    }
+
+    #[test]
+    fn givens_symmetry_several_at_once() {
+        // Mirrored across both middle lines, which makes the pattern rotationally symmetric as well
+        let board = SudokuBoard::new(&[
+            1,2,0, 0,0,0, 0,3,4,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,9,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            5,6,0, 0,0,0, 0,7,8
+        ]);
+
+        assert_eq!(board.givens_symmetry(), vec![Symmetry::Rotational, Symmetry::Horizontal, Symmetry::Vertical]);
+
+        // The center space and the diagonal spaces map onto themselves
+        let mut diagonal = [0; 81];
+        diagonal[0] = 1;
+        diagonal[40] = 5;
+        diagonal[80] = 9;
+        assert_eq!(SudokuBoard::new(&diagonal).givens_symmetry(), vec![Symmetry::Rotational, Symmetry::Diagonal, Symmetry::AntiDiagonal]);
+        assert_eq!(SudokuBoard::new(&[0; 81]).givens_symmetry(), Symmetry::ALL.to_vec());
+    }
+}
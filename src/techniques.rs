@@ -0,0 +1,876 @@
+use std::collections::HashMap;
+#[cfg(feature = "serde")]
+use std::convert::TryFrom;
+use std::fmt::{ self, Display, Formatter };
+use crate::candidate_board::{ CandidateBoard, CandidateSet };
+use crate::peers::PEERS;
+use crate::sudoku_board::{ House, SudokuBoard };
+use crate::uniqueness::{ is_unique, Uniqueness };
+
+// Logical solving techniques, declared from easiest to hardest so the derived ordering ranks them by difficulty
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "snake_case"))]
+pub enum Technique {
+    NakedSingle, // The space has a single candidate left
+    HiddenSingle, // The value has a single space left in one of the space's houses
+    LockedCandidates // The value is confined to where two houses overlap, so it can't go in the rest of either
+}
+
+impl Technique {
+    // The name in JSON, which stays put when the `Display` wording changes
+    pub fn tag(&self) -> &'static str {
+        return match self {
+            Technique::NakedSingle => "naked_single",
+            Technique::HiddenSingle => "hidden_single",
+            Technique::LockedCandidates => "locked_candidates"
+        };
+    }
+
+    // The inverse of `tag`
+    pub fn from_tag(tag: &str) -> Option<Technique> {
+        return [Technique::NakedSingle, Technique::HiddenSingle, Technique::LockedCandidates].iter().copied().find(|technique| technique.tag() == tag);
+    }
+}
+
+// Which techniques a logical solve may use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TechniqueSet {
+    techniques: u8 // Bit `n` is set for the technique declared `n`th in `Technique`
+}
+
+impl TechniqueSet {
+    pub fn new() -> TechniqueSet {
+        return TechniqueSet { techniques: 0 };
+    }
+
+    pub fn all() -> TechniqueSet {
+        return TechniqueSet::new().with(Technique::NakedSingle).with(Technique::HiddenSingle).with(Technique::LockedCandidates);
+    }
+
+    pub fn with(mut self, technique: Technique) -> TechniqueSet {
+        self.techniques |= 1 << technique as u8;
+        return self;
+    }
+
+    pub fn without(mut self, technique: Technique) -> TechniqueSet {
+        self.techniques &= !(1 << technique as u8);
+        return self;
+    }
+
+    pub fn contains(&self, technique: Technique) -> bool {
+        return self.techniques & 1 << technique as u8 != 0;
+    }
+}
+
+impl Default for TechniqueSet {
+    fn default() -> TechniqueSet {
+        return TechniqueSet::all();
+    }
+}
+
+// Whether a position, possibly with mistakes in it, still allows logical progress
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Progress {
+    Yes(usize), // How many placements the allowed techniques offer right now
+    NoButSolvable, // No allowed technique applies, but a solution still exists so guessing is needed
+    Contradiction // No solution is left, some entry is wrong
+}
+
+// A contradiction is reported even while moves are still available, those moves would only lead further astray
+pub fn can_progress_logically(board: &SudokuBoard, techniques: &TechniqueSet) -> Progress {
+    if is_unique(board) == Uniqueness::NoSolution {
+        return Progress::Contradiction;
+    }
+
+    return match allowed_singles(&mut CandidateBoard::new(board), techniques, &mut Vec::new()).len() {
+        0 => Progress::NoButSolvable,
+        moves => Progress::Yes(moves)
+    };
+}
+
+pub fn all_houses() -> impl Iterator<Item = House> {
+    return SudokuBoard::houses();
+}
+
+// The singles `technique_set` allows on `candidate_board`. Only when there are none, and locked candidates are allowed,
+// are its eliminations made on `candidate_board` one at a time, each added to `eliminated`, until some single turns up.
+fn allowed_singles(candidate_board: &mut CandidateBoard, technique_set: &TechniqueSet, eliminated: &mut Vec<((usize, usize), u8)>) -> Vec<Hint> {
+    loop {
+        let hints: Vec<Hint> = find_singles(candidate_board).into_iter().filter(|hint| technique_set.contains(hint.technique)).collect();
+        if !hints.is_empty() || !technique_set.contains(Technique::LockedCandidates) {
+            return hints;
+        }
+
+        match find_locked_candidates(candidate_board).into_iter().next().map(|hint| hint.kind) {
+            Some(HintKind::Eliminate { cells, value }) => for (row, column) in cells {
+                candidate_board.remove(row, column, value);
+                eliminated.push(((row, column), value));
+            },
+            _ => return hints
+        }
+    }
+}
+
+// The placements of a logical solve limited to `technique_set` and the board they lead to. The easiest allowed single
+// is placed at every step. When none is left, locked candidates eliminations are made until one turns up, and that
+// placement counts as `LockedCandidates` with the eliminations added to its `eliminated`.
+pub fn logical_solve(board: &SudokuBoard, technique_set: &TechniqueSet) -> (Vec<Hint>, SudokuBoard) {
+    let mut board = SudokuBoard::copy(board);
+    let mut candidate_board = CandidateBoard::new(&board);
+    let mut steps = Vec::new();
+    loop {
+        let mut eliminated = Vec::new();
+        let mut hint = match allowed_singles(&mut candidate_board, technique_set, &mut eliminated).into_iter().min_by_key(|hint| (hint.technique, hint.row, hint.column)) {
+            Some(hint) => hint,
+            None => return (steps, board)
+        };
+
+        board.set_value(hint.row, hint.column, hint.value);
+        candidate_board.set(hint.row, hint.column, CandidateSet::new());
+        for peer in PEERS[9 * hint.row + hint.column].iter() {
+            candidate_board.remove(peer / 9, peer % 9, hint.value);
+        }
+        if !eliminated.is_empty() {
+            hint.technique = Technique::LockedCandidates;
+            hint.details.eliminated.extend(eliminated);
+        }
+        steps.push(hint);
+    }
+}
+
+// Why a hint holds, in a structured form so callers can word it themselves
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    pub technique: Technique,
+    pub space: (usize, usize),
+    pub value: u8,
+    pub house: Option<House>, // The house a hidden single is the only place for `value` in
+    pub cells: Vec<(usize, usize)>, // Every space the reasoning looks at, including `space`
+    pub eliminated: Vec<((usize, usize), u8)> // Candidates ruled out along the way
+}
+
+impl Display for Explanation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let space = format!("R{}C{}", self.space.0 + 1, self.space.1 + 1);
+        return match (self.technique, self.house) {
+            (Technique::HiddenSingle, Some(house)) => write!(f, "{} is the only place left for {} in {}", space, self.value, house_name(house)),
+            _ => write!(f, "{} can only be {}: every other digit already appears in its row, column, or box", space, self.value)
+        };
+    }
+}
+
+fn house_name(house: House) -> String {
+    return match house {
+        House::Row(row_index) => format!("row {}", row_index + 1),
+        House::Column(column_index) => format!("column {}", column_index + 1),
+        House::Nonet(nonet_index) => format!("box {}", nonet_index + 1)
+    };
+}
+
+// How much a hint gives away, from least to most
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum HintLevel {
+    Nudge, // The technique and the box to look in
+    House, // The technique and the house the reasoning happens in
+    Cell, // The space to fill, without its value
+    Full // The space, its value and why
+}
+
+// One finding disclosed up to `level`, every field a lower level shows is also shown by the higher ones
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeveledHint {
+    pub level: HintLevel,
+    pub technique: Technique,
+    pub nonet: usize,
+    pub house: Option<House>, // From `HintLevel::House`, for naked singles this is the space's row
+    pub space: Option<(usize, usize)>, // From `HintLevel::Cell`
+    pub value: Option<u8>, // From `HintLevel::Full`
+    pub explanation: Option<String> // From `HintLevel::Full`
+}
+
+impl Display for LeveledHint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let technique = match self.technique {
+            Technique::NakedSingle => "naked single",
+            Technique::HiddenSingle => "hidden single",
+            Technique::LockedCandidates => "locked candidate"
+        };
+        if let Some(explanation) = &self.explanation {
+            return write!(f, "{}", explanation);
+        }
+        if let Some((row, column)) = self.space {
+            return write!(f, "R{}C{} can be filled with a {}", row + 1, column + 1, technique);
+        }
+        if let Some(house) = self.house {
+            return write!(f, "Look for a {} in {}", technique, house_name(house));
+        }
+        return write!(f, "There's a {} in box {}", technique, self.nonet + 1);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(into = "HintRecord", try_from = "HintRecord"))]
+pub struct Hint {
+    pub row: usize,
+    pub column: usize,
+    pub value: u8,
+    pub technique: Technique,
+    pub details: Explanation
+}
+
+impl Hint {
+    pub fn explanation(&self) -> String {
+        return self.details.to_string();
+    }
+}
+
+// The serde form of a `Hint`. `house` is a label like `R1`, `C2` or `B3`, or null, `eliminated` holds
+// `[row, col, value]`, and `explanation` is derived from the rest, so it's written but never read.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct HintRecord {
+    technique: Technique,
+    row: usize,
+    col: usize,
+    value: u8,
+    house: Option<House>,
+    cells: Vec<(usize, usize)>,
+    eliminated: Vec<(usize, usize, u8)>,
+    #[serde(default)]
+    explanation: String
+}
+
+#[cfg(feature = "serde")]
+impl From<Hint> for HintRecord {
+    fn from(hint: Hint) -> HintRecord {
+        return HintRecord {
+            explanation: hint.explanation(),
+            technique: hint.technique,
+            row: hint.row,
+            col: hint.column,
+            value: hint.value,
+            house: hint.details.house,
+            cells: hint.details.cells,
+            eliminated: hint.details.eliminated.into_iter().map(|(space, value)| (space.0, space.1, value)).collect()
+        };
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<HintRecord> for Hint {
+    type Error = &'static str;
+
+    fn try_from(record: HintRecord) -> Result<Hint, &'static str> {
+        let in_range = |(row, column): (usize, usize)| row < 9 && column < 9;
+        if !in_range((record.row, record.col)) || !record.cells.iter().copied().all(in_range) {
+            return Err("rows and columns must be [0..8] inclusive");
+        }
+        if !(1..=9).contains(&record.value) || !record.eliminated.iter().all(|&(row, column, value)| in_range((row, column)) && (1..=9).contains(&value)) {
+            return Err("values must be [1..9] inclusive");
+        }
+
+        let eliminated = record.eliminated.into_iter().map(|(row, column, value)| ((row, column), value)).collect();
+        let details = Explanation { technique: record.technique, space: (record.row, record.col), value: record.value, house: record.house, cells: record.cells, eliminated };
+        return Ok(Hint { row: record.row, column: record.col, value: record.value, technique: record.technique, details });
+    }
+}
+
+// What a step hint asks the player to do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HintKind {
+    Place { row: usize, column: usize, value: u8 },
+    Eliminate { cells: Vec<(usize, usize)>, value: u8 } // Remove `value` from the pencil marks of every one of `cells`
+}
+
+// The next logical step from a set of candidates, which may be a placement or only an elimination
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepHint {
+    pub kind: HintKind,
+    pub technique: Technique,
+    pub house: Option<House>, // For locked candidates, the house the value is confined within
+    pub explanation: String
+}
+
+// Placements come first, easiest technique first. Only when none is left is an elimination suggested, so pencil marks
+// can get pruned down to the next placement.
+pub fn step_hint(candidate_board: &CandidateBoard) -> Option<StepHint> {
+    if let Some(hint) = find_singles(candidate_board).into_iter().min_by_key(|hint| (hint.technique, hint.row, hint.column)) {
+        return Some(StepHint {
+            kind: HintKind::Place { row: hint.row, column: hint.column, value: hint.value },
+            technique: hint.technique,
+            house: hint.details.house,
+            explanation: hint.explanation()
+        });
+    }
+    return find_locked_candidates(candidate_board).into_iter().next();
+}
+
+// Pointing (a nonet's candidates for a value all in one row or column) first, nonet by nonet, then claiming (a row or
+// column's candidates all in one nonet). Only patterns that actually eliminate something are reported.
+pub fn find_locked_candidates(candidate_board: &CandidateBoard) -> Vec<StepHint> {
+    let mut hints = Vec::new();
+    for chute_index in 0..6 {
+        // Bands and stacks are both three lines crossing three nonets. In their coordinate order every 9 spaces are a
+        // line and every 3 spaces along it the segment where that line meets a nonet.
+        let spaces: Vec<(usize, usize)> = if chute_index < 3 {
+            SudokuBoard::band_coordinates(chute_index).collect()
+        }
+        else {
+            SudokuBoard::stack_coordinates(chute_index - 3).collect()
+        };
+        let line = |line_index: usize| match chute_index {
+            0..=2 => House::Row(3 * chute_index + line_index),
+            _ => House::Column(3 * (chute_index - 3) + line_index)
+        };
+        let nonet = |segment_index: usize| House::Nonet(SudokuBoard::nonet_index_of(spaces[3 * segment_index].0, spaces[3 * segment_index].1));
+
+        for value in 1..=9 {
+            let has_value = |offset: usize| candidate_board.get(spaces[offset].0, spaces[offset].1).contains(value);
+            let segment_count = |line: usize, segment: usize| (0..3).filter(|cell| has_value(9 * line + 3 * segment + cell)).count();
+            let counts: Vec<Vec<usize>> = (0..3).map(|line| (0..3).map(|segment| segment_count(line, segment)).collect()).collect();
+
+            for line_index in 0..3 {
+                for segment_index in 0..3 {
+                    if counts[line_index][segment_index] == 0 {
+                        continue;
+                    }
+                    let nonet_total: usize = (0..3).map(|other| counts[other][segment_index]).sum();
+                    let line_total: usize = counts[line_index].iter().sum();
+
+                    // Pointing, the nonet's spaces for the value all sit on this line
+                    if nonet_total >= 2 && nonet_total == counts[line_index][segment_index] && line_total > nonet_total {
+                        let cells = (0..9).map(|cell| 9 * line_index + cell).filter(|offset| (offset % 9) / 3 != segment_index && has_value(*offset)).map(|offset| spaces[offset]).collect();
+                        hints.push(locked_hint(nonet(segment_index), line(line_index), cells, value));
+                    }
+                    // Claiming, the line's spaces for the value all sit in this nonet
+                    if line_total >= 2 && line_total == counts[line_index][segment_index] && nonet_total > line_total {
+                        let cells = (0..3).filter(|other| *other != line_index).flat_map(|other| (0..3).map(move |cell| 9 * other + 3 * segment_index + cell)).filter(|offset| has_value(*offset)).map(|offset| spaces[offset]).collect();
+                        hints.push(locked_hint(line(line_index), nonet(segment_index), cells, value));
+                    }
+                }
+            }
+        }
+    }
+
+    // Nonets before rows before columns, each in index order, then by value
+    hints.sort_by_key(|hint| {
+        let value = match hint.kind {
+            HintKind::Eliminate { value, .. } => value,
+            HintKind::Place { value, .. } => value
+        };
+        return match hint.house {
+            Some(House::Nonet(index)) => (0, index, value),
+            Some(House::Row(index)) => (1, index, value),
+            Some(House::Column(index)) => (2, index, value),
+            None => (3, 0, value)
+        };
+    });
+    return hints;
+}
+
+// `house` confines the value to where it meets `other_house`, eliminating it from `cells` in the rest of `other_house`
+fn locked_hint(house: House, other_house: House, mut cells: Vec<(usize, usize)>, value: u8) -> StepHint {
+    cells.sort_unstable();
+    let explanation = format!("In {}, {} can only go where it meets {}, so it can be removed from the rest of {}", house_name(house), value, house_name(other_house), house_name(other_house));
+    return StepHint { kind: HintKind::Eliminate { cells, value }, technique: Technique::LockedCandidates, house: Some(house), explanation };
+}
+
+// The first contradiction `diagnose_unsolvable` runs into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnsolvableReason {
+    CellHasNoCandidates { row: usize, column: usize },
+    DigitCannotBePlacedInHouse { house: House, digit: u8 } // No empty space of `house` can take `digit` anymore
+}
+
+impl Display for UnsolvableReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        return match *self {
+            UnsolvableReason::CellHasNoCandidates { row, column } => write!(f, "R{}C{} has no candidates left", row + 1, column + 1),
+            UnsolvableReason::DigitCannotBePlacedInHouse { house, digit } => write!(f, "{} can't go anywhere in {}", digit, house_name(house))
+        };
+    }
+}
+
+// Spaces row-major first, then houses in `SudokuBoard::houses` order
+fn find_contradiction(board: &SudokuBoard, candidate_board: &CandidateBoard) -> Option<UnsolvableReason> {
+    for row_index in 0..=8 {
+        for column_index in 0..=8 {
+            if board.digit(row_index, column_index).is_none() && candidate_board.get(row_index, column_index).is_empty() {
+                return Some(UnsolvableReason::CellHasNoCandidates { row: row_index, column: column_index });
+            }
+        }
+    }
+
+    for house in SudokuBoard::houses() {
+        for digit in board.missing_in(house).iter() {
+            if !SudokuBoard::house_coordinates(house).any(|(row_index, column_index)| candidate_board.get(row_index, column_index).contains(digit)) {
+                return Some(UnsolvableReason::DigitCannotBePlacedInHouse { house, digit });
+            }
+        }
+    }
+    return None;
+}
+
+impl SudokuBoard {
+    // The easiest placement available, ties broken row-major
+    pub fn hint(&self) -> Option<Hint> {
+        return find_singles(&CandidateBoard::new(self)).into_iter().min_by_key(|hint| (hint.technique, hint.row, hint.column));
+    }
+
+    // The same finding as `hint`, disclosed only up to `level`
+    pub fn hint_leveled(&self, level: HintLevel) -> Option<LeveledHint> {
+        let hint = self.hint()?;
+        let house = hint.details.house.unwrap_or(House::Row(hint.row));
+        return Some(LeveledHint {
+            level,
+            technique: hint.technique,
+            nonet: SudokuBoard::nonet_index_of(hint.row, hint.column),
+            house: Some(house).filter(|_| level >= HintLevel::House),
+            space: Some((hint.row, hint.column)).filter(|_| level >= HintLevel::Cell),
+            value: Some(hint.value).filter(|_| level == HintLevel::Full),
+            explanation: Some(hint.explanation()).filter(|_| level == HintLevel::Full)
+        });
+    }
+
+    // The board with singles filled in pass after pass until none are left, the usual first step before searching.
+    // Stops early rather than place two clashing values on a board with no solution.
+    pub fn simplify(&self) -> SudokuBoard {
+        let mut simplified = SudokuBoard::copy(self);
+        loop {
+            let hints = find_singles(&CandidateBoard::new(&simplified));
+            if hints.is_empty() {
+                return simplified;
+            }
+
+            let mut next = SudokuBoard::copy(&simplified);
+            for hint in hints.iter() {
+                next.set_value(hint.row, hint.column, hint.value);
+            }
+            if !next.all_spaces_valid() {
+                return simplified;
+            }
+            simplified = next;
+        }
+    }
+
+    // Why the board has no solution, found by filling in singles one at a time until a space or a house runs out of
+    // options. `None` when the singles stall first, the board then either has a solution or only fails deeper in the
+    // search, and for boards that already break the rules.
+    pub fn diagnose_unsolvable(&self) -> Option<UnsolvableReason> {
+        if !self.all_spaces_valid() {
+            return None;
+        }
+
+        let mut board = SudokuBoard::copy(self);
+        loop {
+            let candidate_board = CandidateBoard::new(&board);
+            if let Some(reason) = find_contradiction(&board, &candidate_board) {
+                return Some(reason);
+            }
+
+            // Singles only ever place a candidate, so the board keeps to the rules along the way
+            let hint = find_singles(&candidate_board).into_iter().min_by_key(|hint| (hint.technique, hint.row, hint.column))?;
+            board.set_value(hint.row, hint.column, hint.value);
+        }
+    }
+
+    // Every space that can be filled right now by a single, from one candidate pass. Spaces that are both a naked and
+    // a hidden single are reported as naked singles.
+    pub fn singles_map(&self) -> HashMap<(usize, usize), (u8, Technique)> {
+        return singles(&CandidateBoard::new(self));
+    }
+}
+
+pub fn singles(candidate_board: &CandidateBoard) -> HashMap<(usize, usize), (u8, Technique)> {
+    return find_singles(candidate_board).into_iter().map(|hint| ((hint.row, hint.column), (hint.value, hint.technique))).collect();
+}
+
+// Naked singles row-major, then hidden singles house by house. Each space appears at most once.
+pub fn find_singles(candidate_board: &CandidateBoard) -> Vec<Hint> {
+    let mut hints: Vec<Hint> = Vec::new();
+    for row_index in 0..=8 {
+        for column_index in 0..=8 {
+            let candidates = candidate_board.get(row_index, column_index);
+            if candidates.len() != 1 {
+                continue;
+            }
+
+            let value = candidates.iter().next().unwrap();
+            hints.push(Hint {
+                row: row_index,
+                column: column_index,
+                value,
+                technique: Technique::NakedSingle,
+                details: Explanation {
+                    technique: Technique::NakedSingle,
+                    space: (row_index, column_index),
+                    value,
+                    house: None,
+                    cells: vec![(row_index, column_index)],
+                    eliminated: (1..=9).filter(|other| *other != value).map(|other| ((row_index, column_index), other)).collect()
+                }
+            });
+        }
+    }
+
+    for house in all_houses() {
+        let house_spaces = house.spaces();
+        for value in 1..=9 {
+            let mut value_spaces = house_spaces.iter().filter(|&&(row_index, column_index)| candidate_board.get(row_index, column_index).contains(value));
+            let space = match (value_spaces.next(), value_spaces.next()) {
+                (Some(space), None) => *space,
+                _ => continue
+            };
+            if hints.iter().any(|hint| (hint.row, hint.column) == space) {
+                continue; // Only the first finding is kept, a space can't hold two values anyway
+            }
+
+            // The other unsolved spaces of the house can't hold the value, which is what makes this space the only option
+            let blocked_spaces: Vec<(usize, usize)> = house_spaces.iter()
+                .filter(|&&other| other != space && !candidate_board.get(other.0, other.1).is_empty())
+                .copied()
+                .collect();
+            let mut cells = vec![space];
+            cells.extend(blocked_spaces.iter().copied());
+            hints.push(Hint {
+                row: space.0,
+                column: space.1,
+                value,
+                technique: Technique::HiddenSingle,
+                details: Explanation {
+                    technique: Technique::HiddenSingle,
+                    space,
+                    value,
+                    house: Some(house),
+                    cells,
+                    eliminated: blocked_spaces.iter().map(|blocked_space| (*blocked_space, value)).collect()
+                }
+            });
+        }
+    }
+
+    return hints;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::medium;
+    use crate::sudoku_solver::{ SolveError, SudokuSolver };
+
+    #[test]
+    fn can_progress_logically_works() {
+        // A candidate of the first empty space that isn't its solution, so nothing conflicts yet
+        let mut mistaken = medium();
+        let solution = SudokuSolver::new(&mistaken).solve();
+        let wrong_value = CandidateBoard::new(&mistaken).get(0, 2).iter().find(|value| *value != solution[(0, 2)]).unwrap();
+        mistaken.set_value(0, 2, wrong_value);
+
+        assert!(matches!(can_progress_logically(&medium(), &TechniqueSet::all()), Progress::Yes(moves) if moves > 0));
+        assert_eq!(can_progress_logically(&exhausted(), &TechniqueSet::all()), Progress::NoButSolvable);
+        assert!(mistaken.all_spaces_valid());
+        assert_eq!(can_progress_logically(&mistaken, &TechniqueSet::all()), Progress::Contradiction);
+        assert_eq!(can_progress_logically(&medium(), &TechniqueSet::new()), Progress::NoButSolvable);
+    }
+
+    #[test]
+    fn locked_candidates_unlock_singles() {
+        let board = SudokuBoard::new(&[
+            0,0,0, 0,0,8, 0,0,0,
+            2,0,3, 0,0,1, 0,0,0,
+            0,9,7, 0,0,0, 5,8,0,
+            0,0,0, 1,0,0, 0,6,0,
+            7,0,0, 6,0,0, 3,0,0,
+            0,2,0, 0,0,0, 0,5,0,
+            9,0,0, 0,8,0, 0,0,0,
+            0,0,8, 0,4,3, 0,9,0,
+            0,3,5, 0,0,6, 0,0,0
+        ]);
+        let singles = TechniqueSet::all().without(Technique::LockedCandidates);
+
+        let (singles_steps, stalled) = logical_solve(&board, &singles);
+        assert_eq!((singles_steps.len(), stalled.unsolved_count()), (15, 42));
+        assert_eq!(can_progress_logically(&stalled, &singles), Progress::NoButSolvable);
+        assert!(matches!(can_progress_logically(&stalled, &TechniqueSet::all()), Progress::Yes(moves) if moves > 0));
+
+        let (steps, solved) = logical_solve(&board, &TechniqueSet::all());
+        assert_eq!(solved, SudokuSolver::new(&board).solve());
+        let unlocked = &steps[singles_steps.len()];
+        assert_eq!(unlocked.technique, Technique::LockedCandidates);
+        assert!(unlocked.details.eliminated.len() > unlocked.details.cells.len()); // The single's own reasoning plus the eliminations
+    }
+
+    // The medium fixture with 12 of its givens removed, leaving no single anywhere
+    fn exhausted() -> SudokuBoard {
+        return SudokuBoard::new(&[
+            7,8,0, 4,0,0, 1,2,0,
+            0,0,0, 0,0,5, 0,0,0,
+            0,0,0, 6,0,1, 0,0,0,
+            0,0,0, 0,0,0, 2,6,0,
+            0,0,1, 0,5,0, 9,3,0,
+            9,0,4, 0,6,0, 0,0,0,
+            0,0,0, 0,0,0, 0,1,2,
+            0,0,0, 0,0,7, 4,0,0,
+            0,4,9, 2,0,6, 0,0,7
+        ]);
+    }
+
+    #[test]
+    fn simplify_works() {
+        // The medium fixture falls to singles alone, the exhausted one has none to begin with
+        assert_eq!(medium().simplify(), SudokuSolver::new(&medium()).solve());
+        assert_eq!(exhausted().simplify(), exhausted());
+        assert!(medium().simplify().all_spaces_solved());
+    }
+
+    #[test]
+    fn diagnose_unsolvable_works() {
+        let mut no_candidates = [0; 81];
+        no_candidates[..8].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        no_candidates[17] = 9;
+        assert_eq!(SudokuBoard::new(&no_candidates).diagnose_unsolvable(), Some(UnsolvableReason::CellHasNoCandidates { row: 0, column: 8 }));
+
+        // R1C8 and R1C9 can both be 8, but the 9 in box 3 leaves no place for row 1's 9
+        let mut no_place = [0; 81];
+        no_place[..7].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7]);
+        no_place[16] = 9;
+        let reason = SudokuBoard::new(&no_place).diagnose_unsolvable();
+        assert_eq!(reason, Some(UnsolvableReason::DigitCannotBePlacedInHouse { house: House::Row(0), digit: 9 }));
+        assert_eq!(reason.unwrap().to_string(), "9 can't go anywhere in row 1");
+
+        // Only after R1C8 is filled in as the naked single 8 does R4C8 run out of candidates
+        let mut after_singles = [0; 81];
+        after_singles[..7].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7]);
+        after_singles[27..36].copy_from_slice(&[2, 3, 4, 5, 6, 7, 9, 0, 1]);
+        after_singles[70] = 9;
+        let board = SudokuBoard::new(&after_singles);
+        assert!(find_contradiction(&board, &CandidateBoard::new(&board)).is_none());
+        assert_eq!(board.diagnose_unsolvable(), Some(UnsolvableReason::CellHasNoCandidates { row: 3, column: 7 }));
+    }
+
+    #[test]
+    fn diagnose_unsolvable_deep_contradiction() {
+        // AI Escargot with a wrong 2 in R1C2, the contradiction only shows up once the search starts guessing
+        let board = SudokuBoard::new(&[
+            1,2,0, 0,0,7, 0,9,0,
+            0,3,0, 0,2,0, 0,0,8,
+            0,0,9, 6,0,0, 5,0,0,
+            0,0,5, 3,0,0, 9,0,0,
+            0,1,0, 0,8,0, 0,0,2,
+            6,0,0, 0,0,4, 0,0,0,
+            3,0,0, 0,0,0, 0,1,0,
+            0,4,0, 0,0,0, 0,0,7,
+            0,0,7, 0,0,0, 3,0,0
+        ]);
+
+        assert_eq!(board.diagnose_unsolvable(), None);
+        assert_eq!(SudokuSolver::new(&board).try_solve(), Err(SolveError::NoSolution(None)));
+        assert_eq!(medium().diagnose_unsolvable(), None);
+        assert_eq!(SudokuBoard::new(&[5; 81]).diagnose_unsolvable(), None);
+    }
+
+    #[test]
+    fn step_hint_eliminates_when_no_placement() {
+        let candidate_board = CandidateBoard::new(&exhausted());
+        assert!(find_singles(&candidate_board).is_empty());
+
+        let hint = step_hint(&candidate_board).unwrap();
+
+        // Box 6 can only hold its 4 in column 9, where rows 4 and 5 cross it
+        assert_eq!(hint.kind, HintKind::Eliminate { cells: vec![(1, 8), (2, 8)], value: 4 });
+        assert_eq!(hint.technique, Technique::LockedCandidates);
+        assert_eq!(hint.house, Some(House::Nonet(5)));
+        assert_eq!(hint.explanation, "In box 6, 4 can only go where it meets column 9, so it can be removed from the rest of column 9");
+    }
+
+    #[test]
+    fn step_hint_prefers_placements() {
+        let hint = step_hint(&CandidateBoard::new(&medium())).unwrap();
+
+        assert!(matches!(hint.kind, HintKind::Place { .. }));
+        assert_eq!(hint.technique, Technique::NakedSingle);
+    }
+
+    #[test]
+    fn technique_set_works() {
+        let technique_set = TechniqueSet::new().with(Technique::HiddenSingle);
+
+        assert!(technique_set.contains(Technique::HiddenSingle));
+        assert!(!technique_set.contains(Technique::NakedSingle));
+        assert!(TechniqueSet::all().contains(Technique::NakedSingle));
+        assert_eq!(TechniqueSet::default(), TechniqueSet::all());
+        assert_eq!(TechniqueSet::all().without(Technique::NakedSingle).without(Technique::LockedCandidates), technique_set);
+    }
+
+    #[test]
+    fn singles_map_works() {
+        let board = SudokuBoard::new(&[
+            0,7,0, 8,9,4, 0,0,0,
+            9,1,2, 7,3,5, 0,0,6,
+            8,0,0, 0,0,2, 0,7,0,
+            0,0,0, 0,0,0, 0,5,0,
+            5,2,6, 0,7,0, 0,0,0,
+            1,3,4, 0,0,0, 0,6,0,
+            0,6,0, 0,0,0, 0,3,0,
+            0,0,0, 3,0,6, 0,0,0,
+            3,0,1, 0,4,0, 6,0,0
+        ]);
+
+        let singles_map = board.singles_map();
+
+        let mut expected = HashMap::new();
+        expected.insert((0, 0), (6, Technique::NakedSingle));
+        expected.insert((3, 0), (7, Technique::NakedSingle));
+        expected.insert((2, 1), (4, Technique::HiddenSingle));
+        assert_eq!(singles_map, expected);
+    }
+
+    #[test]
+    fn singles_map_prefers_naked_singles() {
+        // Every empty space of a nearly solved board is both a naked and a hidden single
+        let board = SudokuBoard::new(&[
+            0,7,3, 8,9,4, 5,1,2,
+            9,1,2, 7,3,5, 4,8,6,
+            8,4,5, 6,1,2, 9,7,3,
+            7,9,8, 2,6,1, 3,5,4,
+            5,2,6, 4,7,3, 8,9,1,
+            1,3,4, 5,8,9, 2,6,7,
+            4,6,9, 1,2,8, 7,3,5,
+            2,8,7, 3,5,6, 1,4,9,
+            3,5,1, 9,4,7, 6,2,0
+        ]);
+
+        let singles_map = board.singles_map();
+
+        assert_eq!(singles_map.len(), 2);
+        assert_eq!(singles_map[&(0, 0)], (6, Technique::NakedSingle));
+        assert_eq!(singles_map[&(8, 8)], (8, Technique::NakedSingle));
+    }
+
+    #[test]
+    fn hint_naked_single_explanation_works() {
+        let board = SudokuBoard::new(&[
+            0,7,0, 8,9,4, 0,0,0,
+            9,1,2, 7,3,5, 0,0,6,
+            8,0,0, 0,0,2, 0,7,0,
+            0,0,0, 0,0,0, 0,5,0,
+            5,2,6, 0,7,0, 0,0,0,
+            1,3,4, 0,0,0, 0,6,0,
+            0,6,0, 0,0,0, 0,3,0,
+            0,0,0, 3,0,6, 0,0,0,
+            3,0,1, 0,4,0, 6,0,0
+        ]);
+
+        let hint = board.hint().unwrap();
+
+        assert_eq!((hint.row, hint.column, hint.value, hint.technique), (0, 0, 6, Technique::NakedSingle));
+        assert_eq!(hint.details, Explanation {
+            technique: Technique::NakedSingle,
+            space: (0, 0),
+            value: 6,
+            house: None,
+            cells: vec![(0, 0)],
+            eliminated: vec![((0, 0), 1), ((0, 0), 2), ((0, 0), 3), ((0, 0), 4), ((0, 0), 5), ((0, 0), 7), ((0, 0), 8), ((0, 0), 9)]
+        });
+        assert_eq!(hint.explanation(), "R1C1 can only be 6: every other digit already appears in its row, column, or box");
+    }
+
+    #[test]
+    fn hint_hidden_single_explanation_works() {
+        let board = SudokuBoard::new(&[
+            0,7,0, 8,9,4, 0,0,0,
+            9,1,2, 7,3,5, 0,0,6,
+            8,0,0, 0,0,2, 0,7,0,
+            0,0,0, 0,0,0, 0,5,0,
+            5,2,6, 0,7,0, 0,0,0,
+            1,3,4, 0,0,0, 0,6,0,
+            0,6,0, 0,0,0, 0,3,0,
+            0,0,0, 3,0,6, 0,0,0,
+            3,0,1, 0,4,0, 6,0,0
+        ]);
+
+        let hint = find_singles(&CandidateBoard::new(&board)).into_iter().find(|hint| hint.technique == Technique::HiddenSingle).unwrap();
+
+        assert_eq!((hint.row, hint.column, hint.value), (2, 1, 4));
+        assert_eq!(hint.details.house, Some(House::Nonet(0)));
+        assert_eq!(hint.details.cells, vec![(2, 1), (0, 0), (0, 2), (2, 2)]);
+        assert_eq!(hint.details.eliminated, vec![((0, 0), 4), ((0, 2), 4), ((2, 2), 4)]);
+        assert_eq!(hint.explanation(), "R3C2 is the only place left for 4 in box 1");
+    }
+
+    #[test]
+    fn hint_leveled_works() {
+        let mut board = SudokuBoard::new(&[
+            0,7,0, 8,9,4, 0,0,0,
+            9,1,2, 7,3,5, 0,0,6,
+            8,0,0, 0,0,2, 0,7,0,
+            0,0,0, 0,0,0, 0,5,0,
+            5,2,6, 0,7,0, 0,0,0,
+            1,3,4, 0,0,0, 0,6,0,
+            0,6,0, 0,0,0, 0,3,0,
+            0,0,0, 3,0,6, 0,0,0,
+            3,0,1, 0,4,0, 6,0,0
+        ]);
+        board.set_value(0, 0, 6);
+        board.set_value(3, 0, 7);
+
+        let levels = [HintLevel::Nudge, HintLevel::House, HintLevel::Cell, HintLevel::Full];
+        let hints: Vec<LeveledHint> = levels.iter().map(|level| board.hint_leveled(*level).unwrap()).collect();
+        let full_hint = board.hint().unwrap();
+
+        assert_eq!(hints.iter().map(LeveledHint::to_string).collect::<Vec<String>>(), vec![
+            String::from("There's a hidden single in box 1"),
+            String::from("Look for a hidden single in box 1"),
+            String::from("R3C2 can be filled with a hidden single"),
+            full_hint.explanation()
+        ]);
+
+        // Every level repeats what the levels below it said and adds to it
+        for pair in hints.windows(2) {
+            assert_eq!((pair[0].technique, pair[0].nonet), (pair[1].technique, pair[1].nonet));
+            assert!(pair[0].house.is_none() || pair[0].house == pair[1].house);
+            assert!(pair[0].space.is_none() || pair[0].space == pair[1].space);
+        }
+        assert_eq!(hints[1].house, full_hint.details.house);
+        assert_eq!(hints[2].space, Some((full_hint.row, full_hint.column)));
+        assert_eq!((hints[2].value, hints[3].value), (None, Some(full_hint.value)));
+
+        board.clear_value(0, 0);
+        assert_eq!(board.hint_leveled(HintLevel::House).unwrap().to_string(), "Look for a naked single in row 1");
+    }
+
+    #[test]
+    fn hint_solved_board() {
+        let board = SudokuBoard::new(&[
+            6,7,3, 8,9,4, 5,1,2,
+            9,1,2, 7,3,5, 4,8,6,
+            8,4,5, 6,1,2, 9,7,3,
+            7,9,8, 2,6,1, 3,5,4,
+            5,2,6, 4,7,3, 8,9,1,
+            1,3,4, 5,8,9, 2,6,7,
+            4,6,9, 1,2,8, 7,3,5,
+            2,8,7, 3,5,6, 1,4,9,
+            3,5,1, 9,4,7, 6,2,8
+        ]);
+
+        assert_eq!(board.hint(), None);
+    }
+
+    #[test]
+    fn technique_ordering_works() {
+        assert!(Technique::NakedSingle < Technique::HiddenSingle);
+        assert_eq!(all_houses().count(), 27);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn hint_serde_round_trip() {
+        let hints: Vec<Hint> = find_singles(&CandidateBoard::new(&medium())).into_iter().collect();
+        let hidden_single = hints.iter().find(|hint| hint.technique == Technique::HiddenSingle).unwrap();
+
+        assert_eq!(serde_json::to_string(hidden_single).unwrap(), "{\"technique\":\"hidden_single\",\"row\":0,\"col\":2,\"value\":5,\"house\":\"R1\",\"cells\":[[0,2],[0,4],[0,5],[0,8]],\"eliminated\":[[0,4,5],[0,5,5],[0,8,5]],\"explanation\":\"R1C3 is the only place left for 5 in row 1\"}");
+        for hint in hints.iter() {
+            assert_eq!(&serde_json::from_str::<Hint>(&serde_json::to_string(hint).unwrap()).unwrap(), hint);
+        }
+    }
+}
@@ -0,0 +1,62 @@
+// Boards shared by the test modules
+
+use crate::sudoku_board::SudokuBoard;
+
+// Five spaces short of solved, naked singles finish it
+pub fn easy() -> SudokuBoard {
+    return SudokuBoard::new(&[
+        0,7,3, 8,9,4, 5,1,2,
+        9,1,2, 7,3,5, 4,8,6,
+        8,4,5, 0,0,2, 9,7,3,
+        7,9,8, 2,6,1, 3,5,4,
+        5,2,6, 4,7,3, 8,9,1,
+        1,3,4, 5,8,9, 2,6,7,
+        4,6,9, 0,2,8, 7,3,5,
+        2,8,7, 3,5,6, 1,4,9,
+        3,5,1, 9,4,7, 6,2,0
+    ]);
+}
+
+pub fn medium() -> SudokuBoard {
+    return SudokuBoard::new(&[
+        7,8,0, 4,0,0, 1,2,0,
+        6,0,0, 0,7,5, 0,0,9,
+        0,0,0, 6,0,1, 0,7,8,
+        0,0,7, 0,4,0, 2,6,0,
+        0,0,1, 0,5,0, 9,3,0,
+        9,0,4, 0,6,0, 0,0,5,
+        0,7,0, 3,0,0, 0,1,2,
+        1,2,0, 0,0,7, 4,0,0,
+        0,4,9, 2,0,6, 0,0,7
+    ]);
+}
+
+// 17 givens, slow enough for the backtracking to be cancelled partway
+pub fn hard() -> SudokuBoard {
+    return SudokuBoard::new(&[
+        0,0,0, 0,0,0, 0,0,0,
+        0,0,2, 0,0,5, 0,4,0,
+        1,0,8, 0,4,0, 0,0,0,
+        0,0,0, 0,0,0, 4,0,3,
+        0,0,6, 0,5,0, 0,0,1,
+        0,0,0, 0,2,0, 0,0,6,
+        3,0,1, 0,0,0, 0,8,0,
+        2,0,7, 0,0,0, 6,0,0,
+        0,0,0, 0,0,6, 1,3,9
+    ]);
+}
+
+// The example puzzle from Wikipedia
+pub fn classic() -> SudokuBoard {
+    return SudokuBoard::new(&[
+        5,3,0, 0,7,0, 0,0,0,
+        6,0,0, 1,9,5, 0,0,0,
+        0,9,8, 0,0,0, 0,6,0,
+        8,0,0, 0,6,0, 0,0,3,
+        4,0,0, 8,0,3, 0,0,1,
+        7,0,0, 0,2,0, 0,0,6,
+        0,6,0, 0,0,0, 2,8,0,
+        0,0,0, 4,1,9, 0,0,5,
+        0,0,0, 0,8,0, 0,7,9
+    ]);
+}
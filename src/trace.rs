@@ -0,0 +1,187 @@
+use crate::json::{ JsonValue, ToJson };
+use crate::sudoku_board::SudokuBoard;
+use crate::sudoku_solver::{ SolveControl, SolveError, SudokuSolver };
+
+// Bumped whenever a field of the exported trace changes meaning or goes away, new fields don't need a new version
+pub const TRACE_FORMAT_VERSION: u64 = 1;
+
+// One move of the backtracking search. `iteration` counts the search's loop turns from 0, so several steps can share
+// one when a retraction is immediately followed by the next value for the same space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(tag = "type", rename_all = "snake_case"))]
+pub enum SolveStep {
+    Place {
+        row: usize,
+        #[cfg_attr(feature = "serde", serde(rename = "col"))]
+        column: usize,
+        value: u8,
+        iteration: u64
+    },
+    // `value` is cleared from the space again
+    Retract {
+        row: usize,
+        #[cfg_attr(feature = "serde", serde(rename = "col"))]
+        column: usize,
+        value: u8,
+        iteration: u64
+    }
+}
+
+impl ToJson for SolveStep {
+    fn to_json(&self) -> JsonValue {
+        let (step_type, row, column, value, iteration) = match *self {
+            SolveStep::Place { row, column, value, iteration } => ("place", row, column, value, iteration),
+            SolveStep::Retract { row, column, value, iteration } => ("retract", row, column, value, iteration)
+        };
+        return JsonValue::Object(vec![
+            (String::from("type"), step_type.into()),
+            (String::from("row"), row.into()),
+            (String::from("col"), column.into()),
+            (String::from("value"), value.into()),
+            (String::from("iteration"), iteration.into())
+        ]);
+    }
+}
+
+impl SudokuSolver {
+    // Solves a copy of the board with the iterative engine, recording every placement and retraction. The trace only
+    // exists for the iterative engine, whatever the solver's own configuration is.
+    pub fn solve_traced(&self) -> Result<(SudokuBoard, Vec<SolveStep>), SolveError> {
+        return self.solve_traced_with_control(&SolveControl::new());
+    }
+
+    pub fn solve_traced_with_control(&self, control: &SolveControl) -> Result<(SudokuBoard, Vec<SolveStep>), SolveError> {
+        let mut board = SudokuBoard::copy(&self.board);
+        let mut steps = Vec::new();
+        SudokuSolver::run_iterative_recording(&mut board, &self.unsolved_spaces, control, &mut |step| steps.push(step))?;
+        return Ok((board, steps));
+    }
+
+    // `{"version", "puzzle", "config", "stats", "steps"}`. `puzzle` is the 81 character line with `0` for blanks,
+    // `config.engine` names the engine the trace comes from, `stats` counts placements, retractions and iterations,
+    // and `steps` holds one `{"type": "place" | "retract", "row", "col", "value", "iteration"}` object per step with
+    // rows and columns counting from 0.
+    pub fn export_trace_json(&self) -> Result<String, SolveError> {
+        let (_, steps) = self.solve_traced()?;
+        let placements = steps.iter().filter(|step| matches!(step, SolveStep::Place { .. })).count();
+        let iterations = match steps.last() {
+            Some(SolveStep::Place { iteration, .. }) | Some(SolveStep::Retract { iteration, .. }) => iteration + 1,
+            None => 0
+        };
+
+        let trace = JsonValue::Object(vec![
+            (String::from("version"), TRACE_FORMAT_VERSION.into()),
+            (String::from("puzzle"), self.board.to_line().into()),
+            (String::from("config"), JsonValue::Object(vec![(String::from("engine"), "iterative".into())])),
+            (String::from("stats"), JsonValue::Object(vec![
+                (String::from("placements"), placements.into()),
+                (String::from("retractions"), (steps.len() - placements).into()),
+                (String::from("iterations"), iterations.into())
+            ])),
+            (String::from("steps"), JsonValue::Array(steps.iter().map(SolveStep::to_json).collect()))
+        ]);
+        return Ok(trace.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::medium;
+
+    #[test]
+    fn solve_traced_works() {
+        let solver = SudokuSolver::new(&medium());
+
+        let (solved_board, steps) = solver.solve_traced().unwrap();
+
+        assert_eq!(solved_board, solver.solve());
+        assert!(matches!(steps[0], SolveStep::Place { row: 0, column: 2, iteration: 0, .. }));
+        assert!(steps.iter().any(|step| matches!(step, SolveStep::Retract { .. })));
+    }
+
+    #[test]
+    fn solve_traced_honors_control() {
+        let solver = SudokuSolver::new(&medium());
+        let control = SolveControl::new();
+
+        let (_, steps) = solver.solve_traced_with_control(&control).unwrap();
+        assert!(matches!(steps.last(), Some(SolveStep::Place { iteration, .. }) if iteration + 1 == control.iterations()));
+
+        // AI Escargot, which takes far more loop turns than one cancellation check
+        let escargot = SudokuSolver::new(&SudokuBoard::new(&[
+            1,0,0, 0,0,7, 0,9,0,
+            0,3,0, 0,2,0, 0,0,8,
+            0,0,9, 6,0,0, 5,0,0,
+            0,0,5, 3,0,0, 9,0,0,
+            0,1,0, 0,8,0, 0,0,2,
+            6,0,0, 0,0,4, 0,0,0,
+            3,0,0, 0,0,0, 0,1,0,
+            0,4,0, 0,0,0, 0,0,7,
+            0,0,7, 0,0,0, 3,0,0
+        ]));
+        let cancelled = SolveControl::new();
+        cancelled.cancel();
+        assert_eq!(escargot.solve_traced_with_control(&cancelled).err(), Some(SolveError::Cancelled));
+    }
+
+    #[test]
+    fn export_trace_json_replays_to_solution() {
+        let solver = SudokuSolver::new(&medium());
+
+        let trace: serde_json::Value = serde_json::from_str(&solver.export_trace_json().unwrap()).unwrap();
+
+        assert_eq!(trace["version"], TRACE_FORMAT_VERSION);
+        assert_eq!(trace["puzzle"], solver.board.to_line());
+        assert_eq!(trace["config"]["engine"], "iterative");
+
+        let steps = trace["steps"].as_array().unwrap();
+        let count = |name: &str| trace["stats"][name].as_u64().unwrap() as usize;
+        assert_eq!(count("placements") + count("retractions"), steps.len());
+
+        let mut board = SudokuBoard::copy(&solver.board);
+        for step in steps {
+            let field = |name: &str| step[name].as_u64().unwrap() as usize;
+            match step["type"].as_str() {
+                Some("place") => board.set_value(field("row"), field("col"), field("value") as u8),
+                Some("retract") => board.clear_value(field("row"), field("col")),
+                other => panic!("Unexpected step type {:?}", other)
+            }
+        }
+        assert_eq!(board, solver.solve());
+    }
+
+    #[test]
+    fn export_trace_json_no_solution() {
+        // The top right space needs a 9, which its column already has
+        let solver = SudokuSolver::new(&SudokuBoard::new(&[
+            1,2,3, 4,5,6, 7,8,0,
+            0,0,0, 0,0,0, 0,0,9,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0,
+            0,0,0, 0,0,0, 0,0,0
+        ]));
+
+        assert!(matches!(solver.export_trace_json(), Err(SolveError::NoSolution(_))));
+    }
+
+    #[test]
+    fn solve_step_to_json() {
+        let steps = [SolveStep::Place { row: 0, column: 2, value: 3, iteration: 0 }, SolveStep::Retract { row: 8, column: 8, value: 9, iteration: 41 }];
+        let json: Vec<String> = steps.iter().map(|step| step.to_json().to_string()).collect();
+
+        assert_eq!(json, vec!["{\"type\":\"place\",\"row\":0,\"col\":2,\"value\":3,\"iteration\":0}", "{\"type\":\"retract\",\"row\":8,\"col\":8,\"value\":9,\"iteration\":41}"]);
+        #[cfg(feature = "serde")]
+        {
+            for (step, json) in steps.iter().zip(json.iter()) {
+                assert_eq!(&serde_json::to_string(step).unwrap(), json);
+                assert_eq!(serde_json::from_str::<SolveStep>(json).unwrap(), *step);
+            }
+            assert!(serde_json::from_str::<SolveStep>(&json[0].replace("place", "guess")).is_err());
+        }
+    }
+}
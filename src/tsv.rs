@@ -0,0 +1,117 @@
+use std::error::Error;
+use std::fmt::{ self, Display, Formatter };
+use crate::sudoku_board::SudokuBoard;
+
+// 9x9 ranges pasted out of a spreadsheet: one line per row, cells separated by tabs, blanks as empty fields. Rows and
+// columns in the errors count from 1 like the spreadsheet's own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TsvParseError {
+    Cell { row: usize, column: usize, text: String }, // Anything other than a blank, `0`, `.` or a digit 1 to 9
+    TooManyCells { row: usize, cells: usize }, // Non-empty fields past the ninth column
+    RowCount(usize) // Rows that have at least one non-empty field, when that's more than 9
+}
+
+impl Display for TsvParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        return match self {
+            TsvParseError::Cell { row, column, text } => write!(f, "row {}, column {}: expected a digit or a blank, found {:?}", row, column, text),
+            TsvParseError::TooManyCells { row, cells } => write!(f, "row {}: expected at most 9 cells, found {}", row, cells),
+            TsvParseError::RowCount(rows) => write!(f, "expected at most 9 rows, found {}", rows)
+        };
+    }
+}
+
+impl Error for TsvParseError {}
+
+impl SudokuBoard {
+    // Fields are split on tabs only, so an empty field keeps its position. Each field may carry surrounding whitespace.
+    // Spreadsheets drop trailing empty cells and can drop an entirely empty last row, so short rows and missing rows are
+    // blanks, and lines may end in `\r\n`.
+    pub fn parse_tsv(text: &str) -> Result<SudokuBoard, TsvParseError> {
+        let lines: Vec<&str> = text.split('\n').map(|line| line.strip_suffix('\r').unwrap_or(line)).collect();
+        let row_count = lines.iter().rposition(|line| !line.trim().is_empty()).map_or(0, |last_row| last_row + 1);
+        if row_count > 9 {
+            return Err(TsvParseError::RowCount(row_count));
+        }
+
+        let mut puzzle = [0; 81];
+        for (row_index, line) in lines.iter().take(row_count).enumerate() {
+            let fields: Vec<&str> = line.split('\t').collect();
+            let cell_count = fields.iter().rposition(|field| !field.trim().is_empty()).map_or(0, |last_column| last_column + 1);
+            if cell_count > 9 {
+                return Err(TsvParseError::TooManyCells { row: row_index + 1, cells: cell_count });
+            }
+
+            for (column_index, field) in fields.iter().take(cell_count).enumerate() {
+                puzzle[9 * row_index + column_index] = match field.trim() {
+                    "" | "." => 0,
+                    digit if digit.len() == 1 && digit.as_bytes()[0].is_ascii_digit() => digit.as_bytes()[0] - b'0',
+                    text => return Err(TsvParseError::Cell { row: row_index + 1, column: column_index + 1, text: String::from(text) })
+                };
+            }
+        }
+        return Ok(SudokuBoard::new(&puzzle));
+    }
+
+    // Nine lines of nine tab-separated cells, blanks left empty
+    pub fn to_tsv(&self) -> String {
+        let mut tsv = String::new();
+        for row_index in 0..=8 {
+            let cells: Vec<String> = self.row_view(row_index).iter().map(|value| if value == 0 { String::new() } else { value.to_string() }).collect();
+            tsv.push_str(&cells.join("\t"));
+            tsv.push('\n');
+        }
+        return tsv;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn puzzle() -> SudokuBoard {
+        return SudokuBoard::new(&[
+            5,3,0, 0,7,0, 0,0,0,
+            6,0,0, 1,9,5, 0,0,0,
+            0,9,8, 0,0,0, 0,6,0,
+            8,0,0, 0,6,0, 0,0,3,
+            4,0,0, 8,0,3, 0,0,1,
+            7,0,0, 0,2,0, 0,0,6,
+            0,6,0, 0,0,0, 2,8,0,
+            0,0,0, 4,1,9, 0,0,5,
+            0,0,0, 0,8,0, 0,7,0
+        ]);
+    }
+
+    #[test]
+    fn tsv_round_trip() {
+        let tsv = puzzle().to_tsv();
+
+        assert_eq!(tsv.lines().next(), Some("5\t3\t\t\t7\t\t\t\t"));
+        assert_eq!(SudokuBoard::parse_tsv(&tsv), Ok(puzzle()));
+        assert_eq!(SudokuBoard::parse_tsv(&SudokuBoard::new(&[0; 81]).to_tsv()), Ok(SudokuBoard::new(&[0; 81])));
+    }
+
+    #[test]
+    fn parse_tsv_messy_paste() {
+        // Windows line endings, padded numbers, a `0`, trailing tabs dropped on some rows and added on another, and the
+        // last row's only value sitting in the middle
+        let paste = "5\t3\t\t\t7\r\n6\t \t\t1\t9\t5\t\t\t\t\t\r\n\t9\t8\t\t\t\t\t6\t\r\n 8 \t\t\t\t6\t\t\t\t3\r\n4\t\t\t8\t\t3\t\t\t1\r\n7\t\t\t0\t2\t\t\t\t6\r\n\t6\t\t\t\t\t2\t8\r\n\t\t\t4\t1\t9\t\t\t5\r\n\t\t\t\t8\t\t\t7\t\r\n";
+
+        assert_eq!(SudokuBoard::parse_tsv(paste), Ok(puzzle()));
+    }
+
+    #[test]
+    fn parse_tsv_errors() {
+        let mut bad_cell = puzzle().to_tsv();
+        bad_cell.replace_range(0..1, "x");
+        let mut two_digits = puzzle().to_tsv();
+        two_digits.replace_range(0..1, "15");
+
+        assert_eq!(SudokuBoard::parse_tsv(&bad_cell), Err(TsvParseError::Cell { row: 1, column: 1, text: String::from("x") }));
+        assert_eq!(SudokuBoard::parse_tsv(&two_digits), Err(TsvParseError::Cell { row: 1, column: 1, text: String::from("15") }));
+        assert_eq!(SudokuBoard::parse_tsv("1\t2\t3\t4\t5\t6\t7\t8\t9\t1"), Err(TsvParseError::TooManyCells { row: 1, cells: 10 }));
+        assert_eq!(SudokuBoard::parse_tsv(&format!("{}1\n", puzzle().to_tsv())), Err(TsvParseError::RowCount(10)));
+        assert_eq!(TsvParseError::Cell { row: 2, column: 3, text: String::from("x") }.to_string(), "row 2, column 3: expected a digit or a blank, found \"x\"");
+    }
+}
@@ -0,0 +1,332 @@
+#[cfg(feature = "parallel")]
+use std::sync::atomic::{ AtomicUsize, Ordering };
+use crate::sudoku_board::{ SudokuBoard, NONET_OF };
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Uniqueness {
+    NoSolution, // Also the answer for boards that already break the rules
+    Unique,
+    Multiple
+}
+
+// Every house as its 9 space indices: rows, then columns, then nonets
+pub(crate) const HOUSES: [[usize; 9]; 27] = houses_table();
+
+const fn houses_table() -> [[usize; 9]; 27] {
+    let mut table = [[0; 9]; 27];
+    let mut house = 0;
+    while house < 9 {
+        let mut offset = 0;
+        while offset < 9 {
+            table[house][offset] = 9 * house + offset;
+            table[9 + house][offset] = 9 * offset + house;
+            table[18 + house][offset] = 9 * (3 * (house / 3) + offset / 3) + 3 * (house % 3) + offset % 3;
+            offset += 1;
+        }
+        house += 1;
+    }
+    return table;
+}
+
+const ALL_VALUES: u16 = 0b11_1111_1110;
+
+// Just enough state to search with, small enough that branching copies it instead of undoing moves
+#[derive(Clone, Copy)]
+struct Grid {
+    used: [u16; 27], // Bit `n` is set when `n` is placed in the house, indexed like `HOUSES`
+    empties: u128
+}
+
+impl Grid {
+    // `None` when the givens already clash
+    fn new(board: &SudokuBoard) -> Option<Grid> {
+        let mut grid = Grid { used: [0; 27], empties: board.empties_bitboard() };
+        for (index, value) in board.to_array().iter().enumerate() {
+            if *value != 0 && !grid.place(index, *value) {
+                return None;
+            }
+        }
+        return Some(grid);
+    }
+
+    fn candidates(&self, index: usize) -> u16 {
+        return ALL_VALUES & !(self.used[index / 9] | self.used[9 + index % 9] | self.used[18 + NONET_OF[index]]);
+    }
+
+    // `false` when the value is already used in one of the space's houses
+    fn place(&mut self, index: usize, value: u8) -> bool {
+        let bit = 1 << value;
+        let houses = [index / 9, 9 + index % 9, 18 + NONET_OF[index]];
+        if houses.iter().any(|house| self.used[*house] & bit != 0) {
+            return false;
+        }
+
+        for house in houses.iter() {
+            self.used[*house] |= bit;
+        }
+        self.empties &= !(1 << index);
+        return true;
+    }
+
+    // Fills naked and hidden singles until neither is left. Errors on a contradiction, otherwise returns the empty
+    // space with the fewest candidates to branch on, or `None` once the grid is full.
+    fn propagate(&mut self) -> Result<Option<(usize, u16)>, ()> {
+        loop {
+            let mut progress = false;
+            let mut best: Option<(usize, u16)> = None;
+
+            let mut empties = self.empties;
+            while empties != 0 {
+                let index = empties.trailing_zeros() as usize;
+                empties &= empties - 1;
+
+                let candidates = self.candidates(index);
+                match candidates.count_ones() {
+                    0 => return Err(()),
+                    1 => {
+                        if !self.place(index, candidates.trailing_zeros() as u8) {
+                            return Err(());
+                        }
+                        progress = true;
+                    },
+                    count => if best.map_or(true, |(_, best_candidates)| count < best_candidates.count_ones()) {
+                        best = Some((index, candidates));
+                    }
+                }
+            }
+            if progress {
+                continue;
+            }
+
+            for (house_index, house) in HOUSES.iter().enumerate() {
+                let (mut once, mut twice) = (0u16, 0u16);
+                for &index in house.iter().filter(|index| self.empties & (1 << **index) != 0) {
+                    let candidates = self.candidates(index);
+                    twice |= once & candidates;
+                    once |= candidates;
+                }
+                if (once | self.used[house_index]) != ALL_VALUES {
+                    return Err(()); // Some value has nowhere left to go
+                }
+
+                let mut hidden = once & !twice;
+                while hidden != 0 {
+                    let value = hidden.trailing_zeros() as u8;
+                    hidden &= hidden - 1;
+                    let index = *house.iter().find(|index| self.empties & (1 << **index) != 0 && self.candidates(**index) & (1 << value) != 0).ok_or(())?;
+                    if !self.place(index, value) {
+                        return Err(());
+                    }
+                    progress = true;
+                }
+            }
+            if !progress {
+                return Ok(best);
+            }
+        }
+    }
+}
+
+// Counts solutions up to 2 with singles propagation and fewest-candidates branching, without any of the solver's
+// caching or bookkeeping. Meant for the tight loops of puzzle generation, it gives the same answer as
+// `count_solutions(2)` on a solver for the same board.
+pub fn is_unique(board: &SudokuBoard) -> Uniqueness {
+    return match Grid::new(board) {
+        Some(grid) => uniqueness_of(count(grid, 0)),
+        None => Uniqueness::NoSolution
+    };
+}
+
+// The same answer as `is_unique`, with the branches at the first guess searched as separate rayon tasks. All of them
+// add to one shared count and stop once it reaches 2, so the answer doesn't depend on which task finds what first.
+// Handing the branches to the pool costs about as much as a short search, so this pays off on boards with long ones.
+#[cfg(feature = "parallel")]
+pub fn is_unique_parallel(board: &SudokuBoard) -> Uniqueness {
+    let mut grid = match Grid::new(board) {
+        Some(grid) => grid,
+        None => return Uniqueness::NoSolution
+    };
+    let (index, mut candidates) = match grid.propagate() {
+        Err(()) => return Uniqueness::NoSolution,
+        Ok(None) => return Uniqueness::Unique,
+        Ok(Some(branch)) => branch
+    };
+
+    let found = AtomicUsize::new(0);
+    rayon::scope(|scope| {
+        while candidates != 0 {
+            let value = candidates.trailing_zeros() as u8;
+            candidates &= candidates - 1;
+
+            let mut branch = grid;
+            branch.place(index, value);
+            let found = &found;
+            scope.spawn(move |_| count_shared(branch, found));
+        }
+    });
+    return uniqueness_of(found.into_inner());
+}
+
+// `is_unique` giving up with `None` once it would take more than `budget` guesses, for screening boards whose search
+// could run long
+pub fn is_unique_within(board: &SudokuBoard, budget: u64) -> Option<Uniqueness> {
+    let mut remaining = budget;
+    return match Grid::new(board) {
+        Some(grid) => count_within(grid, 0, &mut remaining).map(uniqueness_of),
+        None => Some(Uniqueness::NoSolution)
+    };
+}
+
+fn uniqueness_of(solutions: usize) -> Uniqueness {
+    return match solutions {
+        0 => Uniqueness::NoSolution,
+        1 => Uniqueness::Unique,
+        _ => Uniqueness::Multiple
+    };
+}
+
+fn count(mut grid: Grid, found: usize) -> usize {
+    let (index, mut candidates) = match grid.propagate() {
+        Err(()) => return found,
+        Ok(None) => return found + 1,
+        Ok(Some(branch)) => branch
+    };
+
+    let mut found = found;
+    while candidates != 0 && found < 2 {
+        let value = candidates.trailing_zeros() as u8;
+        candidates &= candidates - 1;
+
+        let mut branch = grid;
+        branch.place(index, value);
+        found = count(branch, found);
+    }
+    return found;
+}
+
+// `count` spending one of `remaining` on every guess, `None` once they run out
+fn count_within(mut grid: Grid, found: usize, remaining: &mut u64) -> Option<usize> {
+    let (index, mut candidates) = match grid.propagate() {
+        Err(()) => return Some(found),
+        Ok(None) => return Some(found + 1),
+        Ok(Some(branch)) => branch
+    };
+
+    let mut found = found;
+    while candidates != 0 && found < 2 {
+        if *remaining == 0 {
+            return None;
+        }
+        *remaining -= 1;
+        let value = candidates.trailing_zeros() as u8;
+        candidates &= candidates - 1;
+
+        let mut branch = grid;
+        branch.place(index, value);
+        found = count_within(branch, found, remaining)?;
+    }
+    return Some(found);
+}
+
+// `count` for one of several threads sharing `found`
+#[cfg(feature = "parallel")]
+fn count_shared(mut grid: Grid, found: &AtomicUsize) {
+    let (index, mut candidates) = match grid.propagate() {
+        Err(()) => return,
+        Ok(None) => {
+            found.fetch_add(1, Ordering::Relaxed);
+            return;
+        },
+        Ok(Some(branch)) => branch
+    };
+
+    while candidates != 0 && found.load(Ordering::Relaxed) < 2 {
+        let value = candidates.trailing_zeros() as u8;
+        candidates &= candidates - 1;
+
+        let mut branch = grid;
+        branch.place(index, value);
+        count_shared(branch, found);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategies::{ invalid_board, masked_puzzle };
+    use crate::sudoku_solver::SudokuSolver;
+
+    #[test]
+    fn is_unique_works() {
+        let mut unsolvable = [0; 81];
+        unsolvable[..8].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        unsolvable[17] = 9;
+
+        assert_eq!(is_unique(&SudokuBoard::new(&[0; 81])), Uniqueness::Multiple);
+        assert_eq!(is_unique(&masked_puzzle(3, 0)), Uniqueness::Unique);
+        assert_eq!(is_unique(&SudokuBoard::new(&unsolvable)), Uniqueness::NoSolution);
+        assert_eq!(is_unique(&invalid_board(3)), Uniqueness::NoSolution);
+    }
+
+    // The general solver's answer for the same board
+    fn expected(board: &SudokuBoard) -> Uniqueness {
+        return match SudokuSolver::new(board).count_solutions(2) {
+            0 => Uniqueness::NoSolution,
+            1 => Uniqueness::Unique,
+            _ => Uniqueness::Multiple
+        };
+    }
+
+    #[test]
+    fn is_unique_matches_count_solutions() {
+        for seed in 0..200 {
+            let board = masked_puzzle(seed, 45 + (seed % 15) as usize);
+
+            assert_eq!(is_unique(&board), expected(&board), "{}", board.to_line());
+        }
+    }
+
+    #[test]
+    fn is_unique_within_budget() {
+        for seed in 0..100 {
+            let board = masked_puzzle(seed, 45 + (seed % 26) as usize);
+
+            assert_eq!(is_unique_within(&board, u64::MAX), Some(is_unique(&board)), "{}", board.to_line());
+        }
+        assert_eq!(is_unique_within(&SudokuBoard::new(&[0; 81]), 10), None);
+        assert_eq!(is_unique_within(&masked_puzzle(3, 0), 0), Some(Uniqueness::Unique)); // Nothing to guess
+        assert_eq!(is_unique_within(&invalid_board(3), 0), Some(Uniqueness::NoSolution));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn is_unique_parallel_matches_is_unique() {
+        for seed in 0..200 {
+            // Up to 70 holes, so plenty of the boards have more than one solution
+            let board = if seed % 20 == 0 { invalid_board(seed) } else { masked_puzzle(seed, 45 + (seed % 26) as usize) };
+
+            assert_eq!(is_unique_parallel(&board), is_unique(&board), "{}", board.to_line());
+        }
+        assert_eq!(is_unique_parallel(&SudokuBoard::new(&[0; 81])), Uniqueness::Multiple);
+        assert_eq!(is_unique_parallel(&masked_puzzle(3, 0)), Uniqueness::Unique);
+    }
+
+    #[cfg(feature = "datasets")]
+    #[test]
+    fn is_unique_matches_datasets() {
+        use crate::datasets;
+
+        for puzzles in [datasets::easy(), datasets::medium(), datasets::hard(), datasets::pathological()].iter() {
+            for board in datasets::load(puzzles).unwrap() {
+                // The first given emptied as well, which may or may not keep the solution unique
+                let mut reduced_board = SudokuBoard::copy(&board);
+                let (row_index, column_index) = (0..81).map(|index| (index / 9, index % 9)).find(|space| board[*space] != 0).unwrap();
+                reduced_board.clear_value(row_index, column_index);
+
+                for board in [board, reduced_board].iter() {
+                    assert_eq!(is_unique(board), expected(board), "{}", board.to_line());
+                }
+            }
+        }
+    }
+}
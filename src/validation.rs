@@ -0,0 +1,320 @@
+use std::fmt::{ self, Display, Formatter };
+use std::io::BufRead;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use crate::analysis::next_combination;
+use crate::file_io::{ ParseErrorAt, PuzzleReader };
+use crate::sudoku_board::{ Conflict, SudokuBoard, ValidationReport };
+use crate::uniqueness::{ is_unique, is_unique_within, Uniqueness };
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolutionCount {
+    NotCounted,
+    Counted(Uniqueness), // Boards with conflicts are always `NoSolution`
+    OverBudget // The search needed more guesses than `ValidationOptions::guess_budget` allowed
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidationOptions {
+    pub count_solutions: bool,
+    pub guess_budget: Option<u64> // Per board, `None` searches as long as it takes
+}
+
+impl Default for ValidationOptions {
+    fn default() -> ValidationOptions {
+        return ValidationOptions { count_solutions: true, guess_budget: None };
+    }
+}
+
+impl ValidationOptions {
+    pub fn new() -> ValidationOptions {
+        return ValidationOptions::default();
+    }
+
+    pub fn count_solutions(mut self, count_solutions: bool) -> ValidationOptions {
+        self.count_solutions = count_solutions;
+        return self;
+    }
+
+    pub fn guess_budget(mut self, guess_budget: Option<u64>) -> ValidationOptions {
+        self.guess_budget = guess_budget;
+        return self;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationSummary {
+    pub report: ValidationReport,
+    pub clues: usize,
+    pub solutions: SolutionCount
+}
+
+impl ValidationSummary {
+    // No conflicts and exactly one solution, `false` whenever the solutions weren't counted
+    pub fn is_sound(&self) -> bool {
+        return self.report.is_valid() && self.solutions == SolutionCount::Counted(Uniqueness::Unique);
+    }
+}
+
+pub fn validate(board: &SudokuBoard, options: &ValidationOptions) -> ValidationSummary {
+    let report = board.validate();
+    let solutions = match (options.count_solutions, options.guess_budget) {
+        (false, _) => SolutionCount::NotCounted,
+        (true, _) if !report.is_valid() => SolutionCount::Counted(Uniqueness::NoSolution),
+        (true, None) => SolutionCount::Counted(is_unique(board)),
+        (true, Some(budget)) => is_unique_within(board, budget).map_or(SolutionCount::OverBudget, SolutionCount::Counted)
+    };
+    return ValidationSummary { report, clues: 81 - board.unsolved_count(), solutions };
+}
+
+// Summaries in the same order as `boards`, with the default options. Nothing panics on a broken board, every problem
+// ends up in its summary.
+pub fn validate_many(boards: &[SudokuBoard]) -> Vec<ValidationSummary> {
+    return validate_many_with(boards, &ValidationOptions::default());
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn validate_many_with(boards: &[SudokuBoard], options: &ValidationOptions) -> Vec<ValidationSummary> {
+    return boards.iter().map(|board| validate(board, options)).collect();
+}
+
+// Spread across all cores like `rate_batch`
+#[cfg(feature = "parallel")]
+pub fn validate_many_with(boards: &[SudokuBoard], options: &ValidationOptions) -> Vec<ValidationSummary> {
+    return boards.par_iter().map(|board| validate(board, options)).collect();
+}
+
+// Every puzzle `reader` yields, keyed by the line it starts on. Puzzles that don't parse keep their error in place of a
+// summary. An I/O error ends the list like it ends the reader.
+pub fn validate_reader<R: BufRead>(mut reader: PuzzleReader<R>, options: &ValidationOptions) -> Vec<(usize, Result<ValidationSummary, ParseErrorAt>)> {
+    let mut lines = Vec::new();
+    let mut boards = Vec::new();
+    let mut errors = Vec::new();
+    while let Some(result) = reader.next() {
+        match result {
+            Ok(board) => {
+                lines.push(reader.puzzle_line());
+                boards.push(board);
+            },
+            Err(error) => errors.push(error)
+        }
+    }
+
+    let mut summaries: Vec<(usize, Result<ValidationSummary, ParseErrorAt>)> = lines.into_iter()
+        .zip(validate_many_with(&boards, options))
+        .map(|(line, summary)| (line, Ok(summary)))
+        .collect();
+    for error in errors {
+        let line = match error {
+            ParseErrorAt::Io { line, .. } | ParseErrorAt::Parse { line, .. } => line
+        };
+        summaries.push((line, Err(error)));
+    }
+    summaries.sort_by_key(|(line, _)| *line);
+    return summaries;
+}
+
+// One space whose value is most likely wrong, with every conflict it's part of
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidityExplanation {
+    pub row: usize,
+    pub column: usize,
+    pub value: u8,
+    pub conflicts: Vec<Conflict>
+}
+
+impl Display for InvalidityExplanation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let houses: Vec<String> = self.conflicts.iter().map(|conflict| conflict.house.label()).collect();
+        return write!(f, "Removing R{}C{}={} resolves the conflicts in {}", self.row + 1, self.column + 1, self.value, houses.join(", "));
+    }
+}
+
+// Sets of up to this many spaces are searched exhaustively, larger ones are picked greedily
+const MAX_EXACT_REMOVALS: usize = 3;
+
+impl SudokuBoard {
+    // The fewest spaces whose removal leaves no conflicts, one explanation each in row-major order, empty for a valid
+    // board. Among equally small sets the one whose spaces take part in the most conflicts wins, so a single misread
+    // value clashing in its row, column and nonet is named instead of the three spaces it clashes with. Past
+    // `MAX_EXACT_REMOVALS` spaces the set is built by repeatedly removing the space in the most unresolved conflicts,
+    // which is small but not always the smallest.
+    pub fn explain_invalidity(&self) -> Vec<InvalidityExplanation> {
+        let conflicts = self.validate().conflicts;
+        let mut suspects: Vec<usize> = conflicts.iter()
+            .flat_map(|conflict| conflict.spaces.iter().map(|(row_index, column_index)| 9 * row_index + column_index))
+            .collect();
+        suspects.sort_unstable();
+        suspects.dedup();
+
+        let removals = (1..=MAX_EXACT_REMOVALS.min(suspects.len()))
+            .find_map(|size| smallest_removal(&conflicts, &suspects, size))
+            .unwrap_or_else(|| greedy_removal(&conflicts));
+        return removals.into_iter().map(|index| {
+            let (row, column) = (index / 9, index % 9);
+            InvalidityExplanation {
+                row,
+                column,
+                value: self[(row, column)],
+                conflicts: conflicts.iter().filter(|conflict| conflict.spaces.contains(&(row, column))).cloned().collect()
+            }
+        }).collect();
+    }
+}
+
+// Whether no conflict keeps more than one of its spaces once `removed` are gone
+fn resolves(conflicts: &[Conflict], removed: &[usize]) -> bool {
+    return conflicts.iter().all(|conflict| conflict.spaces.iter().filter(|(row_index, column_index)| !removed.contains(&(9 * row_index + column_index))).count() <= 1);
+}
+
+// How many conflicts the spaces take part in, counting a conflict once per space
+fn involvement(conflicts: &[Conflict], removed: &[usize]) -> usize {
+    return conflicts.iter().map(|conflict| conflict.spaces.iter().filter(|(row_index, column_index)| removed.contains(&(9 * row_index + column_index))).count()).sum();
+}
+
+// The best resolving set of exactly `size` suspects, visited in lexicographic order so ties go to the first
+fn smallest_removal(conflicts: &[Conflict], suspects: &[usize], size: usize) -> Option<Vec<usize>> {
+    let mut positions: Vec<usize> = (0..size).collect();
+    let mut best: Option<(usize, Vec<usize>)> = None;
+    loop {
+        let removed: Vec<usize> = positions.iter().map(|position| suspects[*position]).collect();
+        if resolves(conflicts, &removed) {
+            let score = involvement(conflicts, &removed);
+            if best.as_ref().map_or(true, |(best_score, _)| score > *best_score) {
+                best = Some((score, removed));
+            }
+        }
+
+        if !next_combination(&mut positions, suspects.len()) {
+            return best.map(|(_, removed)| removed);
+        }
+    }
+}
+
+fn greedy_removal(conflicts: &[Conflict]) -> Vec<usize> {
+    let mut removed = Vec::new();
+    while !resolves(conflicts, &removed) {
+        let mut counts = [0; 81];
+        for conflict in conflicts.iter() {
+            let remaining: Vec<usize> = conflict.spaces.iter().map(|(row_index, column_index)| 9 * row_index + column_index).filter(|index| !removed.contains(index)).collect();
+            if remaining.len() > 1 {
+                remaining.iter().for_each(|index| counts[*index] += 1);
+            }
+        }
+        // `max_by_key` keeps the last of equal counts, reversing keeps the first
+        let next = (0..81).rev().max_by_key(|index| counts[*index]).unwrap();
+        removed.push(next);
+    }
+    removed.sort_unstable();
+    return removed;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::{ RandomSource, SplitMix64 };
+    use crate::strategies::masked_puzzle;
+    use crate::sudoku_board::House;
+
+    // A unique puzzle, the same with a duplicated 1 in its top row, one without a solution, a parse error, and the
+    // unique puzzle again with three clues taken out so it has several solutions
+    const FIXTURE: &str = "\
+# Imported collection
+78.4..12.6...75..9...6.1.78..7.4.26...1.5.93.9.4.6...5.7.3...1212...74...492.6..7
+78.4..1216...75..9...6.1.78..7.4.26...1.5.93.9.4.6...5.7.3...1212...74...492.6..7
+
+12345678.........9...............................................................
+78.4..12.6...75..9...6.1.78..7.4.26...1.5.93.9.4.6...5.7.3...1212...74...492.6..x
+...4..12.....75..9...6.1.78..7.4.26...1.5.93.9.4.6...5.7.3...1212...74...492.6..7
+";
+
+    #[test]
+    fn validate_reader_classifies_each_puzzle() {
+        let summaries = validate_reader(PuzzleReader::new(FIXTURE.as_bytes()), &ValidationOptions::new());
+        let lines: Vec<usize> = summaries.iter().map(|(line, _)| *line).collect();
+        assert_eq!(lines, vec![2, 3, 5, 6, 7]);
+
+        let summary = |index: usize| summaries[index].1.as_ref().unwrap();
+        assert!(summary(0).is_sound());
+        assert_eq!(summary(0).clues, 38);
+
+        assert_eq!(summary(1).report.conflicts.len(), 2); // Its row and its nonet
+        assert!(summary(1).report.conflicts.iter().all(|conflict| conflict.value == 1));
+        assert_eq!(summary(1).solutions, SolutionCount::Counted(Uniqueness::NoSolution));
+
+        assert!(summary(2).report.is_valid());
+        assert_eq!(summary(2).clues, 9);
+        assert_eq!(summary(2).solutions, SolutionCount::Counted(Uniqueness::NoSolution));
+
+        match summaries[3].1 {
+            Err(ParseErrorAt::Parse { line: 6, column: 81, .. }) => {},
+            ref other => panic!("Unexpected result {:?}", other)
+        }
+
+        assert!(summary(4).report.is_valid());
+        assert_eq!(summary(4).solutions, SolutionCount::Counted(Uniqueness::Multiple));
+    }
+
+    #[test]
+    fn validate_many_respects_options() {
+        let boards = [SudokuBoard::new(&[0; 81]), SudokuBoard::parse_grid(FIXTURE.lines().nth(1).unwrap()).unwrap()];
+
+        let uncounted = validate_many_with(&boards, &ValidationOptions::new().count_solutions(false));
+        assert!(uncounted.iter().all(|summary| summary.solutions == SolutionCount::NotCounted && !summary.is_sound()));
+
+        let budgeted = validate_many_with(&boards, &ValidationOptions::new().guess_budget(Some(5)));
+        assert_eq!(budgeted[0].solutions, SolutionCount::OverBudget);
+        assert_eq!(budgeted[0].clues, 0);
+        assert_eq!(validate_many(&boards)[1], validate(&boards[1], &ValidationOptions::new()));
+    }
+
+    fn solved() -> SudokuBoard {
+        return SudokuBoard::new(&[
+            6,7,3, 8,9,4, 5,1,2,
+            9,1,2, 7,3,5, 4,8,6,
+            8,4,5, 6,1,2, 9,7,3,
+            7,9,8, 2,6,1, 3,5,4,
+            5,2,6, 4,7,3, 8,9,1,
+            1,3,4, 5,8,9, 2,6,7,
+            4,6,9, 1,2,8, 7,3,5,
+            2,8,7, 3,5,6, 1,4,9,
+            3,5,1, 9,4,7, 6,2,8
+        ]);
+    }
+
+    #[test]
+    fn explain_invalidity_names_corrupted_cell() {
+        let mut board = solved();
+        assert!(board.explain_invalidity().is_empty());
+
+        board.set_value(4, 4, 3);
+        let explanations = board.explain_invalidity();
+        assert_eq!(explanations.len(), 1);
+        assert_eq!((explanations[0].row, explanations[0].column, explanations[0].value), (4, 4, 3));
+        assert_eq!(explanations[0].conflicts.iter().map(|conflict| conflict.house).collect::<Vec<House>>(), vec![House::Row(4), House::Column(4), House::Nonet(4)]);
+        assert_eq!(explanations[0].to_string(), "Removing R5C5=3 resolves the conflicts in R5, C5, B5");
+
+        board.set_value(0, 0, 9);
+        let explanations = board.explain_invalidity();
+        let named: Vec<(usize, usize, u8)> = explanations.iter().map(|explanation| (explanation.row, explanation.column, explanation.value)).collect();
+        assert_eq!(named, vec![(0, 0, 9), (4, 4, 3)]);
+    }
+
+    #[test]
+    fn explained_removals_restore_validity() {
+        for seed in 0..100 {
+            let mut board = masked_puzzle(seed, 30);
+            let mut random = SplitMix64::new(seed);
+            for _ in 0..1 + seed % 8 {
+                board.set_value(random.below(9) as usize, random.below(9) as usize, 1 + random.below(9) as u8);
+            }
+
+            let explanations = board.explain_invalidity();
+            for explanation in explanations.iter() {
+                assert!(!explanation.conflicts.is_empty());
+                board.clear_value(explanation.row, explanation.column);
+            }
+            assert!(board.all_spaces_valid(), "seed {}", seed);
+        }
+    }
+}
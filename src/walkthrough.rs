@@ -0,0 +1,130 @@
+use crate::sudoku_board::SudokuBoard;
+use crate::sudoku_solver::SudokuSolver;
+use crate::techniques::{ logical_solve, Hint, TechniqueSet };
+
+// One placement of the logical solution, with the board as it stands right after it
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WalkthroughStep {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub hint: Hint, // The technique, the spaces looked at, the candidates ruled out and the placement itself
+    #[cfg_attr(feature = "serde", serde(rename = "board"))]
+    pub board_after: String // In the `to_line` form
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(tag = "type", rename_all = "snake_case"))]
+pub enum WalkthroughEnd {
+    Solved,
+    #[cfg_attr(feature = "serde", serde(rename = "stuck"))]
+    StuckAt { board: String, unsolved: usize } // Where the allowed techniques ran out and guessing would be needed
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Walkthrough {
+    pub puzzle: String, // In the `to_line` form
+    pub steps: Vec<WalkthroughStep>,
+    pub end: WalkthroughEnd
+}
+
+impl SudokuSolver {
+    // The full teaching script of the logical solution as `logical_solve` makes it, the same order `rate` uses, until
+    // the board is solved or no allowed technique applies
+    pub fn walkthrough(&self, technique_set: &TechniqueSet) -> Walkthrough {
+        let (hints, board) = logical_solve(&self.board, technique_set);
+        let mut replayed = SudokuBoard::copy(&self.board);
+        let steps = hints.into_iter().map(|hint| {
+            replayed.set_value(hint.row, hint.column, hint.value);
+            return WalkthroughStep { hint, board_after: replayed.to_line() };
+        }).collect();
+
+        let end = if board.all_spaces_solved() {
+            WalkthroughEnd::Solved
+        }
+        else {
+            WalkthroughEnd::StuckAt { board: board.to_line(), unsolved: board.unsolved_count() }
+        };
+        return Walkthrough { puzzle: self.board.to_line(), steps, end };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{ easy, hard };
+    use crate::candidate_board::CandidateBoard;
+    use crate::sudoku_board::House;
+    use crate::techniques::Technique;
+
+    // Checks every step against the board before it, then applies it
+    fn replay(walkthrough: &Walkthrough) -> SudokuBoard {
+        let mut board = SudokuBoard::parse_grid(&walkthrough.puzzle).unwrap();
+        for step in walkthrough.steps.iter() {
+            let candidate_board = CandidateBoard::new(&board);
+            let (space, value) = ((step.hint.row, step.hint.column), step.hint.value);
+            match step.hint.technique {
+                Technique::NakedSingle => assert_eq!(candidate_board.get(space.0, space.1).iter().collect::<Vec<u8>>(), vec![value]),
+                Technique::HiddenSingle => {
+                    let house: House = step.hint.details.house.unwrap();
+                    let spaces: Vec<(usize, usize)> = house.spaces().into_iter().filter(|other| candidate_board.get(other.0, other.1).contains(value)).collect();
+                    assert_eq!(spaces, vec![space]);
+                },
+                Technique::LockedCandidates => assert!(candidate_board.get(space.0, space.1).contains(value)) // A single once the eliminations are made
+            }
+
+            board.set_value(space.0, space.1, value);
+            assert_eq!(board.to_line(), step.board_after);
+        }
+        return board;
+    }
+
+    #[test]
+    fn walkthrough_easy_solves() {
+        let solver = SudokuSolver::new(&easy());
+
+        let walkthrough = solver.walkthrough(&TechniqueSet::all());
+
+        assert_eq!(walkthrough.end, WalkthroughEnd::Solved);
+        assert_eq!(walkthrough.steps.len(), 5);
+        assert_eq!(replay(&walkthrough), solver.solve());
+    }
+
+    #[test]
+    fn walkthrough_hard_gets_stuck() {
+        let walkthrough = SudokuSolver::new(&hard()).walkthrough(&TechniqueSet::all());
+
+        let board = replay(&walkthrough);
+        assert_eq!(walkthrough.end, WalkthroughEnd::StuckAt { board: board.to_line(), unsolved: board.unsolved_count() });
+        assert!(board.unsolved_count() > 0);
+    }
+
+    #[test]
+    fn walkthrough_limited_techniques() {
+        let walkthrough = SudokuSolver::new(&hard()).walkthrough(&TechniqueSet::new().with(Technique::NakedSingle));
+
+        assert!(walkthrough.steps.iter().all(|step| step.hint.technique == Technique::NakedSingle));
+        replay(&walkthrough);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn walkthrough_serde_round_trip() {
+        let walkthrough = SudokuSolver::new(&easy()).walkthrough(&TechniqueSet::all());
+
+        let json = serde_json::to_value(&walkthrough).unwrap();
+
+        let steps = json["steps"].as_array().unwrap();
+        assert_eq!(steps.len(), 5);
+        assert_eq!(steps[0]["technique"], "naked_single");
+        assert_eq!(steps[0]["value"], 6);
+        assert_eq!(steps[4]["board"], SudokuSolver::new(&easy()).solve().to_line());
+        assert_eq!(json["end"], serde_json::json!({ "type": "solved" }));
+        assert_eq!(serde_json::from_value::<Walkthrough>(json).unwrap(), walkthrough);
+
+        let stuck = SudokuSolver::new(&hard()).walkthrough(&TechniqueSet::new().with(Technique::NakedSingle));
+        let json = serde_json::to_value(&stuck).unwrap();
+        assert_eq!(json["end"]["type"], "stuck");
+        assert_eq!(serde_json::from_value::<Walkthrough>(json).unwrap(), stuck);
+    }
+}